@@ -1,9 +1,11 @@
 use crate::models::{ClientConfigInfo, ClientId, ClientSyncStatus, DetectedClient};
+use crate::services::sync_engine::client_sync_status;
 use crate::services::{
-    detect_all_clients, detect_client, get_client_config_info, get_client_config_path,
+    detect_all_clients, detect_client, get_client_config_info, log_warning, reload_custom_clients,
+    watch_targets, ConfigWatcher, DescriptorLoadResult,
 };
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
 use super::config::{AppState, CommandError};
 
@@ -32,41 +34,9 @@ pub fn get_client_status(
         message: e.to_string(),
     })?;
 
-    // Get settings for this client, or create default
-    let settings = config.clients.get(id.as_str());
-
-    // Detect client to get current state
+    // Detect client to get current state, then compare with the last sync
     let detected = detect_client(id);
-
-    // Check if config has been modified externally (by comparing checksums)
-    let externally_modified = if let (Some(settings), true) = (settings, detected.config_exists) {
-        if let Some(last_checksum) = &settings.last_sync_checksum {
-            // Read current file and compute checksum
-            if let Ok(path) = get_client_config_path(id) {
-                if let Ok(content) = std::fs::read(&path) {
-                    let current_checksum = compute_checksum(&content);
-                    &current_checksum != last_checksum
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false // No previous sync, so can't be modified
-        }
-    } else {
-        false
-    };
-
-    Ok(ClientSyncStatus {
-        client_id: id,
-        enabled: settings.map(|s| s.enabled).unwrap_or(true),
-        last_sync: settings.and_then(|s| s.last_sync.clone()),
-        last_sync_checksum: settings.and_then(|s| s.last_sync_checksum.clone()),
-        externally_modified,
-        sync_error: detected.error,
-    })
+    Ok(client_sync_status(&detected, config.clients.get(id.as_str())))
 }
 
 /// Get all clients with their sync status
@@ -87,39 +57,7 @@ pub fn get_all_client_statuses(
 
     let statuses: Vec<ClientSyncStatus> = detected_clients
         .iter()
-        .map(|detected| {
-            let settings = config.clients.get(detected.id.as_str());
-
-            // Check if externally modified
-            let externally_modified =
-                if let (Some(settings), true) = (settings, detected.config_exists) {
-                    if let Some(last_checksum) = &settings.last_sync_checksum {
-                        if let Some(path) = &detected.config_path {
-                            if let Ok(content) = std::fs::read(path) {
-                                let current_checksum = compute_checksum(&content);
-                                &current_checksum != last_checksum
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        }
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                };
-
-            ClientSyncStatus {
-                client_id: detected.id,
-                enabled: settings.map(|s| s.enabled).unwrap_or(true),
-                last_sync: settings.and_then(|s| s.last_sync.clone()),
-                last_sync_checksum: settings.and_then(|s| s.last_sync_checksum.clone()),
-                externally_modified,
-                sync_error: detected.error.clone(),
-            }
-        })
+        .map(|detected| client_sync_status(detected, config.clients.get(detected.id.as_str())))
         .collect();
 
     Ok(statuses)
@@ -139,31 +77,22 @@ pub fn get_client_config(client_id: String) -> Result<Option<ClientConfigInfo>,
     })
 }
 
-/// Compute a checksum for file content (for change detection)
-fn compute_checksum(content: &[u8]) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
-    let mut hasher = DefaultHasher::new();
-    content.hash(&mut hasher);
-    format!("{:x}", hasher.finish())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_compute_checksum() {
-        let content1 = b"hello world";
-        let content2 = b"hello world";
-        let content3 = b"different content";
-
-        let checksum1 = compute_checksum(content1);
-        let checksum2 = compute_checksum(content2);
-        let checksum3 = compute_checksum(content3);
-
-        assert_eq!(checksum1, checksum2);
-        assert_ne!(checksum1, checksum3);
+/// Reload user-defined client descriptors from ~/.mcp-nexus/clients.d/
+#[tauri::command]
+pub fn reload_client_descriptors(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<DescriptorLoadResult, CommandError> {
+    let result = reload_custom_clients();
+
+    // Watch the configs of clients the reload added
+    if let Some(watcher) = app.try_state::<Mutex<ConfigWatcher>>() {
+        let central_config = state.lock().unwrap().config_manager.config_path().clone();
+        let targets = watch_targets(&central_config);
+        if let Err(e) = watcher.lock().unwrap().set_targets(targets) {
+            log_warning(&format!("Failed to watch reloaded client configs: {}", e));
+        }
     }
+
+    Ok(result)
 }
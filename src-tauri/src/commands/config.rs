@@ -1,5 +1,5 @@
-use crate::models::{McpHubConfig, McpServer};
-use crate::services::{ConfigError, ConfigManager};
+use crate::models::{ClientOptions, ClientOverride, McpHubConfig, McpServer, ServerDiagnostic};
+use crate::services::{self, ConfigError, ConfigManager, JournalSummary};
 use std::sync::Mutex;
 use tauri::State;
 
@@ -55,21 +55,37 @@ pub fn initialize_config(state: State<'_, Mutex<AppState>>) -> Result<InitResult
     })
 }
 
+/// The full configuration and the revision it was read at
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSnapshot {
+    pub config: McpHubConfig,
+    /// Pass back to `save_config` so edits made since are not overwritten
+    pub revision: String,
+}
+
 /// Get the full configuration
 #[tauri::command]
-pub fn get_config(state: State<'_, Mutex<AppState>>) -> Result<McpHubConfig, CommandError> {
+pub fn get_config(state: State<'_, Mutex<AppState>>) -> Result<ConfigSnapshot, CommandError> {
     let state = state.lock().unwrap();
-    state.config_manager.load().map_err(Into::into)
+    let (config, revision) = state.config_manager.load_with_revision()?;
+    Ok(ConfigSnapshot { config, revision })
 }
 
-/// Save the full configuration
+/// Save the full configuration read at `revision`, returning the new revision.
+///
+/// Fails if the config file changed since that revision was read.
 #[tauri::command]
 pub fn save_config(
     state: State<'_, Mutex<AppState>>,
     config: McpHubConfig,
-) -> Result<(), CommandError> {
+    revision: String,
+) -> Result<String, CommandError> {
     let state = state.lock().unwrap();
-    state.config_manager.save(&config).map_err(Into::into)
+    state
+        .config_manager
+        .save_revision(&config, &revision)
+        .map_err(Into::into)
 }
 
 /// Get all servers
@@ -115,6 +131,17 @@ pub fn update_server(
         .map_err(Into::into)
 }
 
+/// Check a server definition without saving it, for showing problems in the editor
+#[tauri::command]
+pub fn validate_server(
+    state: State<'_, Mutex<AppState>>,
+    server: McpServer,
+) -> Result<Vec<ServerDiagnostic>, CommandError> {
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load()?;
+    Ok(services::validate_server(&server, &config.servers))
+}
+
 /// Remove a server by ID
 #[tauri::command]
 pub fn remove_server(
@@ -148,3 +175,74 @@ pub fn toggle_server_client(
         .toggle_server_client(&uuid, &client_id, enabled)
         .map_err(Into::into)
 }
+
+/// Set or clear a server's args/env/header override for a specific client
+#[tauri::command]
+pub fn set_server_client_override(
+    state: State<'_, Mutex<AppState>>,
+    server_id: String,
+    client_id: String,
+    client_override: Option<ClientOverride>,
+) -> Result<McpServer, CommandError> {
+    let state = state.lock().unwrap();
+    let uuid = uuid::Uuid::parse_str(&server_id).map_err(|e| CommandError {
+        message: format!("Invalid server ID: {}", e),
+    })?;
+    state
+        .config_manager
+        .set_server_client_override(&uuid, &client_id, client_override)
+        .map_err(Into::into)
+}
+
+/// Set or clear a server's entry options (disabled, auto-approve, ...) for a specific client
+#[tauri::command]
+pub fn set_server_client_options(
+    state: State<'_, Mutex<AppState>>,
+    server_id: String,
+    client_id: String,
+    options: Option<ClientOptions>,
+) -> Result<McpServer, CommandError> {
+    let state = state.lock().unwrap();
+    let uuid = uuid::Uuid::parse_str(&server_id).map_err(|e| CommandError {
+        message: format!("Invalid server ID: {}", e),
+    })?;
+    state
+        .config_manager
+        .set_server_client_options(&uuid, &client_id, options)
+        .map_err(Into::into)
+}
+
+/// List journaled config changes, newest first
+#[tauri::command]
+pub fn get_config_history(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<JournalSummary>, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.history().map_err(Into::into)
+}
+
+/// Revert the most recent config change (`None` if there is nothing to undo)
+#[tauri::command]
+pub fn undo_last_change(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Option<JournalSummary>, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.undo_last_change().map_err(Into::into)
+}
+
+/// Re-apply the most recently undone change (`None` if there is nothing to redo)
+#[tauri::command]
+pub fn redo(state: State<'_, Mutex<AppState>>) -> Result<Option<JournalSummary>, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.redo().map_err(Into::into)
+}
+
+/// Restore the config as it was at an RFC 3339 timestamp
+#[tauri::command]
+pub fn restore_config_to(
+    state: State<'_, Mutex<AppState>>,
+    timestamp: String,
+) -> Result<Option<JournalSummary>, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.restore_to(&timestamp).map_err(Into::into)
+}
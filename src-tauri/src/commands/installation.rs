@@ -1,4 +1,5 @@
 use crate::commands::config::AppState;
+use crate::commands::sync::record_sync;
 use crate::services::{
     cleanup_server as do_cleanup, install_server as do_install, run_doctor, validate_runtime,
     InstallResult, InstallServerRequest, InstallSource,
@@ -123,7 +124,9 @@ pub async fn install_mcp_server(
     let sync_result = if sync_after_install.unwrap_or(true) {
         let state = state.lock().unwrap();
         let config = state.config_manager.load()?;
-        Some(sync_to_all_clients(&config))
+        let result = sync_to_all_clients(&config);
+        record_sync(&state.config_manager, &result)?;
+        Some(result)
     } else {
         None
     };
@@ -178,7 +181,9 @@ pub async fn uninstall_mcp_server(
     let sync_result = if sync_after_uninstall.unwrap_or(true) {
         let state = state.lock().unwrap();
         let config = state.config_manager.load()?;
-        Some(sync_to_all_clients(&config))
+        let result = sync_to_all_clients(&config);
+        record_sync(&state.config_manager, &result)?;
+        Some(result)
     } else {
         None
     };
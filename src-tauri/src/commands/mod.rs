@@ -5,14 +5,21 @@ mod health;
 mod installation;
 mod keychain;
 mod marketplace;
+mod profiles;
+mod projects;
 mod sync;
 mod system;
 mod updates;
 
-pub use clients::{detect_clients, get_all_client_statuses, get_client_config, get_client_status};
+pub use clients::{
+    detect_clients, get_all_client_statuses, get_client_config, get_client_status,
+    reload_client_descriptors,
+};
 pub use config::{
-    add_server, get_config, get_server, get_servers, initialize_config, remove_server, save_config,
-    toggle_server_client, update_server, AppState,
+    add_server, get_config, get_config_history, get_server, get_servers, initialize_config, redo,
+    remove_server, restore_config_to, save_config, set_server_client_options,
+    set_server_client_override, toggle_server_client, undo_last_change, update_server,
+    validate_server, AppState,
 };
 pub use doctor::run_doctor;
 pub use health::{check_all_health, check_health, get_server_status};
@@ -27,9 +34,15 @@ pub use marketplace::MarketplaceState;
 pub use marketplace::{
     check_marketplace_cache, clear_marketplace_cache, get_server_details, search_servers,
 };
+pub use profiles::{activate_profile, create_profile, delete_profile, diff_profile, list_profiles};
+pub use projects::{
+    add_project, import_project_servers, remove_project, set_project_servers, set_project_targets,
+    sync_project,
+};
 pub use sync::{
-    get_manual_config, import_client_servers, set_client_sync_enabled, sync_all_clients,
-    sync_client,
+    get_manual_config, import_client_servers, list_client_backups, preview_sync_all_clients,
+    preview_sync_client, restore_client_backup, run_auto_sync, set_client_sync_enabled,
+    sync_all_clients, sync_client,
 };
 pub use system::get_system_accent_color;
 pub use updates::{
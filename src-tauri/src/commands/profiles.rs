@@ -0,0 +1,107 @@
+use crate::models::{Profile, ProfileServerDiff};
+use crate::services::sync_engine::{record_sync_results, sync_to_all_clients, SyncResult};
+use std::sync::Mutex;
+use tauri::State;
+
+use super::config::{AppState, CommandError};
+use super::sync::append_sync_log;
+
+/// Result of activating a profile
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileActivation {
+    /// Enablement changes that were applied
+    pub changes: Vec<ProfileServerDiff>,
+    /// Result of syncing every client afterwards
+    pub sync: SyncResult,
+}
+
+fn profile_not_found(name: &str) -> CommandError {
+    CommandError {
+        message: format!("Profile not found: {}", name),
+    }
+}
+
+/// List saved profiles
+#[tauri::command]
+pub fn list_profiles(state: State<'_, Mutex<AppState>>) -> Result<Vec<Profile>, CommandError> {
+    let state = state.lock().unwrap();
+    Ok(state.config_manager.load()?.profiles)
+}
+
+/// Save the current server enablement as a new profile
+#[tauri::command]
+pub fn create_profile(
+    state: State<'_, Mutex<AppState>>,
+    name: String,
+) -> Result<Profile, CommandError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(CommandError {
+            message: "Profile name cannot be empty".to_string(),
+        });
+    }
+
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        if config.get_profile(&name).is_some() {
+            return Err(CommandError {
+                message: format!("Profile already exists: {}", name),
+            });
+        }
+
+        let operation = format!("Create profile '{}'", name);
+        let profile = Profile::capture(name, &config.servers);
+        config.profiles.push(profile.clone());
+        Ok((profile, operation))
+    })
+}
+
+/// Delete a saved profile. Server enablement is left as it is.
+#[tauri::command]
+pub fn delete_profile(
+    state: State<'_, Mutex<AppState>>,
+    name: String,
+) -> Result<Profile, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        let removed = config
+            .remove_profile(&name)
+            .ok_or_else(|| profile_not_found(&name))?;
+        Ok((removed, format!("Delete profile '{}'", name)))
+    })
+}
+
+/// Preview the enablement changes activating a profile would make
+#[tauri::command]
+pub fn diff_profile(
+    state: State<'_, Mutex<AppState>>,
+    name: String,
+) -> Result<Vec<ProfileServerDiff>, CommandError> {
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load()?;
+
+    config.diff_profile(&name).ok_or_else(|| profile_not_found(&name))
+}
+
+/// Apply a profile's server enablement, then sync every client
+#[tauri::command]
+pub fn activate_profile(
+    state: State<'_, Mutex<AppState>>,
+    name: String,
+) -> Result<ProfileActivation, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        let changes = config
+            .activate_profile(&name)
+            .ok_or_else(|| profile_not_found(&name))?;
+
+        let sync = sync_to_all_clients(config);
+        append_sync_log(&sync);
+
+        // Saved together with the sync results, so auto-sync sees nothing left to do
+        record_sync_results(config, &sync);
+        let operation = format!("Activate profile '{}'", name);
+        Ok((ProfileActivation { changes, sync }, operation))
+    })
+}
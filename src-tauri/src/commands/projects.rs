@@ -0,0 +1,173 @@
+use crate::models::{McpHubConfig, ProjectSettings, ProjectTarget};
+use crate::services::detect_project_targets;
+use crate::services::sync_engine::{
+    import_from_project, record_project_sync_results, sync_to_project, ImportResult,
+    ProjectSyncResult,
+};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+use super::config::{AppState, CommandError};
+
+/// Canonical form of a project path, as used to key it in the config
+fn normalize_project_path(path: &str) -> Result<String, CommandError> {
+    let canonical = PathBuf::from(path).canonicalize().map_err(|e| CommandError {
+        message: format!("Invalid project path '{}': {}", path, e),
+    })?;
+
+    if !canonical.is_dir() {
+        return Err(CommandError {
+            message: format!("Project path '{}' is not a directory", path),
+        });
+    }
+
+    Ok(canonical.to_string_lossy().to_string())
+}
+
+/// Look up a registered project for modification
+fn project_mut<'a>(
+    config: &'a mut McpHubConfig,
+    path: &str,
+) -> Result<&'a mut ProjectSettings, CommandError> {
+    config.get_project_mut(path).ok_or_else(|| CommandError {
+        message: format!("Project not registered: {}", path),
+    })
+}
+
+/// Register a project directory.
+///
+/// When `targets` is omitted, they are detected from the config files and
+/// editor directories already present in the project.
+#[tauri::command]
+pub fn add_project(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+    name: Option<String>,
+    targets: Option<Vec<ProjectTarget>>,
+) -> Result<ProjectSettings, CommandError> {
+    let path = normalize_project_path(&path)?;
+
+    let root = PathBuf::from(&path);
+    let name = name.unwrap_or_else(|| {
+        root.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone())
+    });
+    let targets = targets.unwrap_or_else(|| detect_project_targets(&root));
+
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        if config.get_project(&path).is_some() {
+            return Err(CommandError {
+                message: format!("Project already registered: {}", path),
+            });
+        }
+
+        let operation = format!("Add project '{}'", name);
+        let project = ProjectSettings::new(path, name, targets);
+        config.projects.push(project.clone());
+        Ok((project, operation))
+    })
+}
+
+/// Unregister a project. Its config files are left as they are.
+#[tauri::command]
+pub fn remove_project(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+) -> Result<ProjectSettings, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        let removed = config.remove_project(&path).ok_or_else(|| CommandError {
+            message: format!("Project not registered: {}", path),
+        })?;
+        let operation = format!("Remove project '{}'", removed.name);
+        Ok((removed, operation))
+    })
+}
+
+/// Choose which servers apply to a project
+#[tauri::command]
+pub fn set_project_servers(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+    server_ids: Vec<String>,
+) -> Result<ProjectSettings, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        for id in &server_ids {
+            let uuid = uuid::Uuid::parse_str(id).map_err(|e| CommandError {
+                message: format!("Invalid server ID: {}", e),
+            })?;
+            if config.get_server(&uuid).is_none() {
+                return Err(CommandError {
+                    message: format!("Server not found: {}", id),
+                });
+            }
+        }
+
+        let project = project_mut(config, &path)?;
+        project.servers = server_ids;
+        let operation = format!("Change servers of project '{}'", project.name);
+        Ok((project.clone(), operation))
+    })
+}
+
+/// Choose which project-level config files are written for a project
+#[tauri::command]
+pub fn set_project_targets(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+    targets: Vec<ProjectTarget>,
+) -> Result<ProjectSettings, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        let project = project_mut(config, &path)?;
+        project.targets = targets;
+        let operation = format!("Change config files of project '{}'", project.name);
+        Ok((project.clone(), operation))
+    })
+}
+
+/// Sync a project's selected servers to its project-level config files
+#[tauri::command]
+pub fn sync_project(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+) -> Result<Vec<ProjectSyncResult>, CommandError> {
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load()?;
+
+    let results = sync_to_project(&config, &path).map_err(|e| CommandError {
+        message: e.to_string(),
+    })?;
+
+    // Recorded on the latest config, so changes made during the sync are kept
+    state.config_manager.modify(|config| {
+        record_project_sync_results(config, &results);
+        Ok::<_, CommandError>(((), format!("Sync project '{}'", path)))
+    })?;
+
+    Ok(results)
+}
+
+/// Import servers from one of a project's config files
+#[tauri::command]
+pub fn import_project_servers(
+    state: State<'_, Mutex<AppState>>,
+    path: String,
+    target: ProjectTarget,
+    overwrite_existing: bool,
+) -> Result<ImportResult, CommandError> {
+    let state = state.lock().unwrap();
+    state.config_manager.modify(|config| {
+        let result = import_from_project(config, &path, target, overwrite_existing).map_err(
+            |e| CommandError {
+                message: e.to_string(),
+            },
+        )?;
+        let operation = format!("Import servers from project '{}'", path);
+        Ok((result, operation))
+    })
+}
@@ -1,8 +1,15 @@
 use crate::models::ClientId;
-use crate::services::get_client_config_path;
+use crate::services::{
+    auto_sync_clients, get_client_config_path, log_warning, AutoSyncProgress, ConfigError,
+    ConfigManager,
+};
+use crate::services::backups::{BackupEntry, RestoreResult};
 use crate::services::sync_engine::{
-    import_from_client, sync_to_all_clients, sync_to_client, update_client_sync_status,
-    ClientSyncResult, ImportResult, SyncResult,
+    import_from_client, list_client_backups as list_backups_for_client,
+    preview_sync_to_all_clients, preview_sync_to_client, record_sync_results,
+    restore_client_backup as restore_backup_for_client, sync_to_all_clients,
+    sync_to_all_clients_transactional, sync_to_client, update_client_sync_status,
+    ClientSyncPreview, ClientSyncResult, ImportResult, SyncPreview, SyncResult,
 };
 use chrono::Utc;
 use dirs;
@@ -14,8 +21,9 @@ use tauri::State;
 use super::config::{AppState, CommandError};
 
 /// Append failed sync results to an auto-sync log file under ~/.mcp-nexus/logs/.
-fn append_sync_log(result: &SyncResult) {
-    if result.failed == 0 {
+pub(super) fn append_sync_log(result: &SyncResult) {
+    let project_failed = result.project_results.iter().any(|r| !r.success);
+    if result.failed == 0 && !project_failed {
         return;
     }
 
@@ -66,6 +74,66 @@ fn append_sync_log(result: &SyncResult) {
             break;
         }
     }
+
+    for project_result in result.project_results.iter().filter(|r| !r.success) {
+        let error = project_result
+            .error
+            .as_deref()
+            .unwrap_or("Unknown sync error");
+
+        if let Err(e) = writeln!(
+            file,
+            "{} project={} target={} error={}",
+            timestamp,
+            project_result.project_path,
+            project_result.target.as_str(),
+            error
+        ) {
+            eprintln!("Failed to write to auto-sync log {:?}: {}", log_path, e);
+            break;
+        }
+    }
+}
+
+/// Record a sync's results on the latest config, keeping any change saved
+/// while the clients were being written
+pub(super) fn record_sync(
+    config_manager: &ConfigManager,
+    result: &SyncResult,
+) -> Result<(), ConfigError> {
+    config_manager.modify(|config| {
+        record_sync_results(config, result);
+        Ok(((), "Record sync status".to_string()))
+    })
+}
+
+/// Sync clients after a config change, when auto-sync is enabled.
+///
+/// Failures go to the auto-sync log and successful clients get their sync
+/// status recorded; `on_progress` sees each step, ending with `Finished`.
+pub fn run_auto_sync<F>(config_manager: &ConfigManager, clients: &[ClientId], mut on_progress: F)
+where
+    F: FnMut(AutoSyncProgress),
+{
+    let config = match config_manager.load() {
+        Ok(config) => config,
+        Err(e) => {
+            log_warning(&format!("Auto-sync skipped: failed to load config: {}", e));
+            return;
+        }
+    };
+    if !config.preferences.auto_sync_on_changes {
+        return;
+    }
+
+    let result = auto_sync_clients(&config, clients, &mut on_progress);
+    append_sync_log(&result);
+
+    if let Err(e) = record_sync(config_manager, &result) {
+        log_warning(&format!("Auto-sync: failed to record sync status: {}", e));
+    }
+
+    on_progress(AutoSyncProgress::Finished { result });
 }
 
 /// Sync configuration to a single client
@@ -90,53 +158,75 @@ pub fn sync_client(
     // Update client settings if sync was successful
     if result.success && result.manual_config.is_none() {
         if let Ok(path) = get_client_config_path(id) {
-            let mut updated_config = config.clone();
-            update_client_sync_status(&mut updated_config, id, &path.to_string_lossy());
-            state
-                .config_manager
-                .save(&updated_config)
-                .map_err(|e| CommandError {
-                    message: e.to_string(),
-                })?;
+            let path = path.to_string_lossy();
+            state.config_manager.modify(|config| {
+                update_client_sync_status(config, id, &path, &result.synced_servers);
+                Ok::<_, CommandError>(((), "Record sync status".to_string()))
+            })?;
         }
     }
 
     Ok(result)
 }
 
+/// Preview what syncing a single client would change, without writing anything
+#[tauri::command]
+pub fn preview_sync_client(
+    state: State<'_, Mutex<AppState>>,
+    client_id: String,
+) -> Result<ClientSyncPreview, CommandError> {
+    // Parse client ID
+    let id: ClientId =
+        serde_json::from_value(serde_json::json!(client_id)).map_err(|e| CommandError {
+            message: format!("Invalid client ID '{}': {}", client_id, e),
+        })?;
+
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load().map_err(|e| CommandError {
+        message: e.to_string(),
+    })?;
+
+    Ok(preview_sync_to_client(id, &config))
+}
+
+/// Preview what syncing all enabled clients would change, without writing anything
+#[tauri::command]
+pub fn preview_sync_all_clients(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<SyncPreview, CommandError> {
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load().map_err(|e| CommandError {
+        message: e.to_string(),
+    })?;
+
+    Ok(preview_sync_to_all_clients(&config))
+}
+
 /// Sync configuration to all enabled clients
+///
+/// With `transactional` set, either every client is written or none is:
+/// a failed write rolls back the clients already written.
 #[tauri::command]
-pub fn sync_all_clients(state: State<'_, Mutex<AppState>>) -> Result<SyncResult, CommandError> {
+pub fn sync_all_clients(
+    state: State<'_, Mutex<AppState>>,
+    transactional: Option<bool>,
+) -> Result<SyncResult, CommandError> {
     let state = state.lock().unwrap();
     let config = state.config_manager.load().map_err(|e| CommandError {
         message: e.to_string(),
     })?;
 
-    let result = sync_to_all_clients(&config);
+    let result = if transactional.unwrap_or(false) {
+        sync_to_all_clients_transactional(&config)
+    } else {
+        sync_to_all_clients(&config)
+    };
 
     // Log any failed syncs for observability (used by auto-sync and manual sync).
     append_sync_log(&result);
 
     // Update client settings for successful syncs
-    let mut updated_config = config.clone();
-    for client_result in &result.results {
-        if client_result.success && client_result.manual_config.is_none() {
-            if let Ok(path) = get_client_config_path(client_result.client_id) {
-                update_client_sync_status(
-                    &mut updated_config,
-                    client_result.client_id,
-                    &path.to_string_lossy(),
-                );
-            }
-        }
-    }
-
-    state
-        .config_manager
-        .save(&updated_config)
-        .map_err(|e| CommandError {
-            message: e.to_string(),
-        })?;
+    record_sync(&state.config_manager, &result)?;
 
     Ok(result)
 }
@@ -155,24 +245,14 @@ pub fn import_client_servers(
         })?;
 
     let state = state.lock().unwrap();
-    let mut config = state.config_manager.load().map_err(|e| CommandError {
-        message: e.to_string(),
-    })?;
-
-    let result =
-        import_from_client(id, &mut config, overwrite_existing).map_err(|e| CommandError {
-            message: e.to_string(),
-        })?;
-
-    // Save updated config
-    state
-        .config_manager
-        .save(&config)
-        .map_err(|e| CommandError {
-            message: e.to_string(),
-        })?;
-
-    Ok(result)
+    state.config_manager.modify(|config| {
+        let result =
+            import_from_client(id, config, overwrite_existing).map_err(|e| CommandError {
+                message: e.to_string(),
+            })?;
+        let operation = format!("Import servers from {}", id.display_name());
+        Ok((result, operation))
+    })
 }
 
 /// Get the generated config JSON for a manual-configuration client (like Warp)
@@ -217,31 +297,63 @@ pub fn set_client_sync_enabled(
         })?;
 
     let state = state.lock().unwrap();
-    let mut config = state.config_manager.load().map_err(|e| CommandError {
+    state.config_manager.modify(|config| {
+        // Get or create client settings
+        let settings = config
+            .clients
+            .entry(id.as_str().to_string())
+            .or_insert_with(|| crate::models::ClientSettings {
+                enabled: true,
+                config_path: String::new(),
+                last_sync: None,
+                last_sync_checksum: None,
+                managed_servers: None,
+                server_hashes: None,
+                option_keys: None,
+            });
+
+        settings.enabled = enabled;
+        let verb = if enabled { "Enable" } else { "Disable" };
+        Ok::<_, CommandError>(((), format!("{} syncing to {}", verb, id.display_name())))
+    })
+}
+
+/// List the backup history of a client's config file, newest first
+#[tauri::command]
+pub fn list_client_backups(client_id: String) -> Result<Vec<BackupEntry>, CommandError> {
+    // Parse client ID
+    let id: ClientId =
+        serde_json::from_value(serde_json::json!(client_id)).map_err(|e| CommandError {
+            message: format!("Invalid client ID '{}': {}", client_id, e),
+        })?;
+
+    list_backups_for_client(id).map_err(|e| CommandError {
         message: e.to_string(),
-    })?;
+    })
+}
 
-    // Get or create client settings
-    let settings = config
-        .clients
-        .entry(id.as_str().to_string())
-        .or_insert_with(|| crate::models::ClientSettings {
-            enabled: true,
-            config_path: String::new(),
-            last_sync: None,
-            last_sync_checksum: None,
-        });
-
-    settings.enabled = enabled;
-
-    state
-        .config_manager
-        .save(&config)
-        .map_err(|e| CommandError {
-            message: e.to_string(),
+/// Restore a client's config file from a backup, backing up the current file first
+#[tauri::command]
+pub fn restore_client_backup(
+    state: State<'_, Mutex<AppState>>,
+    client_id: String,
+    backup_id: String,
+) -> Result<RestoreResult, CommandError> {
+    // Parse client ID
+    let id: ClientId =
+        serde_json::from_value(serde_json::json!(client_id)).map_err(|e| CommandError {
+            message: format!("Invalid client ID '{}': {}", client_id, e),
         })?;
 
-    Ok(())
+    let state = state.lock().unwrap();
+    let config = state.config_manager.load().map_err(|e| CommandError {
+        message: e.to_string(),
+    })?;
+
+    let retention = config.preferences.backup_retention as usize;
+    restore_backup_for_client(id, &backup_id, retention).map_err(|e| CommandError {
+        message: e.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -3,25 +3,42 @@ mod models;
 mod services;
 
 use commands::{
-    add_server, check_all_health, check_credential_exists, check_for_updates, check_health,
-    check_marketplace_cache, check_package_version, check_runtime_for_registry,
-    check_server_update, clear_marketplace_cache, delete_credential, detect_clients,
-    get_all_client_statuses, get_client_config, get_client_status, get_config,
-    get_credential_value, get_manual_config, get_server, get_server_details, get_server_status,
-    get_servers, get_system_accent_color, get_update_count, import_client_servers,
-    initialize_config, install_mcp_server, list_credentials, remove_server, run_doctor,
-    save_config, save_credential, search_servers, set_client_sync_enabled, sync_all_clients,
-    sync_client, toggle_server_client, uninstall_mcp_server, update_server,
-    validate_credential_references, validate_install, AppState, MarketplaceState,
+    activate_profile, add_project, add_server, check_all_health, check_credential_exists,
+    check_for_updates, check_health, check_marketplace_cache, check_package_version,
+    check_runtime_for_registry, check_server_update, clear_marketplace_cache, create_profile,
+    delete_credential, delete_profile, detect_clients, diff_profile, get_all_client_statuses,
+    get_client_config, get_client_status, get_config, get_config_history, get_credential_value,
+    get_manual_config, get_server, get_server_details, get_server_status, get_servers,
+    get_system_accent_color, get_update_count, import_client_servers, import_project_servers,
+    initialize_config, install_mcp_server, list_client_backups, list_credentials, list_profiles,
+    preview_sync_all_clients, preview_sync_client, redo, reload_client_descriptors, remove_project,
+    remove_server, restore_client_backup, restore_config_to, run_auto_sync, run_doctor, save_config,
+    save_credential, search_servers, set_client_sync_enabled, set_project_servers,
+    set_project_targets, set_server_client_options, set_server_client_override, sync_all_clients,
+    sync_client, sync_project, toggle_server_client, undo_last_change, uninstall_mcp_server,
+    update_server, validate_credential_references, validate_install, validate_server, AppState,
+    MarketplaceState,
+};
+use services::{
+    affected_clients, log_warning, reload_custom_clients, watch_targets, AutoSyncScheduler,
+    ConfigManager, ConfigWatcher, MarketplaceClient, AUTO_SYNC_DEBOUNCE, AUTO_SYNC_EVENT,
+    CONFIG_CHANGED_EVENT,
 };
-use services::{ConfigManager, MarketplaceClient};
 use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
 use tokio::sync::RwLock;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize the config manager
     let config_manager = ConfigManager::new().expect("Failed to initialize config manager");
+    let central_config = config_manager.config_path().clone();
+
+    // Register user-defined clients from ~/.mcp-nexus/clients.d/
+    let descriptors = reload_custom_clients();
+    for error in &descriptors.errors {
+        log_warning(&format!("Skipping client descriptor {}: {}", error.path, error.message));
+    }
 
     // Initialize the marketplace client
     let marketplace_state = Arc::new(RwLock::new(MarketplaceState {
@@ -32,6 +49,42 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .manage(Mutex::new(AppState { config_manager }))
         .manage(marketplace_state)
+        .setup(move |app| {
+            // Report config files edited outside Nexus to the frontend
+            let handle = app.handle().clone();
+            let watcher = ConfigWatcher::start(watch_targets(&central_config), move |event| {
+                if event.client_id.is_none() {
+                    let state = handle.state::<Mutex<AppState>>();
+                    state.lock().unwrap().config_manager.invalidate_cache();
+                }
+                let _ = handle.emit(CONFIG_CHANGED_EVENT, &event);
+            });
+            match watcher {
+                Ok(watcher) => {
+                    app.manage(Mutex::new(watcher));
+                }
+                Err(e) => log_warning(&format!("Config watching disabled: {}", e)),
+            }
+
+            // Sync the clients a save affects, in the background and debounced
+            let sync_handle = app.handle().clone();
+            let scheduler = AutoSyncScheduler::start(AUTO_SYNC_DEBOUNCE, move |clients| {
+                let state = sync_handle.state::<Mutex<AppState>>();
+                let state = state.lock().unwrap();
+                run_auto_sync(&state.config_manager, &clients, |progress| {
+                    let _ = sync_handle.emit(AUTO_SYNC_EVENT, &progress);
+                });
+            });
+            let state = app.state::<Mutex<AppState>>();
+            let state = state.lock().unwrap();
+            state.config_manager.set_save_hook(move |previous, current| {
+                if current.preferences.auto_sync_on_changes {
+                    scheduler.schedule(affected_clients(previous, current));
+                }
+            });
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Config commands
             initialize_config,
@@ -41,8 +94,16 @@ pub fn run() {
             get_server,
             add_server,
             update_server,
+            validate_server,
             remove_server,
             toggle_server_client,
+            set_server_client_override,
+            set_server_client_options,
+            // History commands
+            get_config_history,
+            undo_last_change,
+            redo,
+            restore_config_to,
             // Doctor commands
             run_doctor,
             // Client commands
@@ -50,12 +111,30 @@ pub fn run() {
             get_client_status,
             get_all_client_statuses,
             get_client_config,
+            reload_client_descriptors,
             // Sync commands
             sync_client,
             sync_all_clients,
+            preview_sync_client,
+            preview_sync_all_clients,
+            list_client_backups,
+            restore_client_backup,
             import_client_servers,
             get_manual_config,
             set_client_sync_enabled,
+            // Project commands
+            add_project,
+            remove_project,
+            set_project_servers,
+            set_project_targets,
+            sync_project,
+            import_project_servers,
+            // Profile commands
+            list_profiles,
+            create_profile,
+            delete_profile,
+            diff_profile,
+            activate_profile,
             // Marketplace commands
             search_servers,
             get_server_details,
@@ -1,10 +1,15 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-/// All supported AI client types that can use MCP servers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+use super::descriptor::{custom_client, custom_clients, intern};
+use super::server::Transport;
+
+/// All supported AI client types that can use MCP servers.
+///
+/// Serialized as the kebab-case string from [`ClientId::as_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ClientId {
     /// Claude Code CLI tool
     ClaudeCode,
@@ -22,10 +27,44 @@ pub enum ClientId {
     Windsurf,
     /// Warp terminal (manual configuration only)
     Warp,
+    /// OpenAI Codex CLI
+    Codex,
+    /// Zed editor
+    Zed,
+    /// Block's Goose agent
+    Goose,
+    /// Google Gemini CLI
+    GeminiCli,
+    /// Roo Code extension
+    RooCode,
+    /// Amazon Q Developer
+    AmazonQ,
+    /// User-defined client described by a file in `~/.mcp-nexus/clients.d/`
+    Custom(&'static str),
 }
 
 impl ClientId {
-    /// Get all supported client IDs
+    /// Client ID for a custom client descriptor
+    pub fn custom(id: &str) -> ClientId {
+        ClientId::Custom(intern(id))
+    }
+
+    /// Look up a built-in client, or a custom client with a loaded descriptor
+    pub fn from_id(id: &str) -> Option<ClientId> {
+        if let Some(builtin) = ClientId::all().into_iter().find(|c| c.as_str() == id) {
+            return Some(builtin);
+        }
+        custom_client(id).map(|d| ClientId::custom(&d.id))
+    }
+
+    /// Built-in clients followed by every loaded custom client
+    pub fn all_with_custom() -> Vec<ClientId> {
+        let mut all = ClientId::all();
+        all.extend(custom_clients().iter().map(|d| ClientId::custom(&d.id)));
+        all
+    }
+
+    /// Get all built-in client IDs
     pub fn all() -> Vec<ClientId> {
         vec![
             ClientId::ClaudeCode,
@@ -36,12 +75,18 @@ impl ClientId {
             ClientId::Continue,
             ClientId::Windsurf,
             ClientId::Warp,
+            ClientId::Codex,
+            ClientId::Zed,
+            ClientId::Goose,
+            ClientId::GeminiCli,
+            ClientId::RooCode,
+            ClientId::AmazonQ,
         ]
     }
 
     /// Get the display name for this client
-    pub fn display_name(&self) -> &'static str {
-        match self {
+    pub fn display_name(&self) -> Cow<'static, str> {
+        let name = match self {
             ClientId::ClaudeCode => "Claude Code",
             ClientId::ClaudeDesktop => "Claude Desktop",
             ClientId::Cursor => "Cursor",
@@ -50,7 +95,17 @@ impl ClientId {
             ClientId::Continue => "Continue.dev",
             ClientId::Windsurf => "Windsurf",
             ClientId::Warp => "Warp",
-        }
+            ClientId::Codex => "Codex CLI",
+            ClientId::Zed => "Zed",
+            ClientId::Goose => "Goose",
+            ClientId::GeminiCli => "Gemini CLI",
+            ClientId::RooCode => "Roo Code",
+            ClientId::AmazonQ => "Amazon Q Developer",
+            ClientId::Custom(id) => {
+                return custom_client(id).map(|d| Cow::Owned(d.name)).unwrap_or(Cow::Borrowed(id))
+            }
+        };
+        Cow::Borrowed(name)
     }
 
     /// Get the string identifier for this client (used in config)
@@ -64,6 +119,32 @@ impl ClientId {
             ClientId::Continue => "continue",
             ClientId::Windsurf => "windsurf",
             ClientId::Warp => "warp",
+            ClientId::Codex => "codex",
+            ClientId::Zed => "zed",
+            ClientId::Goose => "goose",
+            ClientId::GeminiCli => "gemini-cli",
+            ClientId::RooCode => "roo-code",
+            ClientId::AmazonQ => "amazon-q",
+            ClientId::Custom(id) => id,
+        }
+    }
+
+    /// What this client's config can express
+    pub fn capabilities(&self) -> ClientCapabilities {
+        let all = ClientCapabilities::default();
+        match self {
+            // Remote servers are only reachable through Connectors in the app, not the config
+            ClientId::ClaudeDesktop => ClientCapabilities {
+                sse: false,
+                http: false,
+                headers: false,
+                ..all
+            },
+            ClientId::Codex | ClientId::Zed | ClientId::AmazonQ => {
+                ClientCapabilities { sse: false, ..all }
+            }
+            ClientId::Custom(id) => custom_client(id).map(|d| d.capabilities).unwrap_or(all),
+            _ => all,
         }
     }
 
@@ -79,6 +160,13 @@ impl ClientId {
             ClientId::Continue => "play-circle",
             ClientId::Windsurf => "wind",
             ClientId::Warp => "terminal-square",
+            ClientId::Codex => "square-terminal",
+            ClientId::Zed => "zap",
+            ClientId::Goose => "bird",
+            ClientId::GeminiCli => "sparkles",
+            ClientId::RooCode => "bot",
+            ClientId::AmazonQ => "cloud",
+            ClientId::Custom(_) => "puzzle",
         }
     }
 }
@@ -89,6 +177,22 @@ impl std::fmt::Display for ClientId {
     }
 }
 
+impl Serialize for ClientId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// An id with no built-in client or loaded descriptor (e.g. its file was removed
+/// from `clients.d/`) becomes an inert custom client: it keeps its id, and syncing
+/// it reports the client as not found instead of failing to load.
+impl<'de> Deserialize<'de> for ClientId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(ClientId::from_id(&id).unwrap_or_else(|| ClientId::custom(&id)))
+    }
+}
+
 /// The sync mode for a client - determines how configuration is synced
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -109,6 +213,55 @@ pub enum ConfigFormat {
     Vscode,
     /// Continue.dev format: config.json with mcpServers array
     Continue,
+    /// Codex CLI format: config.toml with [mcp_servers.<name>] tables
+    CodexToml,
+    /// Zed format: settings.json with {"context_servers": {"name": {"command": {"path", ...}}}}
+    Zed,
+    /// Continue.dev YAML format: config.yaml with an mcpServers list
+    ContinueYaml,
+    /// Goose format: config.yaml with {"extensions": {"name": {"cmd": ..., "type": "stdio"}}}
+    GooseYaml,
+    /// Layout given by a custom client's descriptor
+    Custom,
+}
+
+/// Transports and fields a client's config supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClientCapabilities {
+    /// Local servers launched as a command
+    pub stdio: bool,
+    /// Remote servers over Server-Sent Events
+    pub sse: bool,
+    /// Remote servers over Streamable HTTP
+    pub http: bool,
+    /// Custom headers on remote servers
+    pub headers: bool,
+    /// Environment variables on local servers
+    pub env: bool,
+}
+
+impl Default for ClientCapabilities {
+    fn default() -> Self {
+        Self {
+            stdio: true,
+            sse: true,
+            http: true,
+            headers: true,
+            env: true,
+        }
+    }
+}
+
+impl ClientCapabilities {
+    /// Whether a transport can be written to the client as-is
+    pub fn supports(&self, transport: &Transport) -> bool {
+        match transport {
+            Transport::Stdio { env, .. } => self.stdio && (self.env || env.is_empty()),
+            Transport::Sse { headers, .. } => self.sse && (self.headers || headers.is_empty()),
+            Transport::Http { headers, .. } => self.http && (self.headers || headers.is_empty()),
+        }
+    }
 }
 
 /// Information about a detected AI client installation
@@ -123,6 +276,9 @@ pub struct DetectedClient {
     pub detected: bool,
     /// Path to the client's MCP config file (if applicable)
     pub config_path: Option<PathBuf>,
+    /// Every location checked for the config on this platform, most preferred first
+    #[serde(default)]
+    pub config_candidates: Vec<PathBuf>,
     /// Whether the config file exists
     pub config_exists: bool,
     /// Number of MCP servers currently configured in this client
@@ -135,6 +291,9 @@ pub struct DetectedClient {
     pub error: Option<String>,
     /// Documentation URL for manual configuration
     pub docs_url: Option<String>,
+    /// Transports and fields the client supports
+    #[serde(default)]
+    pub capabilities: ClientCapabilities,
 }
 
 impl DetectedClient {
@@ -148,6 +307,14 @@ impl DetectedClient {
             ),
             ClientId::Vscode => (SyncMode::Automatic, ConfigFormat::Vscode, None),
             ClientId::Continue => (SyncMode::Automatic, ConfigFormat::Continue, None),
+            ClientId::Codex => (SyncMode::Automatic, ConfigFormat::CodexToml, None),
+            ClientId::Zed => (SyncMode::Automatic, ConfigFormat::Zed, None),
+            ClientId::Goose => (SyncMode::Automatic, ConfigFormat::GooseYaml, None),
+            ClientId::Custom(id) => (
+                SyncMode::Automatic,
+                ConfigFormat::Custom,
+                custom_client(id).and_then(|d| d.docs_url),
+            ),
             _ => (SyncMode::Automatic, ConfigFormat::Standard, None),
         };
 
@@ -156,12 +323,14 @@ impl DetectedClient {
             name: id.display_name().to_string(),
             detected: false,
             config_path: None,
+            config_candidates: vec![],
             config_exists: false,
             server_count: 0,
             sync_mode,
             config_format,
             error: None,
             docs_url,
+            capabilities: id.capabilities(),
         }
     }
 }
@@ -180,6 +349,12 @@ pub struct ClientSyncStatus {
     pub last_sync_checksum: Option<String>,
     /// Whether the config has been modified externally since last sync
     pub externally_modified: bool,
+    /// Managed servers whose entry was edited since last sync
+    #[serde(default)]
+    pub modified_servers: Vec<String>,
+    /// Managed servers whose entry was removed since last sync
+    #[serde(default)]
+    pub removed_servers: Vec<String>,
     /// Any sync errors
     pub sync_error: Option<String>,
 }
@@ -203,7 +378,7 @@ mod tests {
     #[test]
     fn test_client_id_all() {
         let all = ClientId::all();
-        assert_eq!(all.len(), 8);
+        assert_eq!(all.len(), 14);
         assert!(all.contains(&ClientId::ClaudeCode));
         assert!(all.contains(&ClientId::Warp));
     }
@@ -221,6 +396,30 @@ mod tests {
         assert_eq!(ClientId::Vscode.as_str(), "vscode");
     }
 
+    #[test]
+    fn test_client_capabilities() {
+        let remote = Transport::Sse {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+        };
+        let with_headers = Transport::Http {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::from([("Authorization".to_string(), "Bearer x".to_string())]),
+        };
+
+        assert!(!ClientId::ClaudeDesktop.capabilities().supports(&remote));
+        assert!(!ClientId::Codex.capabilities().supports(&remote));
+        assert!(ClientId::Codex.capabilities().supports(&with_headers));
+        assert!(ClientId::Cursor.capabilities().supports(&with_headers));
+
+        let no_headers = ClientCapabilities {
+            headers: false,
+            ..Default::default()
+        };
+        assert!(no_headers.supports(&remote));
+        assert!(!no_headers.supports(&with_headers));
+    }
+
     #[test]
     fn test_detected_client_warp_manual() {
         let client = DetectedClient::new(ClientId::Warp);
@@ -235,6 +434,40 @@ mod tests {
         assert_eq!(client.sync_mode, SyncMode::Automatic);
     }
 
+    #[test]
+    fn test_client_id_serialization_matches_as_str() {
+        for id in ClientId::all() {
+            let json = serde_json::to_value(id).unwrap();
+            assert_eq!(json, serde_json::json!(id.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_client_id_without_descriptor_deserializes_inert() {
+        // Stands in for a client whose descriptor was removed from clients.d/
+        let ids: Vec<ClientId> =
+            serde_json::from_str(r#"["cursor", "removed-client"]"#).unwrap();
+        assert_eq!(ids, vec![ClientId::Cursor, ClientId::custom("removed-client")]);
+
+        let removed = ids[1];
+        assert_eq!(removed.display_name(), "removed-client");
+        assert_eq!(removed.capabilities(), ClientCapabilities::default());
+        assert_eq!(serde_json::to_string(&removed).unwrap(), r#""removed-client""#);
+        assert_eq!(ClientId::from_id("removed-client"), None);
+    }
+
+    #[test]
+    fn test_custom_client_id() {
+        let id = ClientId::custom("unregistered-client");
+        assert_eq!(id, ClientId::custom("unregistered-client"));
+        assert_eq!(id.as_str(), "unregistered-client");
+        assert_eq!(id.display_name(), "unregistered-client");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""unregistered-client""#);
+
+        assert_eq!(ClientId::from_id("codex"), Some(ClientId::Codex));
+        assert_eq!(DetectedClient::new(id).config_format, ConfigFormat::Custom);
+    }
+
     #[test]
     fn test_client_id_serialization() {
         let id = ClientId::ClaudeCode;
@@ -244,4 +477,15 @@ mod tests {
         let parsed: ClientId = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, ClientId::ClaudeCode);
     }
+
+    #[test]
+    fn test_detected_client_codex_format() {
+        let client = DetectedClient::new(ClientId::Codex);
+        assert_eq!(client.config_format, ConfigFormat::CodexToml);
+        assert_eq!(client.sync_mode, SyncMode::Automatic);
+        assert_eq!(
+            serde_json::to_string(&ConfigFormat::CodexToml).unwrap(),
+            r#""codexToml""#
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use super::McpServer;
+use super::{diff_enablement, enablement, McpServer, Profile, ProfileServerDiff, ProjectSettings};
 
 /// Client-specific settings
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -18,6 +18,20 @@ pub struct ClientSettings {
     /// Checksum of the config file at last sync (for conflict detection)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_sync_checksum: Option<String>,
+    /// Names of the server entries Nexus wrote to this client's config at last sync.
+    /// Entries not listed here belong to the user and are never touched by a sync.
+    /// `None` for clients that have not been synced since ownership tracking was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub managed_servers: Option<Vec<String>>,
+    /// Checksum of each managed server entry at last sync, keyed by server name,
+    /// so drift can be traced to the entries that changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_hashes: Option<BTreeMap<String, String>>,
+    /// Entry option keys (e.g. Cline's `autoApprove`) Nexus wrote for each managed
+    /// server at last sync, keyed by server name. A key cleared in Nexus since then
+    /// is removed from the entry rather than kept as the client's own setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option_keys: Option<BTreeMap<String, Vec<String>>>,
 }
 
 fn default_true() -> bool {
@@ -40,12 +54,19 @@ pub struct UserPreferences {
     /// Registry refresh interval in minutes
     #[serde(default = "default_refresh_interval")]
     pub registry_refresh_interval: u32,
+    /// Number of timestamped backups to keep per client
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: u32,
 }
 
 fn default_refresh_interval() -> u32 {
     60 // 1 hour
 }
 
+fn default_backup_retention() -> u32 {
+    10
+}
+
 impl Default for UserPreferences {
     fn default() -> Self {
         Self {
@@ -53,10 +74,14 @@ impl Default for UserPreferences {
             show_notifications: true,
             auto_sync_on_changes: true,
             registry_refresh_interval: default_refresh_interval(),
+            backup_retention: default_backup_retention(),
         }
     }
 }
 
+/// Schema version written by this release; older configs are migrated on load
+pub const CONFIG_VERSION: &str = "1.2";
+
 /// The central MCP Hub configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -72,15 +97,27 @@ pub struct McpHubConfig {
     /// User preferences
     #[serde(default)]
     pub preferences: UserPreferences,
+    /// Registered project directories with project-level configs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<ProjectSettings>,
+    /// Saved server enablement snapshots
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+    /// Name of the profile activated last
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
 }
 
 impl Default for McpHubConfig {
     fn default() -> Self {
         Self {
-            version: "1.0".to_string(),
+            version: CONFIG_VERSION.to_string(),
             servers: vec![],
             clients: HashMap::new(),
             preferences: UserPreferences::default(),
+            projects: vec![],
+            profiles: vec![],
+            active_profile: None,
         }
     }
 }
@@ -124,6 +161,81 @@ impl McpHubConfig {
             .filter(|s| s.is_enabled_for_client(client_id))
             .collect()
     }
+
+    /// Get a registered project by its root path
+    pub fn get_project(&self, path: &str) -> Option<&ProjectSettings> {
+        self.projects.iter().find(|p| p.path == path)
+    }
+
+    /// Get a mutable reference to a registered project by its root path
+    pub fn get_project_mut(&mut self, path: &str) -> Option<&mut ProjectSettings> {
+        self.projects.iter_mut().find(|p| p.path == path)
+    }
+
+    /// Remove a registered project by its root path
+    pub fn remove_project(&mut self, path: &str) -> Option<ProjectSettings> {
+        let pos = self.projects.iter().position(|p| p.path == path)?;
+        Some(self.projects.remove(pos))
+    }
+
+    /// Get all enabled servers selected for a project
+    pub fn get_servers_for_project(&self, path: &str) -> Vec<&McpServer> {
+        match self.get_project(path) {
+            Some(project) => self
+                .servers
+                .iter()
+                .filter(|s| s.enabled && project.includes_server(&s.id))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Get a saved profile by name
+    pub fn get_profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Remove a saved profile by name, clearing it as the active profile
+    pub fn remove_profile(&mut self, name: &str) -> Option<Profile> {
+        let pos = self.profiles.iter().position(|p| p.name == name)?;
+        if self.active_profile.as_deref() == Some(name) {
+            self.active_profile = None;
+        }
+        Some(self.profiles.remove(pos))
+    }
+
+    /// Changes activating a saved profile would make to the current enablement
+    pub fn diff_profile(&self, name: &str) -> Option<Vec<ProfileServerDiff>> {
+        let profile = self.get_profile(name)?;
+        Some(diff_enablement(
+            &self.servers,
+            &enablement(&self.servers),
+            &profile.enabled_clients,
+        ))
+    }
+
+    /// Apply a saved profile's enablement and mark it active, returning the changes.
+    ///
+    /// Servers added after the profile was saved are left as they are.
+    pub fn activate_profile(&mut self, name: &str) -> Option<Vec<ProfileServerDiff>> {
+        let changes = self.diff_profile(name)?;
+
+        for change in &changes {
+            let server = self.servers.iter_mut().find(|s| s.id.to_string() == change.server_id);
+            let Some(server) = server else {
+                continue;
+            };
+            for client in &change.disabled {
+                server.disable_for_client(client);
+            }
+            for client in &change.enabled {
+                server.enable_for_client(client);
+            }
+        }
+
+        self.active_profile = Some(name.to_string());
+        Some(changes)
+    }
 }
 
 #[cfg(test)]
@@ -149,7 +261,7 @@ mod tests {
     #[test]
     fn test_config_default() {
         let config = McpHubConfig::default();
-        assert_eq!(config.version, "1.0");
+        assert_eq!(config.version, CONFIG_VERSION);
         assert!(config.servers.is_empty());
         assert!(config.clients.is_empty());
     }
@@ -232,6 +344,7 @@ mod tests {
         assert!(prefs.show_notifications);
         assert!(prefs.auto_sync_on_changes);
         assert_eq!(prefs.registry_refresh_interval, 60);
+        assert_eq!(prefs.backup_retention, 10);
     }
 
     #[test]
@@ -245,4 +358,65 @@ mod tests {
         assert!(prefs.auto_sync_on_changes);
         assert_eq!(prefs.registry_refresh_interval, 60);
     }
+
+    #[test]
+    fn test_get_servers_for_project() {
+        let mut config = McpHubConfig::new();
+
+        let selected = create_test_server("selected");
+        let mut disabled = create_test_server("disabled");
+        disabled.enabled = false;
+        let other = create_test_server("other");
+
+        let mut project = ProjectSettings::new(
+            "/work/app".to_string(),
+            "app".to_string(),
+            vec![crate::models::ProjectTarget::McpJson],
+        );
+        project.servers = vec![selected.id.to_string(), disabled.id.to_string()];
+
+        config.add_server(selected);
+        config.add_server(disabled);
+        config.add_server(other);
+        config.projects.push(project);
+
+        let servers = config.get_servers_for_project("/work/app");
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "selected");
+        assert!(config.get_servers_for_project("/work/other").is_empty());
+
+        assert!(config.remove_project("/work/app").is_some());
+        assert!(config.projects.is_empty());
+    }
+
+    #[test]
+    fn test_activate_profile() {
+        let mut config = McpHubConfig::new();
+        let mut work = create_test_server("work");
+        work.enable_for_client("cursor");
+        config.add_server(work);
+        config.add_server(create_test_server("personal"));
+
+        config.profiles.push(Profile::capture("work".to_string(), &config.servers));
+        config.servers[0].disable_for_client("cursor");
+        config.servers[1].enable_for_client("cursor");
+        config.profiles.push(Profile::capture("personal".to_string(), &config.servers));
+
+        // Added after both snapshots, so activation leaves it alone
+        let mut late = create_test_server("late");
+        late.enable_for_client("zed");
+        config.add_server(late);
+
+        let changes = config.activate_profile("work").unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(config.servers[0].is_enabled_for_client("cursor"));
+        assert!(!config.servers[1].is_enabled_for_client("cursor"));
+        assert!(config.servers[2].is_enabled_for_client("zed"));
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        assert_eq!(config.diff_profile("work"), Some(vec![]));
+
+        assert!(config.activate_profile("missing").is_none());
+        assert!(config.remove_profile("work").is_some());
+        assert_eq!(config.active_profile, None);
+    }
 }
@@ -0,0 +1,181 @@
+use super::client::ClientCapabilities;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, RwLock};
+
+/// A user-defined client, loaded from a JSON file in `~/.mcp-nexus/clients.d/`.
+///
+/// Descriptors let Nexus detect, import from and sync to homegrown clients
+/// without a code change, as long as they keep servers as a map in a JSON,
+/// YAML or TOML file (chosen by the config file's extension).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDescriptor {
+    /// Client identifier, e.g. "acme-agent" (lowercase letters, digits and dashes)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Config file location per OS
+    pub config_path: DescriptorPaths,
+    /// JSON pointer (RFC 6901) to the server map, e.g. "/mcpServers"
+    pub servers_pointer: String,
+    /// Key names used in each server entry
+    #[serde(default)]
+    pub fields: FieldMapping,
+    /// Transports and fields the client supports (everything by default)
+    #[serde(default)]
+    pub capabilities: ClientCapabilities,
+    /// Documentation URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+}
+
+/// Config file location per OS.
+///
+/// A leading `~` expands to the home directory and `{config}` to the
+/// platform's per-user config directory (e.g. `%APPDATA%` on Windows).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorPaths {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub macos: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linux: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows: Option<String>,
+    /// Used on any OS without its own entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl DescriptorPaths {
+    /// Path template for an OS name as reported by `std::env::consts::OS`
+    pub fn for_os(&self, os: &str) -> Option<&str> {
+        let specific = match os {
+            "macos" => self.macos.as_deref(),
+            "linux" => self.linux.as_deref(),
+            "windows" => self.windows.as_deref(),
+            _ => None,
+        };
+        specific.or(self.default.as_deref())
+    }
+}
+
+/// Key names a client uses in a server entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FieldMapping {
+    pub command: String,
+    pub args: String,
+    pub env: String,
+    pub url: String,
+    pub headers: String,
+    /// Key holding the transport type; `None` if the client infers it
+    pub r#type: Option<String>,
+    /// Type value written for stdio servers (omitted when `None`)
+    pub stdio_type: Option<String>,
+    /// Type value for SSE servers
+    pub sse_type: Option<String>,
+    /// Type value for Streamable HTTP servers
+    pub http_type: Option<String>,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            command: "command".to_string(),
+            args: "args".to_string(),
+            env: "env".to_string(),
+            url: "url".to_string(),
+            headers: "headers".to_string(),
+            r#type: Some("type".to_string()),
+            stdio_type: None,
+            sse_type: Some("sse".to_string()),
+            http_type: Some("http".to_string()),
+        }
+    }
+}
+
+impl ClientDescriptor {
+    /// Keys leading from the document root to the server map
+    pub fn servers_keys(&self) -> Vec<String> {
+        self.servers_pointer
+            .split('/')
+            .skip(1)
+            .map(|key| key.replace("~1", "/").replace("~0", "~"))
+            .collect()
+    }
+}
+
+/// Descriptors currently loaded, consulted for `ClientId::Custom` clients
+static CUSTOM_CLIENTS: RwLock<Vec<ClientDescriptor>> = RwLock::new(Vec::new());
+
+/// Interned custom client ids, so `ClientId` can stay `Copy`
+static INTERNED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Get a `'static` copy of a string, allocating it at most once
+pub(crate) fn intern(value: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap();
+    if let Some(existing) = interned.iter().find(|s| **s == value) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(value.to_string().into_boxed_str());
+    interned.push(leaked);
+    leaked
+}
+
+/// Replace the loaded custom client descriptors
+pub fn set_custom_clients(descriptors: Vec<ClientDescriptor>) {
+    *CUSTOM_CLIENTS.write().unwrap() = descriptors;
+}
+
+/// All loaded custom client descriptors
+pub fn custom_clients() -> Vec<ClientDescriptor> {
+    CUSTOM_CLIENTS.read().unwrap().clone()
+}
+
+/// Descriptor of a loaded custom client
+pub fn custom_client(id: &str) -> Option<ClientDescriptor> {
+    CUSTOM_CLIENTS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|d| d.id == id)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_deserialization_defaults() {
+        let json = r#"{
+            "id": "acme-agent",
+            "name": "Acme Agent",
+            "configPath": {"linux": "~/.config/acme/mcp.json", "default": "~/.acme/mcp.json"},
+            "serversPointer": "/tools/mcp~1servers",
+            "fields": {"command": "cmd", "type": null},
+            "capabilities": {"sse": false}
+        }"#;
+
+        let descriptor: ClientDescriptor = serde_json::from_str(json).unwrap();
+        assert_eq!(descriptor.fields.command, "cmd");
+        assert_eq!(descriptor.fields.args, "args");
+        assert_eq!(descriptor.fields.r#type, None);
+        assert!(!descriptor.capabilities.sse);
+        assert!(descriptor.capabilities.http);
+        assert_eq!(descriptor.servers_keys(), vec!["tools", "mcp/servers"]);
+        assert_eq!(
+            descriptor.config_path.for_os("linux"),
+            Some("~/.config/acme/mcp.json")
+        );
+        assert_eq!(descriptor.config_path.for_os("windows"), Some("~/.acme/mcp.json"));
+    }
+
+    #[test]
+    fn test_intern_reuses_allocations() {
+        let a = intern("descriptor-test-intern");
+        let b = intern(&String::from("descriptor-test-intern"));
+        assert!(std::ptr::eq(a, b));
+    }
+}
@@ -1,11 +1,19 @@
 mod client;
 mod config;
+mod descriptor;
 mod doctor;
 mod marketplace;
+mod profile;
+mod project;
 mod server;
+mod validation;
 
 pub use client::*;
 pub use config::*;
+pub use descriptor::*;
 pub use doctor::*;
 pub use marketplace::*;
+pub use profile::*;
+pub use project::*;
 pub use server::*;
+pub use validation::*;
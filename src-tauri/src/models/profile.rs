@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::McpServer;
+
+/// A named snapshot of which servers are enabled for which clients
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    /// Unique display name
+    pub name: String,
+    /// Client IDs each server is enabled for, keyed by server ID
+    pub enabled_clients: BTreeMap<String, Vec<String>>,
+    /// ISO timestamp of when the snapshot was taken
+    pub created_at: String,
+}
+
+impl Profile {
+    /// Snapshot the current enablement of `servers`
+    pub fn capture(name: String, servers: &[McpServer]) -> Self {
+        Self {
+            name,
+            enabled_clients: enablement(servers),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Client IDs each server is enabled for, keyed by server ID (clients sorted)
+pub fn enablement(servers: &[McpServer]) -> BTreeMap<String, Vec<String>> {
+    servers
+        .iter()
+        .map(|server| {
+            let mut clients = server.enabled_clients.clone();
+            clients.sort();
+            (server.id.to_string(), clients)
+        })
+        .collect()
+}
+
+/// How one server's enablement differs between two snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileServerDiff {
+    pub server_id: String,
+    pub server_name: String,
+    /// Clients the server would be enabled for
+    pub enabled: Vec<String>,
+    /// Clients the server would be disabled for
+    pub disabled: Vec<String>,
+}
+
+/// Per-server changes going from enablement `from` to `to`.
+///
+/// Only servers in `servers` are compared. A server missing from `to` is left
+/// as it is by activation and so never differs; one missing from `from` is
+/// treated as enabled for no clients.
+pub fn diff_enablement(
+    servers: &[McpServer],
+    from: &BTreeMap<String, Vec<String>>,
+    to: &BTreeMap<String, Vec<String>>,
+) -> Vec<ProfileServerDiff> {
+    servers
+        .iter()
+        .filter_map(|server| {
+            let id = server.id.to_string();
+            let target = to.get(&id)?;
+            let source = from.get(&id).map(Vec::as_slice).unwrap_or_default();
+
+            let diff = ProfileServerDiff {
+                enabled: target.iter().filter(|c| !source.contains(c)).cloned().collect(),
+                disabled: source.iter().filter(|c| !target.contains(c)).cloned().collect(),
+                server_id: id,
+                server_name: server.name.clone(),
+            };
+            (!diff.enabled.is_empty() || !diff.disabled.is_empty()).then_some(diff)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ServerSource, Transport};
+    use std::collections::HashMap;
+
+    fn server(name: &str, clients: &[&str]) -> McpServer {
+        let mut server = McpServer::new(
+            name.to_string(),
+            ServerSource::Local {
+                path: format!("/bin/{}", name),
+            },
+            Transport::Stdio {
+                command: format!("/bin/{}", name),
+                args: vec![],
+                env: HashMap::new(),
+            },
+        );
+        for client in clients {
+            server.enable_for_client(client);
+        }
+        server
+    }
+
+    #[test]
+    fn test_diff_enablement() {
+        let servers = vec![server("a", &["cursor", "zed"]), server("b", &[]), server("c", &[])];
+        let current = enablement(&servers);
+
+        let mut target = current.clone();
+        let clients = vec!["claude-code".to_string(), "zed".to_string()];
+        target.insert(servers[0].id.to_string(), clients);
+        target.remove(&servers[1].id.to_string());
+
+        let diff = diff_enablement(&servers, &current, &target);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].server_name, "a");
+        assert_eq!(diff[0].enabled, vec!["claude-code"]);
+        assert_eq!(diff[0].disabled, vec!["cursor"]);
+
+        // A server the source does not know counts as enabled nowhere
+        let mut source = current.clone();
+        source.remove(&servers[2].id.to_string());
+        target.insert(servers[2].id.to_string(), vec!["cursor".to_string()]);
+        let diff = diff_enablement(&servers, &source, &target);
+        assert_eq!(diff[1].server_name, "c");
+        assert_eq!(diff[1].enabled, vec!["cursor"]);
+
+        assert!(diff_enablement(&servers, &current, &current).is_empty());
+    }
+}
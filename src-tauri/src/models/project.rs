@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::ClientId;
+
+/// A project-level MCP config file that can be synced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectTarget {
+    /// `<project>/.mcp.json` - Claude Code project scope, meant to be committed
+    McpJson,
+    /// `projects.<project>.mcpServers` in `~/.claude.json` - Claude Code local scope
+    ClaudeCodeLocal,
+    /// `<project>/.cursor/mcp.json`
+    Cursor,
+    /// `<project>/.vscode/mcp.json`
+    Vscode,
+}
+
+impl ProjectTarget {
+    /// Get all supported project targets
+    pub fn all() -> Vec<ProjectTarget> {
+        vec![
+            ProjectTarget::McpJson,
+            ProjectTarget::ClaudeCodeLocal,
+            ProjectTarget::Cursor,
+            ProjectTarget::Vscode,
+        ]
+    }
+
+    /// Get the string identifier for this target (used in config)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectTarget::McpJson => "mcp-json",
+            ProjectTarget::ClaudeCodeLocal => "claude-code-local",
+            ProjectTarget::Cursor => "cursor",
+            ProjectTarget::Vscode => "vscode",
+        }
+    }
+
+    /// The client that reads this file
+    pub fn client_id(&self) -> ClientId {
+        match self {
+            ProjectTarget::McpJson | ProjectTarget::ClaudeCodeLocal => ClientId::ClaudeCode,
+            ProjectTarget::Cursor => ClientId::Cursor,
+            ProjectTarget::Vscode => ClientId::Vscode,
+        }
+    }
+
+    /// Whether the file lives in the project tree and is likely shared via version control
+    pub fn is_shared(&self) -> bool {
+        !matches!(self, ProjectTarget::ClaudeCodeLocal)
+    }
+
+    /// Keys leading from the document root to the server map
+    pub fn servers_pointer(&self, project_path: &str) -> Vec<String> {
+        match self {
+            ProjectTarget::McpJson | ProjectTarget::Cursor => vec!["mcpServers".to_string()],
+            ProjectTarget::ClaudeCodeLocal => vec![
+                "projects".to_string(),
+                project_path.to_string(),
+                "mcpServers".to_string(),
+            ],
+            // Workspace mcp.json keeps servers at the top level, unlike settings.json
+            ProjectTarget::Vscode => vec!["servers".to_string()],
+        }
+    }
+}
+
+impl std::fmt::Display for ProjectTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A registered project directory with its own server selection
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSettings {
+    /// Absolute path of the project root
+    pub path: String,
+    /// Display name
+    pub name: String,
+    /// Project-level config files to write
+    pub targets: Vec<ProjectTarget>,
+    /// IDs of the servers that apply to this project
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// Names of the entries Nexus wrote to each target at last sync, keyed by target
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub managed_servers: HashMap<String, Vec<String>>,
+    /// ISO timestamp of last successful sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sync: Option<String>,
+}
+
+impl ProjectSettings {
+    /// Create a project with no servers selected
+    pub fn new(path: String, name: String, targets: Vec<ProjectTarget>) -> Self {
+        Self {
+            path,
+            name,
+            targets,
+            servers: vec![],
+            managed_servers: HashMap::new(),
+            last_sync: None,
+        }
+    }
+
+    /// Check if a server is selected for this project
+    pub fn includes_server(&self, server_id: &uuid::Uuid) -> bool {
+        self.servers.contains(&server_id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_target_serialization() {
+        let json = serde_json::to_string(&ProjectTarget::ClaudeCodeLocal).unwrap();
+        assert_eq!(json, r#""claude-code-local""#);
+
+        for target in ProjectTarget::all() {
+            let json = serde_json::to_value(target).unwrap();
+            assert_eq!(json, serde_json::json!(target.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_project_target_servers_pointer() {
+        assert_eq!(
+            ProjectTarget::ClaudeCodeLocal.servers_pointer("/work/app"),
+            vec!["projects", "/work/app", "mcpServers"]
+        );
+        assert_eq!(ProjectTarget::Vscode.servers_pointer("/work/app"), vec!["servers"]);
+        assert_eq!(ProjectTarget::McpJson.client_id(), ClientId::ClaudeCode);
+        assert!(!ProjectTarget::ClaudeCodeLocal.is_shared());
+    }
+}
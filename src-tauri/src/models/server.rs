@@ -18,7 +18,7 @@ pub enum ServerSource {
     Local { path: String },
     /// Docker image
     Docker { image: String },
-    /// Remote server (SSE)
+    /// Remote server (SSE or Streamable HTTP)
     Remote { url: String },
     /// GitHub repository to be cloned
     Github {
@@ -46,6 +46,96 @@ pub enum Transport {
         #[serde(default, skip_serializing_if = "HashMap::is_empty")]
         headers: HashMap<String, String>,
     },
+    /// Streamable HTTP for remote servers (current MCP spec)
+    Http {
+        url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+    },
+}
+
+/// How a client override changes the server's base args
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", content = "values", rename_all = "lowercase")]
+pub enum ArgsOverride {
+    /// Add these args after the base args
+    Append(Vec<String>),
+    /// Use these args instead of the base args
+    Replace(Vec<String>),
+}
+
+/// Per-client adjustments applied on top of a server's transport
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOverride {
+    /// Args change (stdio only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<ArgsOverride>,
+    /// Env vars merged over the base env (stdio only)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Headers merged over the base headers (remote only)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+impl ClientOverride {
+    /// Whether this override changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.args.is_none() && self.env.is_empty() && self.headers.is_empty()
+    }
+
+    /// Apply this override to a transport
+    pub fn apply(&self, transport: &Transport) -> Transport {
+        let mut transport = transport.clone();
+
+        match &mut transport {
+            Transport::Stdio { args, env, .. } => {
+                match &self.args {
+                    Some(ArgsOverride::Append(extra)) => args.extend(extra.iter().cloned()),
+                    Some(ArgsOverride::Replace(replacement)) => *args = replacement.clone(),
+                    None => {}
+                }
+                env.extend(self.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            Transport::Sse { headers, .. } | Transport::Http { headers, .. } => {
+                headers.extend(self.headers.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+
+        transport
+    }
+}
+
+/// Client-specific entry settings that don't change how the server runs.
+///
+/// Only clients with a matching key receive each option (e.g. Cline's
+/// `autoApprove` is written as `alwaysAllow` for Roo Code).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOptions {
+    /// Keep the entry in the client but turn it off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+    /// Tools the client may call without asking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_approve: Option<Vec<String>>,
+    /// Tools hidden from the model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_tools: Option<Vec<String>>,
+    /// Request timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl ClientOptions {
+    /// Whether no option is set
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_none()
+            && self.auto_approve.is_none()
+            && self.disabled_tools.is_none()
+            && self.timeout.is_none()
+    }
 }
 
 /// Represents an MCP server configuration
@@ -82,6 +172,12 @@ pub struct McpServer {
     /// Tags for categorization
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Per-client transport overrides, keyed by client ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub client_overrides: HashMap<String, ClientOverride>,
+    /// Per-client entry options (disabled, auto-approve, ...), keyed by client ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub client_options: HashMap<String, ClientOptions>,
 }
 
 fn default_true() -> bool {
@@ -106,6 +202,8 @@ impl McpServer {
             installed_version: None,
             source_url: None,
             tags: vec![],
+            client_overrides: HashMap::new(),
+            client_options: HashMap::new(),
         }
     }
 
@@ -128,6 +226,40 @@ impl McpServer {
         self.enabled_clients.retain(|c| c != client_id);
         self.updated_at = chrono::Utc::now().to_rfc3339();
     }
+
+    /// Set or clear (with `None` or an empty override) a client's override
+    pub fn set_client_override(&mut self, client_id: &str, client_override: Option<ClientOverride>) {
+        match client_override {
+            Some(o) if !o.is_empty() => {
+                self.client_overrides.insert(client_id.to_string(), o);
+            }
+            _ => {
+                self.client_overrides.remove(client_id);
+            }
+        }
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    /// Set or clear (with `None` or empty options) a client's entry options
+    pub fn set_client_options(&mut self, client_id: &str, options: Option<ClientOptions>) {
+        match options {
+            Some(o) if !o.is_empty() => {
+                self.client_options.insert(client_id.to_string(), o);
+            }
+            _ => {
+                self.client_options.remove(client_id);
+            }
+        }
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    /// The transport as seen by a specific client, with its override applied
+    pub fn transport_for_client(&self, client_id: &str) -> Transport {
+        match self.client_overrides.get(client_id) {
+            Some(o) => o.apply(&self.transport),
+            None => self.transport.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -229,4 +361,177 @@ mod tests {
         assert!(json.contains("\"type\": \"sse\""));
         assert!(json.contains("\"url\": \"https://api.example.com/mcp/sse\""));
     }
+
+    #[test]
+    fn test_http_transport() {
+        let server = McpServer::new(
+            "remote-http".to_string(),
+            ServerSource::Remote {
+                url: "https://api.example.com/mcp".to_string(),
+            },
+            Transport::Http {
+                url: "https://api.example.com/mcp".to_string(),
+                headers: HashMap::new(),
+            },
+        );
+
+        let json = serde_json::to_string_pretty(&server).unwrap();
+        assert!(json.contains("\"type\": \"http\""));
+        assert!(!json.contains("headers"));
+
+        let parsed: McpServer = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.transport, server.transport);
+    }
+
+    #[test]
+    fn test_client_override_append_args_and_merge_env() {
+        let mut server = McpServer::new(
+            "fs".to_string(),
+            ServerSource::Local {
+                path: "/path/to/server".to_string(),
+            },
+            Transport::Stdio {
+                command: "node".to_string(),
+                args: vec!["index.js".to_string()],
+                env: HashMap::from([
+                    ("API_KEY".to_string(), "base".to_string()),
+                    ("LOG".to_string(), "info".to_string()),
+                ]),
+            },
+        );
+
+        server.set_client_override(
+            "cursor",
+            Some(ClientOverride {
+                args: Some(ArgsOverride::Append(vec!["--root".to_string(), "/work".to_string()])),
+                env: HashMap::from([("API_KEY".to_string(), "cursor-key".to_string())]),
+                headers: HashMap::new(),
+            }),
+        );
+
+        match server.transport_for_client("cursor") {
+            Transport::Stdio { args, env, .. } => {
+                assert_eq!(args, vec!["index.js", "--root", "/work"]);
+                assert_eq!(env.get("API_KEY").unwrap(), "cursor-key");
+                assert_eq!(env.get("LOG").unwrap(), "info");
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+
+        // Other clients see the base transport
+        assert_eq!(server.transport_for_client("claude-desktop"), server.transport);
+    }
+
+    #[test]
+    fn test_client_override_replace_args_and_clear() {
+        let mut server = McpServer::new(
+            "fs".to_string(),
+            ServerSource::Local {
+                path: "/path/to/server".to_string(),
+            },
+            Transport::Stdio {
+                command: "node".to_string(),
+                args: vec!["index.js".to_string(), "--root".to_string(), "/a".to_string()],
+                env: HashMap::new(),
+            },
+        );
+
+        server.set_client_override(
+            "vscode",
+            Some(ClientOverride {
+                args: Some(ArgsOverride::Replace(vec!["index.js".to_string()])),
+                ..Default::default()
+            }),
+        );
+        match server.transport_for_client("vscode") {
+            Transport::Stdio { args, .. } => assert_eq!(args, vec!["index.js"]),
+            _ => panic!("Expected Stdio transport"),
+        }
+
+        // An empty override clears the entry
+        server.set_client_override("vscode", Some(ClientOverride::default()));
+        assert!(server.client_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_client_override_merges_headers() {
+        let mut server = McpServer::new(
+            "remote".to_string(),
+            ServerSource::Remote {
+                url: "https://api.example.com/mcp".to_string(),
+            },
+            Transport::Http {
+                url: "https://api.example.com/mcp".to_string(),
+                headers: HashMap::from([("X-Team".to_string(), "core".to_string())]),
+            },
+        );
+
+        server.set_client_override(
+            "claude-code",
+            Some(ClientOverride {
+                headers: HashMap::from([(
+                    "Authorization".to_string(),
+                    "keychain:claude-code-token".to_string(),
+                )]),
+                ..Default::default()
+            }),
+        );
+
+        match server.transport_for_client("claude-code") {
+            Transport::Http { headers, .. } => {
+                assert_eq!(headers.len(), 2);
+                assert_eq!(headers.get("Authorization").unwrap(), "keychain:claude-code-token");
+            }
+            _ => panic!("Expected Http transport"),
+        }
+    }
+
+    #[test]
+    fn test_client_override_serialization() {
+        let client_override = ClientOverride {
+            args: Some(ArgsOverride::Append(vec!["--verbose".to_string()])),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&client_override).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"args": {"mode": "append", "values": ["--verbose"]}})
+        );
+
+        let parsed: ClientOverride = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, client_override);
+    }
+
+    #[test]
+    fn test_client_options_set_and_clear() {
+        let mut server = McpServer::new(
+            "fs".to_string(),
+            ServerSource::Local {
+                path: "/path/to/server".to_string(),
+            },
+            Transport::Stdio {
+                command: "node".to_string(),
+                args: vec![],
+                env: HashMap::new(),
+            },
+        );
+
+        let options = ClientOptions {
+            disabled: Some(true),
+            auto_approve: Some(vec!["read_file".to_string()]),
+            ..Default::default()
+        };
+        server.set_client_options("cline", Some(options.clone()));
+        assert_eq!(server.client_options.get("cline"), Some(&options));
+
+        let json = serde_json::to_value(&server).unwrap();
+        assert_eq!(
+            json["clientOptions"]["cline"],
+            serde_json::json!({"disabled": true, "autoApprove": ["read_file"]})
+        );
+
+        server.set_client_options("cline", Some(ClientOptions::default()));
+        assert!(server.client_options.is_empty());
+    }
 }
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use super::IssueSeverity;
+
+/// A problem found in one field of a server definition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerDiagnostic {
+    /// Path of the field in the server's JSON form, e.g. `transport.env.API_KEY`
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl ServerDiagnostic {
+    pub fn error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            severity: IssueSeverity::Error,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            severity: IssueSeverity::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// Whether this diagnostic blocks saving the server
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl std::fmt::Display for ServerDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
@@ -0,0 +1,59 @@
+// src-tauri/src/services/app_log.rs
+//! Log of problems Nexus works around without failing
+//!
+//! Warnings such as a skipped client descriptor or an unreadable journal line
+//! are appended to `~/.mcp-nexus/logs/nexus.log`, next to the auto-sync log,
+//! so they can be found after the fact instead of being lost on stderr.
+
+use chrono::Utc;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Log file inside the `logs` directory of the Nexus config directory
+const LOG_FILE: &str = "nexus.log";
+
+/// Append a timestamped warning to `<config_dir>/logs/nexus.log`.
+///
+/// Best effort: a log that cannot be written is skipped.
+pub fn log_warning_in(config_dir: &Path, message: &str) {
+    let log_dir = config_dir.join("logs");
+    if fs::create_dir_all(&log_dir).is_err() {
+        return;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_dir.join(LOG_FILE));
+    if let Ok(mut file) = file {
+        let _ = writeln!(file, "{} {}", Utc::now().to_rfc3339(), message);
+    }
+}
+
+/// Append a timestamped warning to `~/.mcp-nexus/logs/nexus.log`
+pub fn log_warning(message: &str) {
+    if let Some(home) = dirs::home_dir() {
+        log_warning_in(&home.join(".mcp-nexus"), message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_log_warning_appends_lines() {
+        let temp_dir = TempDir::new().unwrap();
+
+        log_warning_in(temp_dir.path(), "first");
+        log_warning_in(temp_dir.path(), "second");
+
+        let log = fs::read_to_string(temp_dir.path().join("logs").join(LOG_FILE)).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" first"));
+        assert!(lines[1].ends_with(" second"));
+    }
+}
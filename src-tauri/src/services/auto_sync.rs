@@ -0,0 +1,285 @@
+// src-tauri/src/services/auto_sync.rs
+//! Syncing clients automatically when the central config changes
+//!
+//! [`affected_clients`] compares a config before and after a save to find the
+//! clients whose synced output would differ; [`AutoSyncScheduler`] collects
+//! those over a quiet period and hands them to a background sync.
+
+use crate::models::{ClientId, ClientOptions, DetectedClient, McpHubConfig, SyncMode, Transport};
+use crate::services::sync_engine::{
+    enabled_clients, summarize_sync_results, sync_to_client, ClientSyncResult, SyncResult,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Quiet period after the last change before syncing
+pub const AUTO_SYNC_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Name of the progress event emitted to the frontend
+pub const AUTO_SYNC_EVENT: &str = "auto-sync";
+
+/// Progress of a background auto-sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "phase", rename_all = "camelCase")]
+pub enum AutoSyncProgress {
+    /// Syncing is about to start for these clients
+    Started { clients: Vec<ClientId> },
+    /// One client has been synced
+    ClientSynced { result: ClientSyncResult },
+    /// Every client has been synced and the results recorded
+    Finished { result: SyncResult },
+}
+
+/// What a client would receive from a config: each server's name, transport and options
+fn client_view(
+    config: &McpHubConfig,
+    client_id: ClientId,
+) -> Vec<(&str, Transport, Option<&ClientOptions>)> {
+    let mut view: Vec<_> = config
+        .get_servers_for_client(client_id.as_str())
+        .into_iter()
+        .map(|server| {
+            (
+                server.name.as_str(),
+                server.transport_for_client(client_id.as_str()),
+                server.client_options.get(client_id.as_str()),
+            )
+        })
+        .collect();
+    view.sort_by(|a, b| a.0.cmp(b.0));
+    view
+}
+
+/// Clients whose synced config would change between `previous` and `current`.
+///
+/// Only sync-enabled, file-based clients are returned. A client that was just
+/// re-enabled counts as affected. With no previous config every such client is.
+/// A save that records a new sync for a client already covers it.
+pub fn affected_clients(previous: Option<&McpHubConfig>, current: &McpHubConfig) -> Vec<ClientId> {
+    let was_enabled = |client_id: ClientId| match previous {
+        Some(config) => enabled_clients(config).contains(&client_id),
+        None => false,
+    };
+    let last_sync = |config: &McpHubConfig, client_id: ClientId| {
+        config.clients.get(client_id.as_str()).and_then(|s| s.last_sync.clone())
+    };
+    let just_synced = |client_id: ClientId| match previous {
+        Some(previous) => last_sync(previous, client_id) != last_sync(current, client_id),
+        None => false,
+    };
+
+    enabled_clients(current)
+        .into_iter()
+        .filter(|id| DetectedClient::new(*id).sync_mode == SyncMode::Automatic)
+        .filter(|id| !just_synced(*id))
+        .filter(|id| match previous {
+            Some(previous) if was_enabled(*id) => {
+                client_view(previous, *id) != client_view(current, *id)
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Sync the given clients, reporting progress as each one finishes
+pub fn auto_sync_clients<F>(
+    config: &McpHubConfig,
+    clients: &[ClientId],
+    mut on_progress: F,
+) -> SyncResult
+where
+    F: FnMut(AutoSyncProgress),
+{
+    // Skip clients disabled since the sync was scheduled
+    let enabled = enabled_clients(config);
+    let clients: Vec<ClientId> = clients
+        .iter()
+        .copied()
+        .filter(|client_id| enabled.contains(client_id))
+        .collect();
+
+    on_progress(AutoSyncProgress::Started {
+        clients: clients.clone(),
+    });
+
+    let results = clients
+        .iter()
+        .map(|client_id| {
+            let result = sync_to_client(*client_id, config);
+            on_progress(AutoSyncProgress::ClientSynced {
+                result: result.clone(),
+            });
+            result
+        })
+        .collect();
+
+    summarize_sync_results(results)
+}
+
+/// Debounces change notifications and runs syncs on a background thread
+pub struct AutoSyncScheduler {
+    sender: Sender<Vec<ClientId>>,
+}
+
+impl AutoSyncScheduler {
+    /// Start the background thread; `run` receives every client scheduled during a quiet period
+    pub fn start<F>(debounce: Duration, run: F) -> Self
+    where
+        F: Fn(Vec<ClientId>) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Vec<ClientId>>();
+
+        thread::spawn(move || {
+            while let Ok(mut pending) = receiver.recv() {
+                let mut disconnected = false;
+
+                // Keep collecting until nothing new arrives for `debounce`
+                loop {
+                    match receiver.recv_timeout(debounce) {
+                        Ok(clients) => {
+                            for client in clients {
+                                if !pending.contains(&client) {
+                                    pending.push(client);
+                                }
+                            }
+                        }
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            disconnected = true;
+                            break;
+                        }
+                    }
+                }
+
+                run(pending);
+                if disconnected {
+                    return;
+                }
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Queue clients for the next sync (an empty list is ignored)
+    pub fn schedule(&self, clients: Vec<ClientId>) {
+        if !clients.is_empty() {
+            let _ = self.sender.send(clients);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ClientOverride, ClientSettings, McpServer, ServerSource};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn config_with_server() -> McpHubConfig {
+        let mut server = McpServer::new(
+            "fs".to_string(),
+            ServerSource::Local {
+                path: "/bin/fs".to_string(),
+            },
+            Transport::Stdio {
+                command: "/bin/fs".to_string(),
+                args: vec![],
+                env: HashMap::new(),
+            },
+        );
+        server.enable_for_client("cursor");
+        server.enable_for_client("claude-code");
+
+        let mut config = McpHubConfig::default();
+        config.servers.push(server);
+        config
+    }
+
+    #[test]
+    fn test_affected_clients_only_includes_changed_clients() {
+        let previous = config_with_server();
+        assert!(affected_clients(Some(&previous), &previous).is_empty());
+
+        // An override changes only that client's output
+        let mut current = previous.clone();
+        current.servers[0].set_client_override(
+            "cursor",
+            Some(ClientOverride {
+                args: Some(crate::models::ArgsOverride::Append(vec!["-v".to_string()])),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(affected_clients(Some(&previous), &current), vec![ClientId::Cursor]);
+
+        // Toggling a client off changes its server list
+        let mut current = previous.clone();
+        current.servers[0].disable_for_client("claude-code");
+        assert_eq!(affected_clients(Some(&previous), &current), vec![ClientId::ClaudeCode]);
+    }
+
+    #[test]
+    fn test_affected_clients_sync_settings() {
+        let previous = config_with_server();
+
+        // Recording sync metadata is not a change
+        let mut current = previous.clone();
+        current.clients.insert(
+            "cursor".to_string(),
+            ClientSettings {
+                enabled: true,
+                last_sync: Some("2026-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(affected_clients(Some(&previous), &current).is_empty());
+
+        // A disabled client is never synced, and re-enabling it triggers a sync
+        current.clients.get_mut("cursor").unwrap().enabled = false;
+        current.servers[0].disable_for_client("cursor");
+        assert!(affected_clients(Some(&previous), &current).is_empty());
+        let reenabled = {
+            let mut config = current.clone();
+            config.clients.get_mut("cursor").unwrap().enabled = true;
+            config
+        };
+        assert_eq!(affected_clients(Some(&current), &reenabled), vec![ClientId::Cursor]);
+
+        // Manual-only clients are left out
+        assert!(!affected_clients(None, &previous).contains(&ClientId::Warp));
+
+        // A change saved together with a fresh sync of the client needs no second sync
+        let mut synced = previous.clone();
+        synced.servers[0].disable_for_client("claude-code");
+        synced.clients.insert(
+            "claude-code".to_string(),
+            ClientSettings {
+                enabled: true,
+                last_sync: Some("2026-01-02T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(affected_clients(Some(&previous), &synced).is_empty());
+    }
+
+    #[test]
+    fn test_scheduler_debounces_and_merges() {
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&runs);
+        let scheduler = AutoSyncScheduler::start(Duration::from_millis(50), move |clients| {
+            recorded.lock().unwrap().push(clients);
+        });
+
+        scheduler.schedule(vec![ClientId::Cursor]);
+        scheduler.schedule(vec![]);
+        scheduler.schedule(vec![ClientId::ClaudeCode, ClientId::Cursor]);
+        thread::sleep(Duration::from_millis(400));
+
+        assert_eq!(
+            *runs.lock().unwrap(),
+            vec![vec![ClientId::Cursor, ClientId::ClaudeCode]]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+// src-tauri/src/services/backups.rs
+//! Timestamped backup history for client config files
+//!
+//! Every sync snapshots the client's current config into
+//! `~/.mcp-nexus/backups/<client-id>/` before rewriting it. Snapshots are
+//! named `<timestamp>_<file name>` so they sort chronologically, and the
+//! oldest ones are pruned once the configured retention count is exceeded.
+//! Project-level configs get their own history under `backups/projects/`.
+
+use crate::models::{ClientId, ProjectTarget};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Timestamp prefix format for snapshot file names (UTC, millisecond precision)
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Home directory not found")]
+    HomeNotFound,
+    #[error("Backup not found: {0}")]
+    NotFound(String),
+    #[error("Invalid backup ID: {0}")]
+    InvalidId(String),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// A single snapshot in a client's backup history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    /// Snapshot file name, used to identify it when restoring
+    pub id: String,
+    pub client_id: ClientId,
+    pub path: String,
+    /// ISO timestamp of when the snapshot was taken
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// Result of restoring a client config from a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResult {
+    pub client_id: ClientId,
+    pub restored_from: String,
+    pub config_path: String,
+    /// Snapshot of the file as it was just before the restore
+    pub safety_backup: Option<String>,
+}
+
+/// Root directory holding the backup history of every client
+pub fn backups_root() -> Result<PathBuf, BackupError> {
+    let home = dirs::home_dir().ok_or(BackupError::HomeNotFound)?;
+    Ok(home.join(".mcp-nexus").join("backups"))
+}
+
+/// Directory holding the backup history of a single client
+pub fn client_backup_dir(client_id: ClientId) -> Result<PathBuf, BackupError> {
+    Ok(backups_root()?.join(client_id.as_str()))
+}
+
+/// Directory holding the backup history of one project-level config file
+pub fn project_backup_dir(project_path: &str, target: ProjectTarget) -> Result<PathBuf, BackupError> {
+    // Flatten the project path into a single readable directory name
+    let key: String = project_path
+        .trim_matches(|c| c == '/' || c == '\\')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+
+    Ok(backups_root()?
+        .join("projects")
+        .join(key)
+        .join(target.as_str()))
+}
+
+/// Ensure a backup directory exists with user-only permissions
+fn ensure_backup_dir(dir: &Path) -> Result<(), BackupError> {
+    if !dir.exists() {
+        fs::create_dir_all(dir)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = fs::Permissions::from_mode(0o700);
+            fs::set_permissions(dir, permissions)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Snapshot `source` into `backup_dir`, then prune down to `retention` snapshots.
+///
+/// Returns `None` if `source` does not exist (nothing to back up).
+pub fn create_snapshot(
+    backup_dir: &Path,
+    source: &Path,
+    retention: usize,
+) -> Result<Option<PathBuf>, BackupError> {
+    if !source.exists() {
+        return Ok(None);
+    }
+
+    ensure_backup_dir(backup_dir)?;
+
+    let file_name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config.json".to_string());
+    let timestamp = chrono::Utc::now().format(TIMESTAMP_FORMAT).to_string();
+
+    // Snapshots taken in the same millisecond get an increasing counter
+    // suffix so they still sort after every earlier one
+    let latest_counter = snapshot_names(backup_dir)?
+        .iter()
+        .map(|name| snapshot_sort_key(name))
+        .filter(|(ts, _)| *ts == timestamp)
+        .map(|(_, counter)| counter)
+        .max();
+    let snapshot_name = match latest_counter {
+        Some(counter) => format!("{}-{}_{}", timestamp, counter + 1, file_name),
+        None => format!("{}_{}", timestamp, file_name),
+    };
+    let snapshot_path = backup_dir.join(snapshot_name);
+
+    fs::copy(source, &snapshot_path)?;
+
+    // Snapshots may contain resolved credentials
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let permissions = fs::Permissions::from_mode(0o600);
+        fs::set_permissions(&snapshot_path, permissions)?;
+    }
+
+    prune_snapshots(backup_dir, retention)?;
+
+    Ok(Some(snapshot_path))
+}
+
+/// Snapshot file names in `backup_dir`, oldest first
+fn snapshot_names(backup_dir: &Path) -> Result<Vec<String>, BackupError> {
+    if !backup_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut names: Vec<String> = fs::read_dir(backup_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| parse_snapshot_timestamp(name).is_some())
+        .collect();
+
+    names.sort_by_key(|name| snapshot_sort_key(name));
+    Ok(names)
+}
+
+/// Chronological sort key: timestamp prefix, then collision counter
+fn snapshot_sort_key(name: &str) -> (String, u32) {
+    let prefix = name.split_once('_').map(|(p, _)| p).unwrap_or(name);
+    match prefix.split_once('-') {
+        Some((timestamp, counter)) => (timestamp.to_string(), counter.parse().unwrap_or(0)),
+        None => (prefix.to_string(), 0),
+    }
+}
+
+/// Delete the oldest snapshots so at most `retention` remain (always keeps one)
+fn prune_snapshots(backup_dir: &Path, retention: usize) -> Result<(), BackupError> {
+    let names = snapshot_names(backup_dir)?;
+    let keep = retention.max(1);
+
+    if names.len() > keep {
+        for name in &names[..names.len() - keep] {
+            fs::remove_file(backup_dir.join(name))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the creation time encoded in a snapshot file name
+fn parse_snapshot_timestamp(name: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let (prefix, _) = name.split_once('_')?;
+    // Strip the collision counter, if any
+    let timestamp = prefix.split('-').next()?;
+    chrono::NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// List the snapshots in `backup_dir`, newest first
+pub fn list_snapshots(
+    backup_dir: &Path,
+    client_id: ClientId,
+) -> Result<Vec<BackupEntry>, BackupError> {
+    let mut entries = Vec::new();
+
+    for name in snapshot_names(backup_dir)?.into_iter().rev() {
+        let path = backup_dir.join(&name);
+        let size_bytes = fs::metadata(&path)?.len();
+        let created_at = parse_snapshot_timestamp(&name)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default();
+
+        entries.push(BackupEntry {
+            id: name,
+            client_id,
+            path: path.to_string_lossy().to_string(),
+            created_at,
+            size_bytes,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Resolve a backup ID to its snapshot path, rejecting anything outside `backup_dir`
+pub fn snapshot_path(backup_dir: &Path, backup_id: &str) -> Result<PathBuf, BackupError> {
+    let is_plain_name = !backup_id.is_empty()
+        && !backup_id.contains('/')
+        && !backup_id.contains('\\')
+        && backup_id != "."
+        && backup_id != "..";
+
+    if !is_plain_name || parse_snapshot_timestamp(backup_id).is_none() {
+        return Err(BackupError::InvalidId(backup_id.to_string()));
+    }
+
+    let path = backup_dir.join(backup_id);
+    if !path.is_file() {
+        return Err(BackupError::NotFound(backup_id.to_string()));
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("mcp.json");
+        let backup_dir = temp_dir.path().join("backups").join("cursor");
+        fs::write(&source, "original content").unwrap();
+
+        let snapshot = create_snapshot(&backup_dir, &source, 10).unwrap().unwrap();
+
+        assert!(snapshot.starts_with(&backup_dir));
+        assert!(snapshot.to_string_lossy().ends_with("_mcp.json"));
+        assert_eq!(fs::read_to_string(&snapshot).unwrap(), "original content");
+    }
+
+    #[test]
+    fn test_create_snapshot_no_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("nonexistent.json");
+        let backup_dir = temp_dir.path().join("backups");
+
+        let snapshot = create_snapshot(&backup_dir, &source, 10).unwrap();
+        assert!(snapshot.is_none());
+        assert!(!backup_dir.exists());
+    }
+
+    #[test]
+    fn test_snapshots_are_not_overwritten_and_are_pruned() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("mcp.json");
+        let backup_dir = temp_dir.path().join("backups");
+
+        for i in 0..5 {
+            fs::write(&source, format!("version {}", i)).unwrap();
+            create_snapshot(&backup_dir, &source, 3).unwrap();
+        }
+
+        let entries = list_snapshots(&backup_dir, ClientId::Cursor).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        // Newest first, and the oldest two were pruned
+        let contents: Vec<String> = entries
+            .iter()
+            .map(|e| fs::read_to_string(&e.path).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["version 4", "version 3", "version 2"]);
+        assert!(!entries[0].created_at.is_empty());
+        assert_eq!(entries[0].size_bytes, "version 4".len() as u64);
+    }
+
+    #[test]
+    fn test_list_snapshots_ignores_unrelated_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "hello").unwrap();
+
+        let entries = list_snapshots(temp_dir.path(), ClientId::Cursor).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_path_rejects_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert!(matches!(
+            snapshot_path(temp_dir.path(), "../config.json"),
+            Err(BackupError::InvalidId(_))
+        ));
+        assert!(matches!(
+            snapshot_path(temp_dir.path(), "20260101T000000000Z_mcp.json"),
+            Err(BackupError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_project_backup_dir() {
+        let dir = project_backup_dir("/home/me/work/my app", ProjectTarget::McpJson).unwrap();
+        assert!(dir.ends_with(Path::new("projects").join("home_me_work_my_app").join("mcp-json")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_snapshot_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("mcp.json");
+        let backup_dir = temp_dir.path().join("backups");
+        fs::write(&source, "{}").unwrap();
+
+        let snapshot = create_snapshot(&backup_dir, &source, 10).unwrap().unwrap();
+
+        let mode = fs::metadata(&snapshot).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let dir_mode = fs::metadata(&backup_dir).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+    }
+}
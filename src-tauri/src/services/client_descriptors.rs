@@ -0,0 +1,199 @@
+// src-tauri/src/services/client_descriptors.rs
+//! Loading user-defined client descriptors from `~/.mcp-nexus/clients.d/*.json`
+//!
+//! Each file describes one client (see [`ClientDescriptor`]). Loaded
+//! descriptors are registered globally so that `ClientId::Custom` clients
+//! flow through detection, import and sync like built-in ones.
+
+use crate::models::{set_custom_clients, ClientDescriptor, ClientId};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A descriptor file that could not be loaded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorLoadError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Outcome of loading the descriptor directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorLoadResult {
+    /// Directory that was scanned
+    pub directory: String,
+    /// Descriptors that loaded successfully
+    pub clients: Vec<ClientDescriptor>,
+    /// Files that were skipped, with the reason
+    pub errors: Vec<DescriptorLoadError>,
+}
+
+/// Directory holding client descriptor files
+pub fn descriptors_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".mcp-nexus").join("clients.d"))
+}
+
+/// Check a descriptor's fields, returning a description of the first problem
+fn validate_descriptor(descriptor: &ClientDescriptor) -> Result<(), String> {
+    let id = &descriptor.id;
+    let valid_id = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_id {
+        return Err(format!(
+            "Invalid id '{}': use lowercase letters, digits and dashes",
+            id
+        ));
+    }
+
+    if ClientId::all().iter().any(|c| c.as_str() == id) {
+        return Err(format!("Id '{}' is already used by a built-in client", id));
+    }
+
+    if descriptor.name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+
+    let keys = descriptor.servers_keys();
+    if !descriptor.servers_pointer.starts_with('/') || keys.iter().any(|k| k.is_empty()) {
+        return Err(format!(
+            "Invalid serversPointer '{}': expected a JSON pointer such as \"/mcpServers\"",
+            descriptor.servers_pointer
+        ));
+    }
+
+    let paths = &descriptor.config_path;
+    if ["macos", "linux", "windows"]
+        .iter()
+        .all(|os| paths.for_os(os).is_none())
+    {
+        return Err("configPath must name at least one location".to_string());
+    }
+
+    Ok(())
+}
+
+/// Load every `*.json` descriptor in `dir`, in file name order.
+///
+/// A missing directory yields no descriptors. Invalid files, and files
+/// reusing an id that was already loaded, are reported in `errors`.
+pub fn load_descriptors(dir: &Path) -> DescriptorLoadResult {
+    let mut result = DescriptorLoadResult {
+        directory: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return result;
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    for path in files {
+        let loaded = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read file: {}", e))
+            .and_then(|content| {
+                serde_json::from_str::<ClientDescriptor>(&content)
+                    .map_err(|e| format!("Failed to parse descriptor: {}", e))
+            })
+            .and_then(|descriptor| {
+                validate_descriptor(&descriptor)?;
+                if result.clients.iter().any(|c| c.id == descriptor.id) {
+                    return Err(format!("Duplicate client id '{}'", descriptor.id));
+                }
+                Ok(descriptor)
+            });
+
+        match loaded {
+            Ok(descriptor) => result.clients.push(descriptor),
+            Err(message) => result.errors.push(DescriptorLoadError {
+                path: path.to_string_lossy().to_string(),
+                message,
+            }),
+        }
+    }
+
+    result
+}
+
+/// Load descriptors from [`descriptors_dir`] and register them as custom clients
+pub fn reload_custom_clients() -> DescriptorLoadResult {
+    let result = match descriptors_dir() {
+        Some(dir) => load_descriptors(&dir),
+        None => DescriptorLoadResult::default(),
+    };
+
+    set_custom_clients(result.clients.clone());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ACME: &str = r#"{
+        "id": "acme-agent",
+        "name": "Acme Agent",
+        "configPath": {"default": "~/.acme/mcp.json"},
+        "serversPointer": "/mcpServers"
+    }"#;
+
+    #[test]
+    fn test_load_descriptors() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("acme.json"), ACME).unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+        fs::write(temp_dir.path().join("broken.json"), "{").unwrap();
+        fs::write(
+            temp_dir.path().join("zz-duplicate.json"),
+            ACME.replace("Acme Agent", "Acme Again"),
+        )
+        .unwrap();
+
+        let result = load_descriptors(temp_dir.path());
+
+        assert_eq!(result.clients.len(), 1);
+        assert_eq!(result.clients[0].id, "acme-agent");
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].path.ends_with("broken.json"));
+        assert!(result.errors[1].message.contains("Duplicate"));
+    }
+
+    #[test]
+    fn test_load_descriptors_missing_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = load_descriptors(&temp_dir.path().join("clients.d"));
+        assert!(result.clients.is_empty());
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_validate_descriptor() {
+        let valid: ClientDescriptor = serde_json::from_str(ACME).unwrap();
+        assert!(validate_descriptor(&valid).is_ok());
+
+        let mut builtin = valid.clone();
+        builtin.id = "cursor".to_string();
+        assert!(validate_descriptor(&builtin).unwrap_err().contains("built-in"));
+
+        let mut bad_id = valid.clone();
+        bad_id.id = "Acme Agent".to_string();
+        assert!(validate_descriptor(&bad_id).is_err());
+
+        let mut bad_pointer = valid.clone();
+        bad_pointer.servers_pointer = "mcpServers".to_string();
+        assert!(validate_descriptor(&bad_pointer).is_err());
+
+        let mut no_path = valid;
+        no_path.config_path.default = None;
+        assert!(validate_descriptor(&no_path).is_err());
+    }
+}
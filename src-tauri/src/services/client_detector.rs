@@ -1,8 +1,12 @@
-use crate::models::{ClientConfigInfo, ClientId, ConfigFormat, DetectedClient};
+use crate::models::{
+    custom_client, ClientConfigInfo, ClientDescriptor, ClientId, ConfigFormat, DetectedClient,
+    ProjectTarget,
+};
+use crate::services::{jsonc, toml_config, yaml_config};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,6 +17,12 @@ pub enum ClientDetectionError {
     ReadError(#[from] std::io::Error),
     #[error("Failed to parse config file: {0}")]
     ParseError(#[from] serde_json::Error),
+    #[error("Failed to parse TOML config file: {0}")]
+    TomlParseError(#[from] toml_edit::de::Error),
+    #[error("Failed to parse YAML config file: {0}")]
+    YamlParseError(#[from] serde_yaml::Error),
+    #[error("No config location known for client '{0}'")]
+    NoConfigPath(String),
 }
 
 /// Get the user's home directory
@@ -20,41 +30,299 @@ fn home_dir() -> Result<PathBuf, ClientDetectionError> {
     dirs::home_dir().ok_or(ClientDetectionError::HomeNotFound)
 }
 
-/// Get the config path for a specific client
-pub fn get_client_config_path(client_id: ClientId) -> Result<PathBuf, ClientDetectionError> {
-    let home = home_dir()?;
+/// Base directories that client config locations are resolved against
+#[derive(Debug, Clone)]
+pub struct PlatformDirs {
+    /// User home directory
+    pub home: PathBuf,
+    /// Per-user application config directory: `~/Library/Application Support`
+    /// on macOS, `$XDG_CONFIG_HOME` (or `~/.config`) on Linux, `%APPDATA%` on Windows
+    pub config: PathBuf,
+}
+
+impl PlatformDirs {
+    /// Directories for the current user on the current platform
+    pub fn current() -> Result<Self, ClientDetectionError> {
+        let home = home_dir()?;
+        let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+        Ok(Self { home, config })
+    }
+}
+
+/// VS Code distributions that share the same user data layout
+const VSCODE_VARIANTS: [&str; 3] = ["Code", "Code - Insiders", "VSCodium"];
 
-    let path = match client_id {
-        ClientId::ClaudeCode => home.join(".claude.json"),
-        ClientId::ClaudeDesktop => home
-            .join("Library")
-            .join("Application Support")
+/// Candidate config locations for a client, most preferred first.
+///
+/// The first candidate is also the default location when none exist yet.
+pub fn config_path_candidates(client_id: ClientId, dirs: &PlatformDirs) -> Vec<PathBuf> {
+    let home = &dirs.home;
+    let vscode_user_dirs = || {
+        VSCODE_VARIANTS
+            .iter()
+            .map(|variant| dirs.config.join(variant).join("User"))
+    };
+
+    match client_id {
+        ClientId::ClaudeCode => vec![home.join(".claude.json")],
+        ClientId::ClaudeDesktop => vec![dirs
+            .config
             .join("Claude")
-            .join("claude_desktop_config.json"),
-        ClientId::Cursor => home.join(".cursor").join("mcp.json"),
-        ClientId::Vscode => home.join(".vscode").join("mcp.json"),
-        ClientId::Cline => home
-            .join("Documents")
-            .join("Cline")
-            .join("cline_mcp_settings.json"),
-        ClientId::Continue => home.join(".continue").join("config.json"),
-        ClientId::Windsurf => home
+            .join("claude_desktop_config.json")],
+        ClientId::Cursor => vec![home.join(".cursor").join("mcp.json")],
+        ClientId::Vscode => std::iter::once(home.join(".vscode").join("mcp.json"))
+            .chain(vscode_user_dirs().map(|dir| dir.join("settings.json")))
+            .collect(),
+        ClientId::Cline => std::iter::once(
+            home.join("Documents")
+                .join("Cline")
+                .join("cline_mcp_settings.json"),
+        )
+        .chain(vscode_user_dirs().map(|dir| {
+            dir.join("globalStorage")
+                .join("saoudrizwan.claude-dev")
+                .join("settings")
+                .join("cline_mcp_settings.json")
+        }))
+        .collect(),
+        // config.yaml is Continue's newer format; config.json stays the default
+        ClientId::Continue => vec![
+            home.join(".continue").join("config.json"),
+            home.join(".continue").join("config.yaml"),
+        ],
+        ClientId::Windsurf => vec![home
             .join(".codeium")
             .join("windsurf")
-            .join("mcp_config.json"),
+            .join("mcp_config.json")],
         ClientId::Warp => {
             // Warp uses internal storage, but we return a placeholder path
             // for documentation purposes
-            home.join(".warp").join("mcp_config.json")
+            vec![home.join(".warp").join("mcp_config.json")]
+        }
+        ClientId::Codex => vec![home.join(".codex").join("config.toml")],
+        // ~/.config/zed on macOS and Linux; the XDG/APPDATA directory otherwise
+        ClientId::Zed => {
+            let mut candidates = vec![home.join(".config").join("zed").join("settings.json")];
+            let platform = dirs.config.join("zed").join("settings.json");
+            if !candidates.contains(&platform) {
+                candidates.push(platform);
+            }
+            candidates
         }
+        ClientId::GeminiCli => vec![home.join(".gemini").join("settings.json")],
+        ClientId::RooCode => vscode_user_dirs()
+            .map(|dir| {
+                dir.join("globalStorage")
+                    .join("rooveterinaryinc.roo-cline")
+                    .join("settings")
+                    .join("mcp_settings.json")
+            })
+            .collect(),
+        ClientId::AmazonQ => vec![home.join(".aws").join("amazonq").join("mcp.json")],
+        // Unknown once its descriptor has been removed
+        ClientId::Custom(id) => custom_client(id)
+            .map(|d| descriptor_config_candidates(&d, dirs, std::env::consts::OS))
+            .unwrap_or_default(),
+        ClientId::Goose => vec![
+            home.join(".config").join("goose").join("config.yaml"),
+            dirs.config
+                .join("Block")
+                .join("goose")
+                .join("config")
+                .join("config.yaml"),
+        ],
+    }
+}
+
+/// Config format of a client's file at `path`
+///
+/// Continue reads either config.json or config.yaml, whichever is in use.
+pub(crate) fn config_format_for_path(client_id: ClientId, path: &Path) -> ConfigFormat {
+    let format = DetectedClient::new(client_id).config_format;
+    let is_yaml = path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+
+    match format {
+        ConfigFormat::Continue if is_yaml => ConfigFormat::ContinueYaml,
+        _ => format,
+    }
+}
+
+/// Expand a descriptor path template: a leading `~` and `{home}` become the
+/// home directory, `{config}` the per-user config directory
+fn expand_path_template(template: &str, dirs: &PlatformDirs) -> PathBuf {
+    let home = dirs.home.to_string_lossy();
+    let expanded = match template.strip_prefix('~') {
+        Some(rest) => format!("{}{}", home, rest),
+        None => template.to_string(),
+    };
+
+    PathBuf::from(
+        expanded
+            .replace("{home}", &home)
+            .replace("{config}", &dirs.config.to_string_lossy()),
+    )
+}
+
+/// Candidate config locations for a custom client on `os`
+pub fn descriptor_config_candidates(
+    descriptor: &ClientDescriptor,
+    dirs: &PlatformDirs,
+    os: &str,
+) -> Vec<PathBuf> {
+    descriptor
+        .config_path
+        .for_os(os)
+        .map(|template| expand_path_template(template, dirs))
+        .into_iter()
+        .collect()
+}
+
+/// Pick the first candidate that exists, or the default (first) one
+fn select_config_path(candidates: &[PathBuf]) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .find(|path| path.exists())
+        .or_else(|| candidates.first())
+        .cloned()
+}
+
+/// Get the candidate config paths for a client on this platform
+pub fn get_client_config_candidates(
+    client_id: ClientId,
+) -> Result<Vec<PathBuf>, ClientDetectionError> {
+    Ok(config_path_candidates(client_id, &PlatformDirs::current()?))
+}
+
+/// Get the config path for a specific client
+pub fn get_client_config_path(client_id: ClientId) -> Result<PathBuf, ClientDetectionError> {
+    let candidates = get_client_config_candidates(client_id)?;
+    select_config_path(&candidates)
+        .ok_or_else(|| ClientDetectionError::NoConfigPath(client_id.to_string()))
+}
+
+/// Get the config path of a project-level target
+pub fn get_project_config_path(
+    target: ProjectTarget,
+    project_root: &Path,
+) -> Result<PathBuf, ClientDetectionError> {
+    let path = match target {
+        ProjectTarget::McpJson => project_root.join(".mcp.json"),
+        // Local-scope servers live in the user's global file, keyed by project path
+        ProjectTarget::ClaudeCodeLocal => home_dir()?.join(".claude.json"),
+        ProjectTarget::Cursor => project_root.join(".cursor").join("mcp.json"),
+        ProjectTarget::Vscode => project_root.join(".vscode").join("mcp.json"),
     };
 
     Ok(path)
 }
 
+/// Guess which project-level configs a project uses from what is already on disk.
+///
+/// Falls back to `.mcp.json` when nothing is found.
+pub fn detect_project_targets(project_root: &Path) -> Vec<ProjectTarget> {
+    let mut targets = Vec::new();
+
+    if project_root.join(".mcp.json").exists() {
+        targets.push(ProjectTarget::McpJson);
+    }
+
+    let has_local_entry = get_project_config_path(ProjectTarget::ClaudeCodeLocal, project_root)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .map(|json| {
+            json.get("projects")
+                .and_then(|p| p.get(project_root.to_string_lossy().as_ref()))
+                .and_then(|p| p.get("mcpServers"))
+                .and_then(|v| v.as_object())
+                .is_some_and(|servers| !servers.is_empty())
+        })
+        .unwrap_or(false);
+    if has_local_entry {
+        targets.push(ProjectTarget::ClaudeCodeLocal);
+    }
+
+    if project_root.join(".cursor").is_dir() {
+        targets.push(ProjectTarget::Cursor);
+    }
+    if project_root.join(".vscode").is_dir() {
+        targets.push(ProjectTarget::Vscode);
+    }
+
+    if targets.is_empty() {
+        targets.push(ProjectTarget::McpJson);
+    }
+
+    targets
+}
+
+/// Parse a document as TOML, YAML or JSON(C) by the file's extension
+fn parse_by_extension(content: &str, path: &Path) -> Result<Value, ClientDetectionError> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => Ok(toml_config::parse(content)?),
+        Some("yaml") | Some("yml") => Ok(yaml_config::parse(content)?),
+        _ => Ok(jsonc::parse(content)?),
+    }
+}
+
+/// Parse a custom client's config, finding servers at its descriptor's pointer
+fn parse_descriptor_config(
+    content: &str,
+    path: &Path,
+    descriptor: &ClientDescriptor,
+) -> Result<ClientConfigInfo, ClientDetectionError> {
+    let json = parse_by_extension(content, path)?;
+    let servers = descriptor
+        .servers_keys()
+        .iter()
+        .try_fold(&json, |value, key| value.get(key))
+        .and_then(|v| v.as_object());
+
+    let (server_count, server_names, raw_config) = match servers {
+        Some(servers) => {
+            let names: Vec<String> = servers.keys().cloned().collect();
+            let raw: HashMap<String, Value> = servers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            (names.len(), names, Some(raw))
+        }
+        None => (0, vec![], None),
+    };
+
+    Ok(ClientConfigInfo {
+        server_count,
+        server_names,
+        raw_config,
+    })
+}
+
+/// Parse a client's config file content
+pub(crate) fn parse_client_config(
+    client_id: ClientId,
+    path: &Path,
+    content: &str,
+) -> Result<ClientConfigInfo, ClientDetectionError> {
+    match client_id {
+        ClientId::Custom(id) => {
+            let descriptor = custom_client(id)
+                .ok_or_else(|| ClientDetectionError::NoConfigPath(id.to_string()))?;
+            parse_descriptor_config(content, path, &descriptor)
+        }
+        _ => parse_mcp_config(content, config_format_for_path(client_id, path)),
+    }
+}
+
 /// Parse an MCP servers config and count servers
 fn parse_mcp_config(content: &str, format: ConfigFormat) -> Result<ClientConfigInfo, ClientDetectionError> {
-    let json: Value = serde_json::from_str(content)?;
+    let json: Value = match format {
+        ConfigFormat::CodexToml => toml_config::parse(content)?,
+        ConfigFormat::ContinueYaml | ConfigFormat::GooseYaml => yaml_config::parse(content)?,
+        // Editor settings files are often JSONC
+        _ => jsonc::parse(content)?,
+    };
 
     let (server_count, server_names, raw_config) = match format {
         ConfigFormat::Standard => {
@@ -89,7 +357,51 @@ fn parse_mcp_config(content: &str, format: ConfigFormat) -> Result<ClientConfigI
                 (0, vec![], None)
             }
         }
-        ConfigFormat::Continue => {
+        ConfigFormat::Zed => {
+            // {"context_servers": {"name": {"command": {"path": ...}}, ...}}
+            if let Some(servers) = json.get("context_servers").and_then(|v| v.as_object()) {
+                let names: Vec<String> = servers.keys().cloned().collect();
+                let count = names.len();
+                let raw: HashMap<String, Value> = servers
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                (count, names, Some(raw))
+            } else {
+                (0, vec![], None)
+            }
+        }
+        ConfigFormat::CodexToml => {
+            // [mcp_servers.name] tables
+            if let Some(servers) = json.get("mcp_servers").and_then(|v| v.as_object()) {
+                let names: Vec<String> = servers.keys().cloned().collect();
+                let count = names.len();
+                let raw: HashMap<String, Value> = servers
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                (count, names, Some(raw))
+            } else {
+                (0, vec![], None)
+            }
+        }
+        ConfigFormat::GooseYaml => {
+            // extensions: {name: {cmd: ..., type: stdio}} - includes Goose's builtins
+            if let Some(servers) = json.get("extensions").and_then(|v| v.as_object()) {
+                let names: Vec<String> = servers.keys().cloned().collect();
+                let count = names.len();
+                let raw: HashMap<String, Value> = servers
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                (count, names, Some(raw))
+            } else {
+                (0, vec![], None)
+            }
+        }
+        // Custom clients are read through their descriptor (parse_descriptor_config)
+        ConfigFormat::Custom => (0, vec![], None),
+        ConfigFormat::Continue | ConfigFormat::ContinueYaml => {
             // {"mcpServers": [...]} - array format, or may be embedded in larger config
             if let Some(servers) = json.get("mcpServers").and_then(|v| v.as_array()) {
                 let names: Vec<String> = servers
@@ -125,9 +437,18 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
     let mut client = DetectedClient::new(client_id);
 
     // Get config path
-    match get_client_config_path(client_id) {
-        Ok(path) => {
+    match get_client_config_candidates(client_id) {
+        Ok(candidates) => {
+            let Some(path) = select_config_path(&candidates) else {
+                return client;
+            };
             client.config_path = Some(path.clone());
+            client.config_format = config_format_for_path(client_id, &path);
+            // Any candidate's directory existing means the client is installed
+            let candidate_dir_exists = candidates
+                .iter()
+                .any(|p| p.parent().map(|d| d.exists()).unwrap_or(false));
+            client.config_candidates = candidates;
 
             // Special handling for Warp - it's manual only
             if client_id == ClientId::Warp {
@@ -145,7 +466,7 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
                 // Try to parse and count servers
                 match fs::read_to_string(&path) {
                     Ok(content) => {
-                        match parse_mcp_config(&content, client.config_format) {
+                        match parse_client_config(client_id, &path, &content) {
                             Ok(info) => {
                                 client.server_count = info.server_count;
                             }
@@ -164,9 +485,9 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
                 // by checking for the parent directory or application
                 let detected = match client_id {
                     ClientId::ClaudeDesktop => {
-                        // Check if Claude.app exists
+                        // Check if Claude.app exists, or its config directory
                         let app_path = PathBuf::from("/Applications/Claude.app");
-                        app_path.exists()
+                        app_path.exists() || candidate_dir_exists
                     }
                     ClientId::Cursor => {
                         // Check if Cursor.app exists or .cursor directory
@@ -177,8 +498,7 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
                     ClientId::Vscode => {
                         // Check if VS Code is installed
                         let app_path = PathBuf::from("/Applications/Visual Studio Code.app");
-                        let dir_path = path.parent().map(|p| p.exists()).unwrap_or(false);
-                        app_path.exists() || dir_path
+                        app_path.exists() || candidate_dir_exists
                     }
                     ClientId::Windsurf => {
                         // Check if Windsurf.app exists
@@ -186,8 +506,8 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
                         app_path.exists()
                     }
                     ClientId::Cline => {
-                        // Check if Cline directory exists
-                        path.parent().map(|p| p.exists()).unwrap_or(false)
+                        // Check if a Cline directory exists
+                        candidate_dir_exists
                     }
                     ClientId::Continue => {
                         // Check if .continue directory exists
@@ -198,6 +518,31 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
                         // For now, assume if the tool is asking, it's probably installed
                         true
                     }
+                    ClientId::Codex => {
+                        // Check if ~/.codex exists
+                        path.parent().map(|p| p.exists()).unwrap_or(false)
+                    }
+                    ClientId::Goose => {
+                        // Check if a goose config directory exists
+                        candidate_dir_exists
+                    }
+                    ClientId::GeminiCli | ClientId::AmazonQ => {
+                        // Check if ~/.gemini or ~/.aws/amazonq exists
+                        path.parent().map(|p| p.exists()).unwrap_or(false)
+                    }
+                    ClientId::Custom(_) => {
+                        // Check if the descriptor's config directory exists
+                        candidate_dir_exists
+                    }
+                    ClientId::RooCode => {
+                        // Check if Roo Code's settings directory exists in any VS Code variant
+                        candidate_dir_exists
+                    }
+                    ClientId::Zed => {
+                        // Check if Zed.app exists or a zed config directory
+                        let app_path = PathBuf::from("/Applications/Zed.app");
+                        app_path.exists() || candidate_dir_exists
+                    }
                     ClientId::Warp => {
                         // Handled above
                         false
@@ -217,7 +562,10 @@ pub fn detect_client(client_id: ClientId) -> DetectedClient {
 
 /// Detect all supported clients
 pub fn detect_all_clients() -> Vec<DetectedClient> {
-    ClientId::all().into_iter().map(detect_client).collect()
+    ClientId::all_with_custom()
+        .into_iter()
+        .map(detect_client)
+        .collect()
 }
 
 /// Get detailed config info for a client (for import purposes)
@@ -229,8 +577,7 @@ pub fn get_client_config_info(client_id: ClientId) -> Result<Option<ClientConfig
     }
 
     let content = fs::read_to_string(&path)?;
-    let client = DetectedClient::new(client_id);
-    let info = parse_mcp_config(&content, client.config_format)?;
+    let info = parse_client_config(client_id, &path, &content)?;
 
     Ok(Some(info))
 }
@@ -246,6 +593,7 @@ mod tests {
         assert!(path.ends_with(".claude.json"));
     }
 
+    #[cfg(target_os = "macos")]
     #[test]
     fn test_get_client_config_path_claude_desktop() {
         let path = get_client_config_path(ClientId::ClaudeDesktop).unwrap();
@@ -327,6 +675,113 @@ mod tests {
         assert!(info.server_names.contains(&"filesystem".to_string()));
     }
 
+    #[test]
+    fn test_get_client_config_path_codex() {
+        let path = get_client_config_path(ClientId::Codex).unwrap();
+        assert!(path.to_string_lossy().contains(".codex"));
+        assert!(path.ends_with("config.toml"));
+    }
+
+    #[test]
+    fn test_parse_mcp_config_codex() {
+        let content = r#"
+model = "o3"
+
+[mcp_servers.filesystem]
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-filesystem"]
+
+[mcp_servers.remote]
+url = "https://example.com/mcp"
+"#;
+
+        let info = parse_mcp_config(content, ConfigFormat::CodexToml).unwrap();
+        assert_eq!(info.server_count, 2);
+        assert!(info.server_names.contains(&"filesystem".to_string()));
+        let raw = info.raw_config.unwrap();
+        assert_eq!(raw["remote"]["url"], "https://example.com/mcp");
+    }
+
+    #[test]
+    fn test_parse_mcp_config_zed() {
+        let content = r#"{
+            // Zed settings
+            "theme": "One Dark",
+            "context_servers": {
+                "filesystem": {
+                    "command": {
+                        "path": "npx",
+                        "args": ["-y", "@modelcontextprotocol/server-filesystem"],
+                    },
+                    "settings": {}
+                }
+            }
+        }"#;
+
+        let info = parse_mcp_config(content, ConfigFormat::Zed).unwrap();
+        assert_eq!(info.server_count, 1);
+        assert!(info.server_names.contains(&"filesystem".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mcp_config_goose() {
+        let content = r#"
+GOOSE_PROVIDER: openai
+extensions:
+  developer:
+    bundled: true
+    enabled: true
+    name: developer
+    type: builtin
+  github:
+    cmd: npx
+    args: [-y, "@modelcontextprotocol/server-github"]
+    enabled: true
+    envs: {}
+    name: github
+    type: stdio
+"#;
+
+        let info = parse_mcp_config(content, ConfigFormat::GooseYaml).unwrap();
+        assert_eq!(info.server_count, 2);
+        assert!(info.server_names.contains(&"github".to_string()));
+        assert_eq!(info.raw_config.unwrap()["github"]["cmd"], "npx");
+    }
+
+    #[test]
+    fn test_parse_mcp_config_continue_yaml() {
+        let content = r#"
+name: Local Assistant
+version: 1.0.0
+schema: v1
+mcpServers:
+  - name: SQLite
+    command: npx
+    args: ["-y", "mcp-sqlite"]
+"#;
+
+        let info = parse_mcp_config(content, ConfigFormat::ContinueYaml).unwrap();
+        assert_eq!(info.server_count, 1);
+        assert_eq!(info.server_names, vec!["SQLite".to_string()]);
+        assert_eq!(parse_mcp_config("", ConfigFormat::GooseYaml).unwrap().server_count, 0);
+    }
+
+    #[test]
+    fn test_config_format_for_path() {
+        assert_eq!(
+            config_format_for_path(ClientId::Continue, Path::new("/home/me/.continue/config.yaml")),
+            ConfigFormat::ContinueYaml
+        );
+        assert_eq!(
+            config_format_for_path(ClientId::Continue, Path::new("/home/me/.continue/config.json")),
+            ConfigFormat::Continue
+        );
+        assert_eq!(
+            config_format_for_path(ClientId::Goose, Path::new("/home/me/.config/goose/config.yaml")),
+            ConfigFormat::GooseYaml
+        );
+    }
+
     #[test]
     fn test_parse_mcp_config_empty() {
         let content = r#"{}"#;
@@ -347,7 +802,7 @@ mod tests {
     #[test]
     fn test_detect_all_clients() {
         let clients = detect_all_clients();
-        assert_eq!(clients.len(), 8);
+        assert_eq!(clients.len(), 14);
 
         // Verify all client IDs are present
         let ids: Vec<ClientId> = clients.iter().map(|c| c.id).collect();
@@ -359,5 +814,270 @@ mod tests {
         assert!(ids.contains(&ClientId::Continue));
         assert!(ids.contains(&ClientId::Windsurf));
         assert!(ids.contains(&ClientId::Warp));
+        assert!(ids.contains(&ClientId::Codex));
+        assert!(ids.contains(&ClientId::Zed));
+        assert!(ids.contains(&ClientId::Goose));
+        assert!(ids.contains(&ClientId::GeminiCli));
+        assert!(ids.contains(&ClientId::RooCode));
+        assert!(ids.contains(&ClientId::AmazonQ));
+    }
+
+    #[test]
+    fn test_get_project_config_path() {
+        let root = Path::new("/work/app");
+
+        assert_eq!(
+            get_project_config_path(ProjectTarget::McpJson, root).unwrap(),
+            root.join(".mcp.json")
+        );
+        assert_eq!(
+            get_project_config_path(ProjectTarget::Cursor, root).unwrap(),
+            root.join(".cursor").join("mcp.json")
+        );
+        assert_eq!(
+            get_project_config_path(ProjectTarget::Vscode, root).unwrap(),
+            root.join(".vscode").join("mcp.json")
+        );
+        assert!(get_project_config_path(ProjectTarget::ClaudeCodeLocal, root)
+            .unwrap()
+            .ends_with(".claude.json"));
+    }
+
+    #[test]
+    fn test_detect_project_targets() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // Nothing on disk: default to .mcp.json
+        assert_eq!(detect_project_targets(root), vec![ProjectTarget::McpJson]);
+
+        fs::create_dir(root.join(".cursor")).unwrap();
+        fs::create_dir(root.join(".vscode")).unwrap();
+        assert_eq!(
+            detect_project_targets(root),
+            vec![ProjectTarget::Cursor, ProjectTarget::Vscode]
+        );
+    }
+
+    #[test]
+    fn test_parse_mcp_config_jsonc() {
+        let content = r#"{
+            // VS Code user settings
+            "editor.tabSize": 2,
+            "mcp": {
+                "servers": {
+                    "filesystem": {"command": "npx",}, /* trailing comma */
+                },
+            },
+        }"#;
+
+        let info = parse_mcp_config(content, ConfigFormat::Vscode).unwrap();
+        assert_eq!(info.server_count, 1);
+        assert!(info.server_names.contains(&"filesystem".to_string()));
+    }
+
+    fn macos_dirs() -> PlatformDirs {
+        PlatformDirs {
+            home: PathBuf::from("/Users/me"),
+            config: PathBuf::from("/Users/me/Library/Application Support"),
+        }
+    }
+
+    fn linux_dirs() -> PlatformDirs {
+        PlatformDirs {
+            home: PathBuf::from("/home/me"),
+            config: PathBuf::from("/home/me/.config"),
+        }
+    }
+
+    fn windows_dirs() -> PlatformDirs {
+        PlatformDirs {
+            home: PathBuf::from("C:/Users/me"),
+            config: PathBuf::from("C:/Users/me/AppData/Roaming"),
+        }
+    }
+
+    #[test]
+    fn test_config_path_candidates_macos() {
+        let dirs = macos_dirs();
+
+        assert_eq!(
+            config_path_candidates(ClientId::ClaudeDesktop, &dirs),
+            vec![PathBuf::from(
+                "/Users/me/Library/Application Support/Claude/claude_desktop_config.json"
+            )]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Vscode, &dirs),
+            vec![
+                PathBuf::from("/Users/me/.vscode/mcp.json"),
+                PathBuf::from("/Users/me/Library/Application Support/Code/User/settings.json"),
+                PathBuf::from(
+                    "/Users/me/Library/Application Support/Code - Insiders/User/settings.json"
+                ),
+                PathBuf::from("/Users/me/Library/Application Support/VSCodium/User/settings.json"),
+            ]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::ClaudeCode, &dirs),
+            vec![PathBuf::from("/Users/me/.claude.json")]
+        );
+    }
+
+    #[test]
+    fn test_config_path_candidates_linux() {
+        let dirs = linux_dirs();
+
+        assert_eq!(
+            config_path_candidates(ClientId::ClaudeDesktop, &dirs),
+            vec![PathBuf::from("/home/me/.config/Claude/claude_desktop_config.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Vscode, &dirs)[1..],
+            [
+                PathBuf::from("/home/me/.config/Code/User/settings.json"),
+                PathBuf::from("/home/me/.config/Code - Insiders/User/settings.json"),
+                PathBuf::from("/home/me/.config/VSCodium/User/settings.json"),
+            ]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Cline, &dirs),
+            vec![
+                PathBuf::from("/home/me/Documents/Cline/cline_mcp_settings.json"),
+                PathBuf::from(
+                    "/home/me/.config/Code/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json"
+                ),
+                PathBuf::from(
+                    "/home/me/.config/Code - Insiders/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json"
+                ),
+                PathBuf::from(
+                    "/home/me/.config/VSCodium/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json"
+                ),
+            ]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Windsurf, &dirs),
+            vec![PathBuf::from("/home/me/.codeium/windsurf/mcp_config.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Zed, &dirs),
+            vec![PathBuf::from("/home/me/.config/zed/settings.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Goose, &dirs)[0],
+            PathBuf::from("/home/me/.config/goose/config.yaml")
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::GeminiCli, &dirs),
+            vec![PathBuf::from("/home/me/.gemini/settings.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::RooCode, &dirs)[0],
+            PathBuf::from(
+                "/home/me/.config/Code/User/globalStorage/rooveterinaryinc.roo-cline/settings/mcp_settings.json"
+            )
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::AmazonQ, &dirs),
+            vec![PathBuf::from("/home/me/.aws/amazonq/mcp.json")]
+        );
+    }
+
+    #[test]
+    fn test_config_path_candidates_windows() {
+        let dirs = windows_dirs();
+
+        assert_eq!(
+            config_path_candidates(ClientId::ClaudeDesktop, &dirs),
+            vec![PathBuf::from(
+                "C:/Users/me/AppData/Roaming/Claude/claude_desktop_config.json"
+            )]
+        );
+        assert!(config_path_candidates(ClientId::Vscode, &dirs)
+            .contains(&PathBuf::from("C:/Users/me/AppData/Roaming/Code/User/settings.json")));
+        assert_eq!(
+            config_path_candidates(ClientId::Cursor, &dirs),
+            vec![PathBuf::from("C:/Users/me/.cursor/mcp.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::RooCode, &dirs)[0],
+            PathBuf::from(
+                "C:/Users/me/AppData/Roaming/Code/User/globalStorage/rooveterinaryinc.roo-cline/settings/mcp_settings.json"
+            )
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::AmazonQ, &dirs),
+            vec![PathBuf::from("C:/Users/me/.aws/amazonq/mcp.json")]
+        );
+        assert_eq!(
+            config_path_candidates(ClientId::Zed, &dirs)[1],
+            PathBuf::from("C:/Users/me/AppData/Roaming/zed/settings.json")
+        );
+    }
+
+    fn acme_descriptor() -> ClientDescriptor {
+        serde_json::from_value(serde_json::json!({
+            "id": "acme-agent",
+            "name": "Acme Agent",
+            "configPath": {
+                "windows": "{config}/Acme/mcp.json",
+                "default": "~/.acme/agent.yaml"
+            },
+            "serversPointer": "/agent/tools"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_descriptor_config_candidates() {
+        let descriptor = acme_descriptor();
+
+        assert_eq!(
+            descriptor_config_candidates(&descriptor, &linux_dirs(), "linux"),
+            vec![PathBuf::from("/home/me/.acme/agent.yaml")]
+        );
+        assert_eq!(
+            descriptor_config_candidates(&descriptor, &windows_dirs(), "windows"),
+            vec![PathBuf::from("C:/Users/me/AppData/Roaming/Acme/mcp.json")]
+        );
+    }
+
+    #[test]
+    fn test_parse_descriptor_config() {
+        let content = "agent:\n  model: fast\n  tools:\n    fs:\n      command: npx\n";
+
+        let info =
+            parse_descriptor_config(content, Path::new("agent.yaml"), &acme_descriptor()).unwrap();
+        assert_eq!(info.server_names, vec!["fs".to_string()]);
+        assert_eq!(info.raw_config.unwrap()["fs"]["command"], "npx");
+
+        let empty =
+            parse_descriptor_config("{}", Path::new("agent.json"), &acme_descriptor()).unwrap();
+        assert_eq!(empty.server_count, 0);
+    }
+
+    #[test]
+    fn test_select_config_path_prefers_existing_candidate() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let default = temp_dir.path().join("default.json");
+        let existing = temp_dir.path().join("existing.json");
+
+        assert_eq!(
+            select_config_path(&[default.clone(), existing.clone()]),
+            Some(default.clone())
+        );
+
+        fs::write(&existing, "{}").unwrap();
+        assert_eq!(select_config_path(&[default, existing.clone()]), Some(existing));
+        assert_eq!(select_config_path(&[]), None);
+    }
+
+    #[test]
+    fn test_detect_client_lists_candidates() {
+        let client = detect_client(ClientId::Vscode);
+        assert_eq!(client.config_candidates.len(), 4);
+        assert!(client
+            .config_candidates
+            .contains(client.config_path.as_ref().unwrap()));
     }
 }
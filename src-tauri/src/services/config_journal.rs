@@ -0,0 +1,472 @@
+// src-tauri/src/services/config_journal.rs
+//! Append-only history of changes to the central config
+//!
+//! Every change is one JSON line in `~/.mcp-nexus/journal.jsonl` holding the
+//! config before and after it. Undo and redo are journaled like any other
+//! change, so the undo/redo position is recovered by replaying the file.
+//! Appending only reads the last line; every [`COMPACT_EVERY`] entries the
+//! file is rewritten to keep just the newest [`MAX_ENTRIES`].
+
+use super::app_log::log_warning_in;
+use super::config_manager::migrate_config;
+use crate::models::McpHubConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Entries kept when the journal is compacted
+pub const MAX_ENTRIES: usize = 200;
+
+/// Compact the journal whenever an entry ID is a multiple of this
+const COMPACT_EVERY: u64 = 50;
+
+/// Bytes read at a time when looking for the last line
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+/// What kind of change a journal entry records
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum JournalAction {
+    /// A regular change (including restoring a point in time)
+    Change,
+    /// Reverted the entry with this ID
+    Undo { entry: u64 },
+    /// Re-applied the entry with this ID
+    Redo { entry: u64 },
+}
+
+/// One recorded change to the central config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub id: u64,
+    /// ISO timestamp of the change
+    pub timestamp: String,
+    /// Human-readable description, e.g. "Add server 'github'"
+    pub operation: String,
+    #[serde(flatten)]
+    pub action: JournalAction,
+    /// Config before the change (`None` if there was no readable config)
+    pub before: Option<McpHubConfig>,
+    pub after: McpHubConfig,
+}
+
+/// A journal entry without its config snapshots, for listing history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalSummary {
+    pub id: u64,
+    pub timestamp: String,
+    pub operation: String,
+    #[serde(flatten)]
+    pub action: JournalAction,
+}
+
+impl From<&JournalEntry> for JournalSummary {
+    fn from(entry: &JournalEntry) -> Self {
+        Self {
+            id: entry.id,
+            timestamp: entry.timestamp.clone(),
+            operation: entry.operation.clone(),
+            action: entry.action,
+        }
+    }
+}
+
+/// The config journal file
+pub struct ConfigJournal {
+    path: PathBuf,
+}
+
+impl ConfigJournal {
+    /// Journal stored in `config_dir`
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            path: config_dir.join("journal.jsonl"),
+        }
+    }
+
+    /// Every entry, oldest first, with snapshots migrated to the current
+    /// config version. Lines that do not parse (e.g. from an interrupted
+    /// write) are skipped and logged.
+    pub fn entries(&self) -> std::io::Result<Vec<JournalEntry>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(index, line)| match parse_entry(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    let message = format!("Skipping journal entry on line {}: {}", index + 1, e);
+                    log_warning_in(self.path.parent().unwrap_or(Path::new(".")), &message);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// The last line of the file, read from the end. `None` if there is none.
+    fn last_line(&self) -> std::io::Result<Option<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let mut pos = file.metadata()?.len();
+        let mut tail = Vec::new();
+        loop {
+            let start = pos.saturating_sub(TAIL_CHUNK);
+            let mut chunk = vec![0; (pos - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut chunk)?;
+            chunk.extend_from_slice(&tail);
+            tail = chunk;
+            pos = start;
+
+            let content = tail.strip_suffix(b"\n").unwrap_or(&tail);
+            if let Some(newline) = content.iter().rposition(|b| *b == b'\n') {
+                return Ok(Some(String::from_utf8_lossy(&content[newline + 1..]).into_owned()));
+            }
+            if pos == 0 {
+                let line = String::from_utf8_lossy(content).into_owned();
+                return Ok((!line.is_empty()).then_some(line));
+            }
+        }
+    }
+
+    /// ID for the next entry
+    fn next_id(&self) -> std::io::Result<u64> {
+        #[derive(Deserialize)]
+        struct EntryId {
+            id: u64,
+        }
+
+        let last = self.last_line()?;
+        match last.as_deref().map(serde_json::from_str::<EntryId>) {
+            None => Ok(1),
+            Some(Ok(last)) => Ok(last.id + 1),
+            // A torn last line: fall back to the entries that did parse
+            Some(Err(_)) => Ok(self.entries()?.last().map_or(1, |e| e.id + 1)),
+        }
+    }
+
+    /// Append a change, returning the recorded entry
+    pub fn append(
+        &self,
+        operation: String,
+        action: JournalAction,
+        before: Option<&McpHubConfig>,
+        after: &McpHubConfig,
+    ) -> std::io::Result<JournalEntry> {
+        let id = self.next_id()?;
+        let entry = JournalEntry {
+            id,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            operation,
+            action,
+            before: before.cloned(),
+            after: after.clone(),
+        };
+
+        // Start on a fresh line if an interrupted write left a partial one
+        let torn = fs::metadata(&self.path).is_ok_and(|m| m.len() > 0)
+            && !self.ends_with_newline()?;
+
+        let mut file = private_options().create(true).append(true).open(&self.path)?;
+        if torn {
+            writeln!(file)?;
+        }
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        file.sync_all()?;
+
+        if id % COMPACT_EVERY == 0 {
+            self.compact()?;
+        }
+
+        Ok(entry)
+    }
+
+    fn ends_with_newline(&self) -> std::io::Result<bool> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::End(-1))?;
+        let mut last = [0u8];
+        file.read_exact(&mut last)?;
+        Ok(last[0] == b'\n')
+    }
+
+    /// Drop all but the newest [`MAX_ENTRIES`] entries
+    fn compact(&self) -> std::io::Result<()> {
+        let entries = self.entries()?;
+        let keep = &entries[entries.len().saturating_sub(MAX_ENTRIES)..];
+
+        let temp_path = self.path.with_extension("jsonl.tmp");
+        {
+            let mut file = private_options()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&temp_path)?;
+            for entry in keep {
+                writeln!(file, "{}", serde_json::to_string(entry)?)?;
+            }
+            file.sync_all()?;
+        }
+        fs::rename(&temp_path, &self.path)
+    }
+}
+
+/// Options for opening journal files readable by the user only
+fn private_options() -> OpenOptions {
+    #[allow(unused_mut)]
+    let mut options = OpenOptions::new();
+    #[cfg(unix)]
+    {
+        // Snapshots can hold env values, so keep them as private as config.json
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+}
+
+/// Parse one journal line, migrating its snapshots to the current config version
+fn parse_entry(line: &str) -> Result<JournalEntry, String> {
+    let mut value: Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+
+    for key in ["before", "after"] {
+        if let Some(snapshot) = value.get_mut(key).filter(|v| !v.is_null()) {
+            migrate_config(snapshot).map_err(|e| format!("{} snapshot: {}", key, e))?;
+        }
+    }
+
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// IDs of the changes that can be undone and redone, each with the next one last
+pub fn undo_redo_stacks(entries: &[JournalEntry]) -> (Vec<u64>, Vec<u64>) {
+    let mut undo = Vec::new();
+    let mut redo = Vec::new();
+
+    for entry in entries {
+        match entry.action {
+            JournalAction::Change => {
+                undo.push(entry.id);
+                redo.clear();
+            }
+            JournalAction::Undo { entry } if undo.last() == Some(&entry) => {
+                undo.pop();
+                redo.push(entry);
+            }
+            JournalAction::Redo { entry } if redo.last() == Some(&entry) => {
+                redo.pop();
+                undo.push(entry);
+            }
+            _ => {}
+        }
+    }
+
+    (undo, redo)
+}
+
+/// The config with sync bookkeeping removed: what the user actually edits
+fn user_view(config: &McpHubConfig) -> Value {
+    let mut value = serde_json::to_value(config).unwrap_or_default();
+
+    // Only the sync toggle is the user's; clients are enabled unless turned off
+    if let Some(clients) = value.get_mut("clients").and_then(Value::as_object_mut) {
+        clients.retain(|_, settings| settings.get("enabled") == Some(&Value::Bool(false)));
+        for settings in clients.values_mut().filter_map(Value::as_object_mut) {
+            settings.retain(|key, _| key == "enabled");
+        }
+    }
+    if let Some(projects) = value.get_mut("projects").and_then(Value::as_array_mut) {
+        for project in projects.iter_mut().filter_map(Value::as_object_mut) {
+            project.remove("managedServers");
+            project.remove("lastSync");
+        }
+    }
+
+    value
+}
+
+/// Whether going from `before` to `after` changes more than sync bookkeeping
+pub fn is_user_change(before: &McpHubConfig, after: &McpHubConfig) -> bool {
+    user_view(before) != user_view(after)
+}
+
+/// Carry `current`'s sync bookkeeping over to a config taken from history.
+///
+/// Paths, checksums and managed-entry ledgers describe the client files as
+/// they are now; restoring old values would misreport drift and ownership.
+pub fn keep_sync_state(target: &mut McpHubConfig, current: &McpHubConfig) {
+    for (client_id, settings) in &current.clients {
+        let enabled = target.clients.get(client_id).is_none_or(|s| s.enabled);
+        let mut settings = settings.clone();
+        settings.enabled = enabled;
+        target.clients.insert(client_id.clone(), settings);
+    }
+
+    for project in target.projects.iter_mut() {
+        if let Some(now) = current.get_project(&project.path) {
+            project.managed_servers = now.managed_servers.clone();
+            project.last_sync = now.last_sync.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ClientSettings;
+    use tempfile::TempDir;
+
+    fn config_with_profile(name: &str) -> McpHubConfig {
+        McpHubConfig {
+            active_profile: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = ConfigJournal::new(temp_dir.path());
+        assert!(journal.entries().unwrap().is_empty());
+
+        let first = journal
+            .append("first".to_string(), JournalAction::Change, None, &config_with_profile("a"))
+            .unwrap();
+        let second = journal
+            .append(
+                "second".to_string(),
+                JournalAction::Undo { entry: first.id },
+                Some(&config_with_profile("a")),
+                &config_with_profile("b"),
+            )
+            .unwrap();
+        assert_eq!((first.id, second.id), (1, 2));
+
+        // A torn last line is ignored
+        let path = temp_dir.path().join("journal.jsonl");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"id\": 3, \"timest").unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].action, JournalAction::Undo { entry: 1 });
+        assert_eq!(entries[1].before.as_ref().unwrap().active_profile.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn test_append_after_torn_line_and_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = ConfigJournal::new(temp_dir.path());
+        let config = McpHubConfig::default();
+
+        journal.append("1".to_string(), JournalAction::Change, None, &config).unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"id\": 7, \"timest").unwrap();
+
+        // The torn line neither breaks the next entry nor decides its ID
+        let entry = journal.append("2".to_string(), JournalAction::Change, None, &config).unwrap();
+        assert_eq!(entry.id, 2);
+        assert_eq!(journal.entries().unwrap().len(), 2);
+        let log = fs::read_to_string(temp_dir.path().join("logs").join("nexus.log")).unwrap();
+        assert!(log.contains("Skipping journal entry on line 2"));
+
+        for i in 3..=(MAX_ENTRIES as u64 + COMPACT_EVERY) {
+            journal.append(i.to_string(), JournalAction::Change, None, &config).unwrap();
+        }
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries.last().unwrap().id, MAX_ENTRIES as u64 + COMPACT_EVERY);
+
+        // IDs keep increasing after compaction
+        let entry = journal.append("next".to_string(), JournalAction::Change, None, &config);
+        assert_eq!(entry.unwrap().id, MAX_ENTRIES as u64 + COMPACT_EVERY + 1);
+    }
+
+    #[test]
+    fn test_entries_migrate_old_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = ConfigJournal::new(temp_dir.path());
+        let old = serde_json::json!({
+            "id": 1,
+            "timestamp": "2026-01-01T00:00:00Z",
+            "operation": "old",
+            "action": "change",
+            "before": null,
+            "after": {
+                "version": "1.0",
+                "clients": {
+                    "cursor": {"enabled": true, "configPath": "/x", "lastSyncChecksum": "abc123"}
+                }
+            }
+        });
+        fs::write(temp_dir.path().join("journal.jsonl"), format!("{}\n", old)).unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries[0].after.version, crate::models::CONFIG_VERSION);
+        assert!(entries[0].after.clients["cursor"].last_sync_checksum.is_none());
+    }
+
+    #[test]
+    fn test_undo_redo_stacks() {
+        let entry = |id, action| JournalEntry {
+            id,
+            timestamp: String::new(),
+            operation: String::new(),
+            action,
+            before: None,
+            after: McpHubConfig::default(),
+        };
+        let mut entries = vec![
+            entry(1, JournalAction::Change),
+            entry(2, JournalAction::Change),
+            entry(3, JournalAction::Undo { entry: 2 }),
+            entry(4, JournalAction::Undo { entry: 1 }),
+            entry(5, JournalAction::Redo { entry: 1 }),
+        ];
+        assert_eq!(undo_redo_stacks(&entries), (vec![1], vec![2]));
+
+        // A new change drops the redo history
+        entries.push(entry(6, JournalAction::Change));
+        assert_eq!(undo_redo_stacks(&entries), (vec![1, 6], vec![]));
+    }
+
+    #[test]
+    fn test_sync_bookkeeping_is_not_a_user_change() {
+        let before = McpHubConfig::default();
+        let mut after = before.clone();
+        after.clients.insert(
+            "cursor".to_string(),
+            ClientSettings {
+                enabled: true,
+                last_sync: Some("2026-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(!is_user_change(&before, &after));
+
+        // Restoring history keeps the current bookkeeping but the old toggle
+        let mut restored = after.clone();
+        restored.clients.get_mut("cursor").unwrap().enabled = false;
+        restored.clients.get_mut("cursor").unwrap().last_sync = None;
+        keep_sync_state(&mut restored, &after);
+        let settings = &restored.clients["cursor"];
+        assert!(!settings.enabled);
+        assert_eq!(settings.last_sync.as_deref(), Some("2026-01-01T00:00:00Z"));
+
+        after.clients.get_mut("cursor").unwrap().enabled = false;
+        assert!(is_user_change(&before, &after));
+    }
+}
@@ -1,11 +1,22 @@
-use std::fs::{self, File};
-use std::io::{Read, Write};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
 use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::SystemTime;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::models::{McpHubConfig, McpServer};
+use crate::models::{
+    ClientOptions, ClientOverride, McpHubConfig, McpServer, ServerDiagnostic, CONFIG_VERSION,
+};
+use crate::services::config_journal::{
+    is_user_change, keep_sync_state, undo_redo_stacks, ConfigJournal, JournalAction, JournalEntry,
+    JournalSummary,
+};
+use crate::services::config_watcher::note_own_write;
+use crate::services::server_validation::{has_errors, validate_server};
+use crate::services::sync_engine::compute_checksum;
 
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -17,13 +28,193 @@ pub enum ConfigError {
     ParseError(#[from] serde_json::Error),
     #[error("Server not found: {0}")]
     ServerNotFound(Uuid),
+    #[error("Config version {found} needs a newer MCP Nexus (this one supports {supported})")]
+    NewerVersion { found: String, supported: String },
+    #[error("Unrecognized config version: {0}")]
+    UnknownVersion(String),
+    #[error("Failed to migrate config from version {from}: {message}")]
+    Migration { from: String, message: String },
+    #[error("Invalid timestamp '{0}': expected RFC 3339")]
+    InvalidTimestamp(String),
+    #[error("Config file was changed by another program since it was last read; try again")]
+    ModifiedExternally,
+    #[error("Invalid server: {}", describe_errors(.0))]
+    InvalidServer(Vec<ServerDiagnostic>),
 }
 
+/// The blocking diagnostics, joined for an error message
+fn describe_errors(diagnostics: &[ServerDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .filter(|d| d.is_error())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Reject `server` if validation against `servers` finds any errors
+fn check_server(server: &McpServer, servers: &[McpServer]) -> Result<(), ConfigError> {
+    let diagnostics = validate_server(server, servers);
+    if has_errors(&diagnostics) {
+        return Err(ConfigError::InvalidServer(diagnostics));
+    }
+    Ok(())
+}
+
+/// Reject `config` if a server added or changed since `previous` is invalid.
+///
+/// Unchanged servers are not rechecked, so a rule added in a later release
+/// does not block saving unrelated changes.
+fn check_changed_servers(
+    config: &McpHubConfig,
+    previous: Option<&McpHubConfig>,
+) -> Result<(), ConfigError> {
+    for server in &config.servers {
+        let unchanged = previous
+            .and_then(|previous| previous.get_server(&server.id))
+            .is_some_and(|old| {
+                serde_json::to_value(old).ok() == serde_json::to_value(server).ok()
+            });
+        if !unchanged {
+            check_server(server, &config.servers)?;
+        }
+    }
+    Ok(())
+}
+
+/// One step in the config schema history, applied to the raw JSON
+struct Migration {
+    from: &'static str,
+    to: &'static str,
+    apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// Every migration, oldest first; each step's `to` is the next step's `from`
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: "1.0",
+        to: "1.1",
+        apply: drop_legacy_checksums,
+    },
+    // Profiles are new optional fields; the bump keeps older releases from
+    // loading (and dropping) them
+    Migration {
+        from: "1.1",
+        to: "1.2",
+        apply: |_| Ok(()),
+    },
+];
+
+/// 1.0 -> 1.1: drop client checksums made with the unstable pre-SHA-256 hasher,
+/// which can never match again and would only read as "unknown"
+fn drop_legacy_checksums(config: &mut Value) -> Result<(), String> {
+    let Some(clients) = config.get_mut("clients").and_then(Value::as_object_mut) else {
+        return Ok(());
+    };
+
+    for (client_id, settings) in clients.iter_mut() {
+        let settings = settings
+            .as_object_mut()
+            .ok_or_else(|| format!("settings for client '{}' are not an object", client_id))?;
+        let is_legacy = settings
+            .get("lastSyncChecksum")
+            .and_then(Value::as_str)
+            .is_some_and(|checksum| !checksum.starts_with("sha256:"));
+        if is_legacy {
+            settings.remove("lastSyncChecksum");
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a `major.minor` version string
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Bring a raw config up to [`CONFIG_VERSION`].
+///
+/// Returns the version the config was at if any migration ran.
+pub(super) fn migrate_config(config: &mut Value) -> Result<Option<String>, ConfigError> {
+    let found = config
+        .get("version")
+        .and_then(Value::as_str)
+        .unwrap_or(MIGRATIONS[0].from)
+        .to_string();
+
+    let Some(parsed) = parse_version(&found) else {
+        return Err(ConfigError::UnknownVersion(found));
+    };
+    if parsed > parse_version(CONFIG_VERSION).expect("CONFIG_VERSION is major.minor") {
+        return Err(ConfigError::NewerVersion {
+            found,
+            supported: CONFIG_VERSION.to_string(),
+        });
+    }
+
+    let mut version = found.clone();
+    while version != CONFIG_VERSION {
+        let step = MIGRATIONS
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| ConfigError::UnknownVersion(version.clone()))?;
+
+        (step.apply)(config).map_err(|message| ConfigError::Migration {
+            from: step.from.to_string(),
+            message,
+        })?;
+        version = step.to.to_string();
+        config["version"] = Value::String(version.clone());
+    }
+
+    Ok((found != CONFIG_VERSION).then_some(found))
+}
+
+/// The config file as this process last read or wrote it
+#[derive(Debug, Clone)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    checksum: String,
+}
+
+impl FileStamp {
+    /// Stamp for `content`, with `metadata` taken before it was read
+    fn new(metadata: Option<fs::Metadata>, content: &[u8]) -> Self {
+        Self {
+            modified: metadata.and_then(|m| m.modified().ok()),
+            len: content.len() as u64,
+            checksum: compute_checksum(content),
+        }
+    }
+
+    /// Cheap check that the file is still the one stamped
+    fn matches(&self, metadata: &fs::Metadata) -> bool {
+        self.modified.is_some()
+            && metadata.modified().ok() == self.modified
+            && metadata.len() == self.len
+    }
+}
+
+/// Callback run after each save with the previous config (if known) and the saved one
+pub type SaveHook = Box<dyn Fn(Option<&McpHubConfig>, &McpHubConfig) + Send + Sync>;
+
 /// Manages the central MCP Hub configuration file
 pub struct ConfigManager {
     config_dir: PathBuf,
     config_path: PathBuf,
     cache: RwLock<Option<McpHubConfig>>,
+    /// What the file looked like when this process last read or wrote it
+    last_seen: RwLock<Option<FileStamp>>,
+    on_save: RwLock<Option<SaveHook>>,
+    journal: ConfigJournal,
+}
+
+/// Holds the advisory lock on the config until dropped
+struct ConfigLock {
+    _file: File,
 }
 
 impl ConfigManager {
@@ -34,9 +225,12 @@ impl ConfigManager {
         let config_path = config_dir.join("config.json");
 
         Ok(Self {
+            journal: ConfigJournal::new(&config_dir),
             config_dir,
             config_path,
             cache: RwLock::new(None),
+            last_seen: RwLock::new(None),
+            on_save: RwLock::new(None),
         })
     }
 
@@ -45,9 +239,12 @@ impl ConfigManager {
     pub fn with_path(config_dir: PathBuf) -> Self {
         let config_path = config_dir.join("config.json");
         Self {
+            journal: ConfigJournal::new(&config_dir),
             config_dir,
             config_path,
             cache: RwLock::new(None),
+            last_seen: RwLock::new(None),
+            on_save: RwLock::new(None),
         }
     }
 
@@ -78,6 +275,7 @@ impl ConfigManager {
         }
 
         // Create default config file if it doesn't exist
+        let _lock = self.lock()?;
         if !self.config_path.exists() {
             let default_config = McpHubConfig::default();
             self.write_config(&default_config)?;
@@ -87,22 +285,101 @@ impl ConfigManager {
         Ok(first_run)
     }
 
-    /// Load the configuration from disk
+    /// Take the advisory lock shared by every process using this config.
+    ///
+    /// The lock lives in a separate file because saving replaces config.json.
+    fn lock(&self) -> Result<ConfigLock, ConfigError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.config_dir.join("config.lock"))?;
+        file.lock()?;
+        Ok(ConfigLock { _file: file })
+    }
+
+    /// The cached config, if the file has not changed since this process last saw it
+    fn fresh_cache(&self) -> Option<McpHubConfig> {
+        let metadata = fs::metadata(&self.config_path).ok()?;
+        let cache = self.cache.read().unwrap();
+        let config = cache.as_ref()?;
+        let fresh = self.last_seen.read().unwrap().as_ref()?.matches(&metadata);
+        fresh.then(|| config.clone())
+    }
+
+    /// Load the configuration from disk.
+    ///
+    /// The cache is used while the file's modification time and size are
+    /// unchanged, so edits made by hand or by another process are picked up.
     pub fn load(&self) -> Result<McpHubConfig, ConfigError> {
-        // Check cache first
-        {
-            let cache = self.cache.read().unwrap();
-            if let Some(ref config) = *cache {
-                return Ok(config.clone());
+        if let Some(config) = self.fresh_cache() {
+            return Ok(config);
+        }
+
+        // Reading locks too, since an old config is rewritten when migrated
+        let _lock = self.lock()?;
+        self.read_config()
+    }
+
+    /// Load the configuration along with its revision: the checksum of the
+    /// file it was read from, to pass back to [`Self::save_revision`]
+    pub fn load_with_revision(&self) -> Result<(McpHubConfig, String), ConfigError> {
+        let _lock = self.lock()?;
+        let config = self.load_locked()?;
+        let revision = self.revision().unwrap_or_default();
+        Ok((config, revision))
+    }
+
+    /// Checksum of the config file as last read or written
+    fn revision(&self) -> Option<String> {
+        self.last_seen.read().unwrap().as_ref().map(|seen| seen.checksum.clone())
+    }
+
+    /// Load while already holding the lock
+    fn load_locked(&self) -> Result<McpHubConfig, ConfigError> {
+        match self.fresh_cache() {
+            Some(config) => Ok(config),
+            None => self.read_config(),
+        }
+    }
+
+    /// Read the file into the cache, migrating it if needed (the caller holds the lock)
+    fn read_config(&self) -> Result<McpHubConfig, ConfigError> {
+        let metadata = fs::metadata(&self.config_path).ok();
+        let contents = fs::read_to_string(&self.config_path)?;
+        let stamp = FileStamp::new(metadata, contents.as_bytes());
+
+        // Only the timestamp changed (e.g. the file was touched): keep the cached config
+        let unchanged = self
+            .last_seen
+            .read()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|seen| seen.checksum == stamp.checksum);
+        if unchanged {
+            if let Some(config) = self.cache.read().unwrap().clone() {
+                *self.last_seen.write().unwrap() = Some(stamp);
+                return Ok(config);
             }
         }
 
-        // Read from file
-        let mut file = File::open(&self.config_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
+        let mut raw: Value = serde_json::from_str(&contents)?;
+        let migrated_from = migrate_config(&mut raw)?;
+        let config: McpHubConfig = serde_json::from_value(raw)?;
 
-        let config: McpHubConfig = serde_json::from_str(&contents)?;
+        // Keep the original next to the config, then persist the migrated form
+        if let Some(from) = migrated_from {
+            let backup_path = self.config_dir.join(format!("config.v{}.backup.json", from));
+            fs::write(&backup_path, &contents)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&backup_path, fs::Permissions::from_mode(0o600))?;
+            }
+            self.write_config(&config)?;
+        } else {
+            *self.last_seen.write().unwrap() = Some(stamp);
+        }
 
         // Update cache
         {
@@ -113,8 +390,96 @@ impl ConfigManager {
         Ok(config)
     }
 
-    /// Save the configuration to disk
-    pub fn save(&self, config: &McpHubConfig) -> Result<(), ConfigError> {
+    /// Fail if the file changed since this process last read or wrote it,
+    /// so a stale copy never overwrites a newer one (the caller holds the lock)
+    fn ensure_unchanged(&self) -> Result<(), ConfigError> {
+        let Some(seen) = self.last_seen.read().unwrap().clone() else {
+            return Ok(());
+        };
+
+        match fs::read(&self.config_path) {
+            Ok(content) if compute_checksum(&content) != seen.checksum => {
+                self.invalidate_cache();
+                Err(ConfigError::ModifiedExternally)
+            }
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Run `hook` after every successful save (replacing any previous hook)
+    pub fn set_save_hook<F>(&self, hook: F)
+    where
+        F: Fn(Option<&McpHubConfig>, &McpHubConfig) + Send + Sync + 'static,
+    {
+        *self.on_save.write().unwrap() = Some(Box::new(hook));
+    }
+
+    /// Save a config read at `revision` (see [`Self::load_with_revision`]),
+    /// returning the new revision.
+    ///
+    /// Fails with [`ConfigError::ModifiedExternally`] if the file has changed
+    /// since, however many times it was reloaded in between.
+    pub fn save_revision(
+        &self,
+        config: &McpHubConfig,
+        revision: &str,
+    ) -> Result<String, ConfigError> {
+        let previous = {
+            let _lock = self.lock()?;
+            let current = self.revision();
+            if current.as_deref() != Some(revision) {
+                return Err(ConfigError::ModifiedExternally);
+            }
+            let operation = "Save config".to_string();
+            self.commit_locked(config, operation, JournalAction::Change)?.0
+        };
+
+        self.run_save_hook(previous.as_ref(), config);
+        Ok(self.revision().unwrap_or_default())
+    }
+
+    /// Load the config, apply `change` and save the result, holding the lock
+    /// throughout so no other save can land in between and be overwritten.
+    ///
+    /// `change` returns its result and the description the change is
+    /// journaled under; if it fails, nothing is saved. It must not call back
+    /// into this manager, which would wait on the lock it holds.
+    pub fn modify<T, E, F>(&self, change: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut McpHubConfig) -> Result<(T, String), E>,
+        E: From<ConfigError>,
+    {
+        let (value, config, previous) = {
+            let _lock = self.lock()?;
+            let mut config = self.load_locked()?;
+            let (value, operation) = change(&mut config)?;
+            let previous = self.commit_locked(&config, operation, JournalAction::Change)?.0;
+            (value, config, previous)
+        };
+
+        self.run_save_hook(previous.as_ref(), &config);
+        Ok(value)
+    }
+
+    /// Write the config and journal the change, returning the config it
+    /// replaced (the caller holds the lock, then runs the save hook).
+    ///
+    /// Every server added or changed is validated first, whichever path the
+    /// change came through. Saves that only record sync bookkeeping are not
+    /// journaled, so undo always reverts something the user did. Undo and redo
+    /// are always journaled, since replaying the journal relies on them.
+    fn commit_locked(
+        &self,
+        config: &McpHubConfig,
+        operation: String,
+        action: JournalAction,
+    ) -> Result<(Option<McpHubConfig>, Option<JournalEntry>), ConfigError> {
+        self.ensure_unchanged()?;
+        let previous = self.load_locked().ok();
+        check_changed_servers(config, previous.as_ref())?;
+
         self.write_config(config)?;
 
         // Update cache
@@ -123,10 +488,120 @@ impl ConfigManager {
             *cache = Some(config.clone());
         }
 
-        Ok(())
+        let entry = match &previous {
+            Some(previous)
+                if action == JournalAction::Change && !is_user_change(previous, config) =>
+            {
+                None
+            }
+            _ => Some(self.journal.append(operation, action, previous.as_ref(), config)?),
+        };
+        Ok((previous, entry))
+    }
+
+    /// Run the save hook, if any, after a save
+    fn run_save_hook(&self, previous: Option<&McpHubConfig>, config: &McpHubConfig) {
+        if let Some(hook) = self.on_save.read().unwrap().as_ref() {
+            hook(previous, config);
+        }
     }
 
-    /// Write config to disk using atomic write pattern (write to temp, then rename)
+    /// Save a config taken from the journal, keeping the current sync bookkeeping
+    fn commit_snapshot(
+        &self,
+        snapshot: &McpHubConfig,
+        operation: String,
+        action: JournalAction,
+    ) -> Result<Option<JournalSummary>, ConfigError> {
+        // Journal snapshots are already migrated to the current version
+        let mut config = snapshot.clone();
+        let (previous, entry) = {
+            let _lock = self.lock()?;
+            if let Ok(current) = self.load_locked() {
+                keep_sync_state(&mut config, &current);
+            }
+            self.commit_locked(&config, operation, action)?
+        };
+
+        self.run_save_hook(previous.as_ref(), &config);
+        Ok(entry.as_ref().map(JournalSummary::from))
+    }
+
+    /// Journaled changes, newest first
+    pub fn history(&self) -> Result<Vec<JournalSummary>, ConfigError> {
+        let entries = self.journal.entries()?;
+        Ok(entries.iter().rev().map(JournalSummary::from).collect())
+    }
+
+    /// Revert the most recent change that has not been undone.
+    ///
+    /// Returns the journal entry recording the undo, or `None` if there is
+    /// nothing to undo.
+    pub fn undo_last_change(&self) -> Result<Option<JournalSummary>, ConfigError> {
+        let entries = self.journal.entries()?;
+        let (undo, _) = undo_redo_stacks(&entries);
+        let Some(entry) = undo.last().and_then(|id| entries.iter().find(|e| e.id == *id)) else {
+            return Ok(None);
+        };
+        let Some(before) = &entry.before else {
+            return Ok(None);
+        };
+
+        self.commit_snapshot(
+            before,
+            format!("Undo: {}", entry.operation),
+            JournalAction::Undo { entry: entry.id },
+        )
+    }
+
+    /// Re-apply the most recently undone change.
+    ///
+    /// Returns the journal entry recording the redo, or `None` if there is
+    /// nothing to redo.
+    pub fn redo(&self) -> Result<Option<JournalSummary>, ConfigError> {
+        let entries = self.journal.entries()?;
+        let (_, redo) = undo_redo_stacks(&entries);
+        let Some(entry) = redo.last().and_then(|id| entries.iter().find(|e| e.id == *id)) else {
+            return Ok(None);
+        };
+
+        self.commit_snapshot(
+            &entry.after,
+            format!("Redo: {}", entry.operation),
+            JournalAction::Redo { entry: entry.id },
+        )
+    }
+
+    /// Restore the config as it was at `timestamp` (RFC 3339).
+    ///
+    /// The restore is itself a change that can be undone. Returns `None` if
+    /// the journal has no record of the config at that time.
+    pub fn restore_to(&self, timestamp: &str) -> Result<Option<JournalSummary>, ConfigError> {
+        let target = chrono::DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|_| ConfigError::InvalidTimestamp(timestamp.to_string()))?;
+        let entries = self.journal.entries()?;
+
+        let at = |entry: &JournalEntry| chrono::DateTime::parse_from_rfc3339(&entry.timestamp).ok();
+        let snapshot = match entries.iter().rev().find(|e| at(e).is_some_and(|t| t <= target)) {
+            Some(entry) => Some(&entry.after),
+            // Before the first journaled change: use what it replaced, unless
+            // compaction has dropped the changes in between
+            None => entries.first().filter(|e| e.id == 1).and_then(|e| e.before.as_ref()),
+        };
+        let Some(snapshot) = snapshot else {
+            return Ok(None);
+        };
+
+        self.commit_snapshot(
+            snapshot,
+            format!("Restore config to {}", timestamp),
+            JournalAction::Change,
+        )
+    }
+
+    /// Write config to disk using atomic write pattern (write to temp, then rename).
+    ///
+    /// The caller holds the lock.
     fn write_config(&self, config: &McpHubConfig) -> Result<(), ConfigError> {
         let json = serde_json::to_string_pretty(config)?;
 
@@ -147,13 +622,16 @@ impl ConfigManager {
         }
 
         // Atomic rename
+        note_own_write(&self.config_path, Some(&json));
         fs::rename(&temp_path, &self.config_path)?;
 
+        let metadata = fs::metadata(&self.config_path).ok();
+        *self.last_seen.write().unwrap() = Some(FileStamp::new(metadata, json.as_bytes()));
+
         Ok(())
     }
 
     /// Invalidate the cache (force reload on next read)
-    #[allow(dead_code)]
     pub fn invalidate_cache(&self) {
         let mut cache = self.cache.write().unwrap();
         *cache = None;
@@ -176,37 +654,36 @@ impl ConfigManager {
 
     /// Add a new server
     pub fn add_server(&self, server: McpServer) -> Result<McpServer, ConfigError> {
-        let mut config = self.load()?;
-        let server_clone = server.clone();
-        config.add_server(server);
-        self.save(&config)?;
-        Ok(server_clone)
+        self.modify(|config| {
+            let operation = format!("Add server '{}'", server.name);
+            config.add_server(server.clone());
+            Ok((server, operation))
+        })
     }
 
     /// Update an existing server
     pub fn update_server(&self, server: McpServer) -> Result<McpServer, ConfigError> {
-        let mut config = self.load()?;
-        let server_id = server.id;
-
-        if let Some(pos) = config.servers.iter().position(|s| s.id == server_id) {
+        self.modify(|config| {
+            let pos = config
+                .servers
+                .iter()
+                .position(|s| s.id == server.id)
+                .ok_or(ConfigError::ServerNotFound(server.id))?;
             config.servers[pos] = server.clone();
-            self.save(&config)?;
-            Ok(server)
-        } else {
-            Err(ConfigError::ServerNotFound(server_id))
-        }
+            let operation = format!("Update server '{}'", server.name);
+            Ok((server, operation))
+        })
     }
 
     /// Remove a server by ID
     pub fn remove_server(&self, server_id: &Uuid) -> Result<McpServer, ConfigError> {
-        let mut config = self.load()?;
-        config
-            .remove_server(server_id)
-            .ok_or(ConfigError::ServerNotFound(*server_id))
-            .and_then(|removed| {
-                self.save(&config)?;
-                Ok(removed)
-            })
+        self.modify(|config| {
+            let removed = config
+                .remove_server(server_id)
+                .ok_or(ConfigError::ServerNotFound(*server_id))?;
+            let operation = format!("Remove server '{}'", removed.name);
+            Ok((removed, operation))
+        })
     }
 
     /// Toggle a server's enabled status for a specific client
@@ -216,26 +693,63 @@ impl ConfigManager {
         client_id: &str,
         enabled: bool,
     ) -> Result<(), ConfigError> {
-        let mut config = self.load()?;
-
-        let server = config
-            .get_server_mut(server_id)
-            .ok_or(ConfigError::ServerNotFound(*server_id))?;
+        self.modify(|config| {
+            let server = config
+                .get_server_mut(server_id)
+                .ok_or(ConfigError::ServerNotFound(*server_id))?;
+
+            let operation = if enabled {
+                server.enable_for_client(client_id);
+                format!("Enable '{}' for {}", server.name, client_id)
+            } else {
+                server.disable_for_client(client_id);
+                format!("Disable '{}' for {}", server.name, client_id)
+            };
+            Ok(((), operation))
+        })
+    }
 
-        if enabled {
-            server.enable_for_client(client_id);
-        } else {
-            server.disable_for_client(client_id);
-        }
+    /// Set or clear a server's override for a specific client
+    pub fn set_server_client_override(
+        &self,
+        server_id: &Uuid,
+        client_id: &str,
+        client_override: Option<ClientOverride>,
+    ) -> Result<McpServer, ConfigError> {
+        self.modify(|config| {
+            let server = config
+                .get_server_mut(server_id)
+                .ok_or(ConfigError::ServerNotFound(*server_id))?;
+            server.set_client_override(client_id, client_override);
+
+            let operation = format!("Change {} override of '{}'", client_id, server.name);
+            Ok((server.clone(), operation))
+        })
+    }
 
-        self.save(&config)
+    /// Set or clear a server's entry options for a specific client
+    pub fn set_server_client_options(
+        &self,
+        server_id: &Uuid,
+        client_id: &str,
+        options: Option<ClientOptions>,
+    ) -> Result<McpServer, ConfigError> {
+        self.modify(|config| {
+            let server = config
+                .get_server_mut(server_id)
+                .ok_or(ConfigError::ServerNotFound(*server_id))?;
+            server.set_client_options(client_id, options);
+
+            let operation = format!("Change {} options of '{}'", client_id, server.name);
+            Ok((server.clone(), operation))
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{ServerSource, Transport};
+    use crate::models::{ArgsOverride, ServerSource, Transport};
     use std::collections::HashMap;
     use tempfile::TempDir;
 
@@ -280,7 +794,7 @@ mod tests {
         manager.initialize().unwrap();
 
         let config = manager.load().unwrap();
-        assert_eq!(config.version, "1.0");
+        assert_eq!(config.version, CONFIG_VERSION);
         assert!(config.servers.is_empty());
     }
 
@@ -317,6 +831,64 @@ mod tests {
         assert_eq!(retrieved.name, "updated-server");
     }
 
+    #[test]
+    fn test_invalid_servers_are_not_saved() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        let server = create_test_server("test-server");
+        manager.add_server(server.clone()).unwrap();
+
+        // Duplicate names would collide as keys in client configs
+        let duplicate = create_test_server("test-server");
+        match manager.add_server(duplicate) {
+            Err(ConfigError::InvalidServer(diagnostics)) => {
+                assert_eq!(diagnostics[0].field, "name");
+            }
+            other => panic!("expected InvalidServer, got {:?}", other),
+        }
+
+        let mut renamed = server.clone();
+        renamed.name = String::new();
+        assert!(matches!(
+            manager.update_server(renamed),
+            Err(ConfigError::InvalidServer(_))
+        ));
+
+        let servers = manager.get_servers().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "test-server");
+    }
+
+    #[test]
+    fn test_whole_config_saves_and_imports_are_validated() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        manager.add_server(create_test_server("test-server")).unwrap();
+
+        // A whole-config save from the editor
+        let (mut config, revision) = manager.load_with_revision().unwrap();
+        config.add_server(create_test_server(" test-server "));
+        assert!(matches!(
+            manager.save_revision(&config, &revision),
+            Err(ConfigError::InvalidServer(_))
+        ));
+
+        // An import adding a server with no command
+        let mut imported = create_test_server("imported");
+        imported.transport = Transport::Stdio {
+            command: String::new(),
+            args: vec![],
+            env: HashMap::new(),
+        };
+        let result = manager.modify(|config| {
+            config.add_server(imported);
+            Ok(((), "Import servers".to_string()))
+        });
+        assert!(matches!(result, Err(ConfigError::InvalidServer(_))));
+        assert_eq!(manager.get_servers().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_remove_server() {
         let (manager, _temp) = create_test_manager();
@@ -368,7 +940,7 @@ mod tests {
         // Invalidate and reload
         manager.invalidate_cache();
         let config = manager.load().unwrap();
-        assert_eq!(config.version, "1.0");
+        assert_eq!(config.version, CONFIG_VERSION);
     }
 
     #[test]
@@ -394,11 +966,13 @@ mod tests {
         manager.initialize().unwrap();
 
         // Load current config and flip the auto_sync_on_changes flag
-        let mut config = manager.load().unwrap();
-        assert!(config.preferences.auto_sync_on_changes);
-
-        config.preferences.auto_sync_on_changes = false;
-        manager.save(&config).unwrap();
+        let config = manager
+            .modify(|config| {
+                assert!(config.preferences.auto_sync_on_changes);
+                config.preferences.auto_sync_on_changes = false;
+                Ok::<_, ConfigError>((config.clone(), "Change preferences".to_string()))
+            })
+            .unwrap();
 
         // Reload from disk and ensure the preference value persisted
         let reloaded = manager.load().unwrap();
@@ -423,4 +997,321 @@ mod tests {
         // Check that file is 0600 (user read/write only)
         assert_eq!(mode & 0o777, 0o600);
     }
+
+    #[test]
+    fn test_client_override_round_trip() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        let server = create_test_server("test-server");
+        let server_id = server.id;
+        manager.add_server(server).unwrap();
+
+        let client_override = ClientOverride {
+            args: Some(ArgsOverride::Replace(vec!["--root".to_string(), "/work".to_string()])),
+            // Saved servers are validated, so no keychain reference to a missing credential
+            env: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            headers: HashMap::new(),
+        };
+        manager
+            .set_server_client_override(&server_id, "cursor", Some(client_override.clone()))
+            .unwrap();
+
+        let retrieved = manager.get_server(&server_id).unwrap();
+        assert_eq!(retrieved.client_overrides.get("cursor"), Some(&client_override));
+
+        manager
+            .set_server_client_override(&server_id, "cursor", None)
+            .unwrap();
+        let retrieved = manager.get_server(&server_id).unwrap();
+        assert!(retrieved.client_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_save_hook_receives_previous_config() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&seen);
+        manager.set_save_hook(move |previous, current| {
+            let previous_count = previous.map(|c| c.servers.len());
+            recorded.lock().unwrap().push((previous_count, current.servers.len()));
+        });
+
+        manager.add_server(create_test_server("test-server")).unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec![(Some(0), 1)]);
+    }
+
+    #[test]
+    fn test_load_migrates_and_backs_up_old_config() {
+        let (manager, temp) = create_test_manager();
+        let original = r#"{
+            "version": "1.0",
+            "clients": {
+                "cursor": {"configPath": "/c/mcp.json", "lastSyncChecksum": "1f2e3d4c"},
+                "zed": {"configPath": "/z/settings.json", "lastSyncChecksum": "sha256:ab"}
+            }
+        }"#;
+        fs::write(manager.config_path(), original).unwrap();
+
+        let config = manager.load().unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.clients["cursor"].last_sync_checksum, None);
+        assert_eq!(config.clients["zed"].last_sync_checksum.as_deref(), Some("sha256:ab"));
+
+        // The original is kept and the migrated config is written back
+        let backup = temp.path().join("config.v1.0.backup.json");
+        assert_eq!(fs::read_to_string(backup).unwrap(), original);
+        let on_disk: McpHubConfig =
+            serde_json::from_str(&fs::read_to_string(manager.config_path()).unwrap()).unwrap();
+        assert_eq!(on_disk.version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn test_migrations_form_a_chain_to_current_version() {
+        assert_eq!(MIGRATIONS.last().unwrap().to, CONFIG_VERSION);
+        for pair in MIGRATIONS.windows(2) {
+            assert_eq!(pair[0].to, pair[1].from);
+        }
+
+        // A current config is left alone
+        let mut config = serde_json::to_value(McpHubConfig::default()).unwrap();
+        assert_eq!(migrate_config(&mut config).unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_rejects_newer_and_unknown_versions() {
+        let (manager, temp) = create_test_manager();
+
+        fs::write(manager.config_path(), r#"{"version": "99.0", "servers": []}"#).unwrap();
+        match manager.load() {
+            Err(ConfigError::NewerVersion { found, supported }) => {
+                assert_eq!(found, "99.0");
+                assert_eq!(supported, CONFIG_VERSION);
+            }
+            other => panic!("Expected NewerVersion, got {:?}", other),
+        }
+        // The newer file is not touched, and no backup is made
+        assert!(fs::read_to_string(manager.config_path()).unwrap().contains("99.0"));
+        let backups = fs::read_dir(temp.path())
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().contains("backup"))
+            .count();
+        assert_eq!(backups, 0);
+
+        fs::write(manager.config_path(), r#"{"version": "one", "servers": []}"#).unwrap();
+        assert!(matches!(manager.load(), Err(ConfigError::UnknownVersion(_))));
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        assert!(manager.undo_last_change().unwrap().is_none());
+
+        let server = create_test_server("test-server");
+        let server_id = server.id;
+        manager.add_server(server).unwrap();
+        manager.toggle_server_client(&server_id, "cursor", true).unwrap();
+
+        let undone = manager.undo_last_change().unwrap().unwrap();
+        assert_eq!(undone.operation, "Undo: Enable 'test-server' for cursor");
+        assert!(manager.get_server(&server_id).unwrap().enabled_clients.is_empty());
+
+        manager.undo_last_change().unwrap().unwrap();
+        assert!(manager.get_servers().unwrap().is_empty());
+        assert!(manager.undo_last_change().unwrap().is_none());
+
+        manager.redo().unwrap().unwrap();
+        manager.redo().unwrap().unwrap();
+        assert!(manager.redo().unwrap().is_none());
+        assert_eq!(manager.get_server(&server_id).unwrap().enabled_clients, vec!["cursor"]);
+
+        // A new change after an undo drops what could be redone
+        manager.undo_last_change().unwrap().unwrap();
+        manager.add_server(create_test_server("other")).unwrap();
+        assert!(manager.redo().unwrap().is_none());
+
+        let history = manager.history().unwrap();
+        assert_eq!(history[0].operation, "Add server 'other'");
+        assert_eq!(history.len(), 8);
+    }
+
+    #[test]
+    fn test_undo_keeps_sync_bookkeeping() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        manager.add_server(create_test_server("test-server")).unwrap();
+
+        // Recording a sync is not journaled
+        manager
+            .modify(|config| {
+                config.clients.insert(
+                    "cursor".to_string(),
+                    crate::models::ClientSettings {
+                        enabled: true,
+                        last_sync: Some("2026-01-01T00:00:00Z".to_string()),
+                        ..Default::default()
+                    },
+                );
+                Ok::<_, ConfigError>(((), "Record sync".to_string()))
+            })
+            .unwrap();
+        assert_eq!(manager.history().unwrap().len(), 1);
+
+        manager.undo_last_change().unwrap().unwrap();
+        let config = manager.load().unwrap();
+        assert!(config.servers.is_empty());
+        assert_eq!(
+            config.clients["cursor"].last_sync.as_deref(),
+            Some("2026-01-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_restore_to_point_in_time() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        manager.add_server(create_test_server("first")).unwrap();
+        let after_first = chrono::Utc::now().to_rfc3339();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        manager.add_server(create_test_server("second")).unwrap();
+
+        manager.restore_to(&after_first).unwrap().unwrap();
+        let servers = manager.get_servers().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "first");
+
+        // Before any journaled change, the config the first change replaced
+        manager.restore_to("2000-01-01T00:00:00Z").unwrap().unwrap();
+        assert!(manager.get_servers().unwrap().is_empty());
+
+        // The restore itself can be undone
+        manager.undo_last_change().unwrap().unwrap();
+        assert_eq!(manager.get_servers().unwrap().len(), 1);
+
+        assert!(matches!(
+            manager.restore_to("yesterday"),
+            Err(ConfigError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_picks_up_external_edits() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        assert!(manager.load().unwrap().servers.is_empty());
+
+        // Another writer adds a server behind the cache's back
+        let mut edited = McpHubConfig::default();
+        edited.add_server(create_test_server("hand-added"));
+        fs::write(manager.config_path(), serde_json::to_string(&edited).unwrap()).unwrap();
+
+        assert_eq!(manager.load().unwrap().servers[0].name, "hand-added");
+    }
+
+    #[test]
+    fn test_save_rejects_stale_copy() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        let (stale, revision) = manager.load_with_revision().unwrap();
+
+        let mut edited = McpHubConfig::default();
+        edited.add_server(create_test_server("hand-added"));
+        let edited_json = serde_json::to_string(&edited).unwrap();
+        fs::write(manager.config_path(), &edited_json).unwrap();
+
+        assert!(matches!(
+            manager.save_revision(&stale, &revision),
+            Err(ConfigError::ModifiedExternally)
+        ));
+        assert_eq!(fs::read_to_string(manager.config_path()).unwrap(), edited_json);
+
+        // Changes are applied on top of the newer file
+        manager.add_server(create_test_server("second")).unwrap();
+        assert_eq!(manager.load().unwrap().servers.len(), 2);
+    }
+
+    #[test]
+    fn test_save_rejects_copy_loaded_before_another_save() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        let (mut first, first_revision) = manager.load_with_revision().unwrap();
+        let (mut second, second_revision) = manager.load_with_revision().unwrap();
+
+        first.add_server(create_test_server("first"));
+        manager.save_revision(&first, &first_revision).unwrap();
+
+        second.add_server(create_test_server("second"));
+        assert!(matches!(
+            manager.save_revision(&second, &second_revision),
+            Err(ConfigError::ModifiedExternally)
+        ));
+        assert_eq!(manager.load().unwrap().servers[0].name, "first");
+    }
+
+    #[test]
+    fn test_concurrent_changes_are_all_kept() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let manager = &manager;
+                scope.spawn(move || {
+                    for i in 0..5 {
+                        let name = format!("server-{}-{}", thread, i);
+                        manager.add_server(create_test_server(&name)).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(manager.load().unwrap().servers.len(), 20);
+    }
+
+    #[test]
+    fn test_save_revision_rejects_copy_older_than_reload() {
+        let (manager, _temp) = create_test_manager();
+        manager.initialize().unwrap();
+        let (stale, revision) = manager.load_with_revision().unwrap();
+
+        // An external edit that Nexus has since reloaded
+        let mut edited = manager.load().unwrap();
+        edited.add_server(create_test_server("hand-added"));
+        fs::write(manager.config_path(), serde_json::to_string(&edited).unwrap()).unwrap();
+        assert_eq!(manager.load().unwrap().servers.len(), 1);
+
+        assert!(matches!(
+            manager.save_revision(&stale, &revision),
+            Err(ConfigError::ModifiedExternally)
+        ));
+        assert_eq!(manager.load().unwrap().servers.len(), 1);
+
+        // Saving at the current revision works and returns the next one
+        let (mut current, revision) = manager.load_with_revision().unwrap();
+        current.add_server(create_test_server("second"));
+        let next = manager.save_revision(&current, &revision).unwrap();
+        assert_ne!(next, revision);
+        assert_eq!(manager.load_with_revision().unwrap().1, next);
+    }
+
+    #[test]
+    fn test_lock_excludes_other_writers() {
+        let (manager, temp) = create_test_manager();
+        manager.initialize().unwrap();
+
+        let other = OpenOptions::new()
+            .write(true)
+            .open(temp.path().join("config.lock"))
+            .unwrap();
+        {
+            let _lock = manager.lock().unwrap();
+            assert!(matches!(other.try_lock(), Err(std::fs::TryLockError::WouldBlock)));
+        }
+        other.try_lock().unwrap();
+    }
 }
@@ -0,0 +1,457 @@
+// src-tauri/src/services/config_watcher.rs
+//! Watching client configs and the central config for edits made outside Nexus
+//!
+//! Parent directories are watched rather than the files themselves, so that
+//! editors replacing a file (write to temp + rename) and configs created after
+//! startup are both seen; for a directory that does not exist yet, its nearest
+//! existing ancestor is watched until it appears. Events are debounced and
+//! only reported when a file's content actually changed to something Nexus did
+//! not write itself (see [`note_own_write`]).
+
+use crate::models::{ClientId, McpHubConfig};
+use crate::services::app_log::log_warning;
+use crate::services::client_detector::{get_client_config_candidates, parse_client_config};
+use crate::services::config_manager::migrate_config;
+use crate::services::sync_engine::compute_checksum;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+/// Quiet period before a change is reported, so a burst of writes yields one event
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Name of the event emitted to the frontend
+pub const CONFIG_CHANGED_EVENT: &str = "config-changed";
+
+/// A watched config file that changed on disk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChangeEvent {
+    /// Client owning the file; `None` for the central Nexus config
+    pub client_id: Option<ClientId>,
+    pub path: String,
+    /// Servers that appeared since the file was last read
+    pub added: Vec<String>,
+    /// Servers that disappeared since the file was last read
+    pub removed: Vec<String>,
+    /// Set if the file no longer parses (`added`/`removed` are then empty)
+    pub error: Option<String>,
+}
+
+/// A watched file and what it held when last read
+struct WatchedFile {
+    client_id: Option<ClientId>,
+    path: PathBuf,
+    /// Checksum of the content (`None` if the file did not exist)
+    checksum: Option<String>,
+    servers: BTreeSet<String>,
+}
+
+/// Checksum of the last write Nexus made to each file (`None` for a removal)
+static OWN_WRITES: Mutex<BTreeMap<PathBuf, Option<String>>> = Mutex::new(BTreeMap::new());
+
+/// Record that Nexus is about to write `content` to `path` (or remove it, for
+/// `None`), so the watcher does not report the change as an external edit
+pub fn note_own_write(path: &Path, content: Option<&str>) {
+    let checksum = content.map(|c| compute_checksum(c.as_bytes()));
+    OWN_WRITES.lock().unwrap().insert(path.to_path_buf(), checksum);
+}
+
+/// Whether `path` holds what Nexus last wrote to it
+fn is_own_write(path: &Path, checksum: &Option<String>) -> bool {
+    OWN_WRITES.lock().unwrap().get(path) == Some(checksum)
+}
+
+/// Directory to watch for changes to `path`: its parent, or the nearest
+/// ancestor that exists if the parent does not yet
+fn watch_dir(path: &Path) -> Option<PathBuf> {
+    path.parent()?
+        .ancestors()
+        .find(|dir| !dir.as_os_str().is_empty() && dir.is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Server names in a config file's content
+fn server_names(
+    client_id: Option<ClientId>,
+    path: &Path,
+    content: &str,
+) -> Result<BTreeSet<String>, String> {
+    match client_id {
+        Some(client_id) => parse_client_config(client_id, path, content)
+            .map(|info| info.server_names.into_iter().collect())
+            .map_err(|e| e.to_string()),
+        // Read like the config manager does, so an older config is migrated
+        // and one from a newer release is reported rather than misread
+        None => {
+            let mut raw: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+            migrate_config(&mut raw).map_err(|e| e.to_string())?;
+            serde_json::from_value::<McpHubConfig>(raw)
+                .map(|config| config.servers.into_iter().map(|s| s.name).collect())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+impl WatchedFile {
+    fn new(client_id: Option<ClientId>, path: PathBuf) -> Self {
+        let mut file = Self {
+            client_id,
+            path,
+            checksum: None,
+            servers: BTreeSet::new(),
+        };
+        file.refresh();
+        file
+    }
+
+    /// Re-read the file, returning an event if its content changed outside Nexus
+    fn refresh(&mut self) -> Option<ConfigChangeEvent> {
+        let content = fs::read_to_string(&self.path).ok();
+        let checksum = content.as_deref().map(|c| compute_checksum(c.as_bytes()));
+        if checksum == self.checksum {
+            return None;
+        }
+        self.checksum = checksum;
+
+        let parsed = match &content {
+            Some(content) => server_names(self.client_id, &self.path, content),
+            None => Ok(BTreeSet::new()),
+        };
+
+        let mut event = ConfigChangeEvent {
+            client_id: self.client_id,
+            path: self.path.to_string_lossy().to_string(),
+            added: vec![],
+            removed: vec![],
+            error: None,
+        };
+
+        match parsed {
+            Ok(servers) => {
+                event.added = servers.difference(&self.servers).cloned().collect();
+                event.removed = self.servers.difference(&servers).cloned().collect();
+                self.servers = servers;
+            }
+            // Keep the last good snapshot so the diff is right once the file is fixed
+            Err(e) => event.error = Some(e),
+        }
+
+        // The snapshot is updated either way, so later diffs start from this write
+        (!is_own_write(&self.path, &self.checksum)).then_some(event)
+    }
+}
+
+/// Files to watch: the central config plus every candidate config of every client
+pub fn watch_targets(central_config: &Path) -> Vec<(Option<ClientId>, PathBuf)> {
+    let mut targets = vec![(None, central_config.to_path_buf())];
+
+    for client_id in ClientId::all_with_custom() {
+        let candidates = get_client_config_candidates(client_id).unwrap_or_default();
+        targets.extend(candidates.into_iter().map(|path| (Some(client_id), path)));
+    }
+
+    targets
+}
+
+/// Watched files and the directories currently watched for them
+struct WatchState {
+    files: Vec<WatchedFile>,
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl WatchState {
+    /// Re-read the files `filter` selects, returning their changes
+    fn refresh<P>(&mut self, filter: P) -> Vec<ConfigChangeEvent>
+    where
+        P: Fn(&WatchedFile) -> bool,
+    {
+        self.files
+            .iter_mut()
+            .filter(|file| filter(file))
+            .filter_map(WatchedFile::refresh)
+            .collect()
+    }
+
+    /// Watch the directories the files need now, returning the newly watched ones
+    fn update_watches(&mut self, watcher: &mut dyn Watcher) -> notify::Result<Vec<PathBuf>> {
+        let wanted: BTreeSet<PathBuf> =
+            self.files.iter().filter_map(|file| watch_dir(&file.path)).collect();
+
+        for dir in self.dirs.difference(&wanted) {
+            // Fails harmlessly if the directory was deleted
+            let _ = watcher.unwatch(dir);
+        }
+        self.dirs.retain(|dir| wanted.contains(dir));
+
+        let mut added = Vec::new();
+        for dir in wanted {
+            if !self.dirs.contains(&dir) {
+                watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+                self.dirs.insert(dir.clone());
+                added.push(dir);
+            }
+        }
+
+        Ok(added)
+    }
+}
+
+type SharedDebouncer = Arc<Mutex<Option<Debouncer<RecommendedWatcher>>>>;
+
+/// Background watcher reporting external changes to config files.
+///
+/// Watching stops when this is dropped.
+pub struct ConfigWatcher {
+    state: Arc<Mutex<WatchState>>,
+    debouncer: SharedDebouncer,
+}
+
+impl ConfigWatcher {
+    /// Start watching `targets`, calling `on_change` from a background thread for each change
+    pub fn start<F>(
+        targets: Vec<(Option<ClientId>, PathBuf)>,
+        on_change: F,
+    ) -> Result<Self, notify::Error>
+    where
+        F: Fn(ConfigChangeEvent) + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(WatchState {
+            files: targets
+                .into_iter()
+                .map(|(client_id, path)| WatchedFile::new(client_id, path))
+                .collect(),
+            dirs: BTreeSet::new(),
+        }));
+        let debouncer: SharedDebouncer = Arc::new(Mutex::new(None));
+
+        // Weak, so dropping the watcher is not prevented by its own callback
+        let handler_state = Arc::clone(&state);
+        let handler_debouncer: Weak<_> = Arc::downgrade(&debouncer);
+        let handler = move |result: DebounceEventResult| {
+            let Ok(events) = result else {
+                return;
+            };
+            let changed: HashSet<PathBuf> = events.into_iter().map(|e| e.path).collect();
+
+            let mut state = handler_state.lock().unwrap();
+            let mut events = state.refresh(|file| changed.contains(&file.path));
+
+            // A directory on the way to a config may have been created or removed
+            if let Some(debouncer) = handler_debouncer.upgrade() {
+                if let Some(debouncer) = debouncer.lock().unwrap().as_mut() {
+                    match state.update_watches(debouncer.watcher()) {
+                        // The config may have been written before its directory was watched
+                        Ok(added) => events.extend(state.refresh(|file| {
+                            added.iter().any(|dir| file.path.starts_with(dir))
+                        })),
+                        Err(e) => {
+                            log_warning(&format!("Failed to update config watches: {}", e))
+                        }
+                    }
+                }
+            }
+            drop(state);
+
+            for event in events {
+                on_change(event);
+            }
+        };
+
+        let mut created = new_debouncer(DEBOUNCE, handler)?;
+        state.lock().unwrap().update_watches(created.watcher())?;
+        *debouncer.lock().unwrap() = Some(created);
+
+        Ok(Self { state, debouncer })
+    }
+
+    /// Replace the watched files, e.g. after client descriptors were reloaded.
+    ///
+    /// Files that were already watched keep their snapshot.
+    pub fn set_targets(&self, targets: Vec<(Option<ClientId>, PathBuf)>) -> notify::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let mut previous = std::mem::take(&mut state.files);
+        state.files = targets
+            .into_iter()
+            .map(|(client_id, path)| {
+                match previous.iter().position(|f| f.client_id == client_id && f.path == path) {
+                    Some(index) => previous.swap_remove(index),
+                    None => WatchedFile::new(client_id, path),
+                }
+            })
+            .collect();
+
+        if let Some(debouncer) = self.debouncer.lock().unwrap().as_mut() {
+            state.update_watches(debouncer.watcher())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watched_file_reports_added_and_removed_servers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        fs::write(&path, r#"{"mcpServers": {"a": {"command": "a"}}}"#).unwrap();
+
+        let mut file = WatchedFile::new(Some(ClientId::Cursor), path.clone());
+        assert!(file.refresh().is_none());
+
+        fs::write(&path, r#"{"mcpServers": {"b": {"command": "b"}}}"#).unwrap();
+        let event = file.refresh().unwrap();
+        assert_eq!(event.client_id, Some(ClientId::Cursor));
+        assert_eq!(event.added, vec!["b"]);
+        assert_eq!(event.removed, vec!["a"]);
+        assert!(event.error.is_none());
+
+        // Rewriting the same content is not a change
+        fs::write(&path, r#"{"mcpServers": {"b": {"command": "b"}}}"#).unwrap();
+        assert!(file.refresh().is_none());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(file.refresh().unwrap().removed, vec!["b"]);
+    }
+
+    #[test]
+    fn test_own_writes_are_not_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        let mut file = WatchedFile::new(Some(ClientId::Cursor), path.clone());
+
+        let content = r#"{"mcpServers": {"a": {"command": "a"}}}"#;
+        note_own_write(&path, Some(content));
+        fs::write(&path, content).unwrap();
+        assert!(file.refresh().is_none());
+
+        // An external edit afterwards is diffed against what Nexus wrote
+        fs::write(&path, r#"{"mcpServers": {"b": {"command": "b"}}}"#).unwrap();
+        let event = file.refresh().unwrap();
+        assert_eq!(event.added, vec!["b"]);
+        assert_eq!(event.removed, vec!["a"]);
+
+        note_own_write(&path, None);
+        fs::remove_file(&path).unwrap();
+        assert!(file.refresh().is_none());
+    }
+
+    #[test]
+    fn test_watch_dir_falls_back_to_existing_ancestor() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("a").join("b").join("mcp.json");
+        assert_eq!(watch_dir(&missing).unwrap(), temp_dir.path());
+
+        fs::create_dir_all(temp_dir.path().join("a").join("b")).unwrap();
+        assert_eq!(watch_dir(&missing).unwrap(), temp_dir.path().join("a").join("b"));
+    }
+
+    #[test]
+    fn test_watcher_sees_configs_in_directories_created_later() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join(".cursor");
+        let path = dir.join("mcp.json");
+        let late = temp_dir.path().join(".late").join("mcp.json");
+
+        let (sender, events) = std::sync::mpsc::channel();
+        let watcher = ConfigWatcher::start(vec![(Some(ClientId::Cursor), path)], move |event| {
+            let _ = sender.send(event);
+        })
+        .unwrap();
+
+        // Wait for events until one adds `server`, returning every server added
+        let wait_for = |server: &str| {
+            let deadline = std::time::Instant::now() + Duration::from_secs(10);
+            let mut added = Vec::new();
+            while !added.iter().any(|name| name == server) {
+                let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+                let event: ConfigChangeEvent = events
+                    .recv_timeout(remaining)
+                    .unwrap_or_else(|_| panic!("no event adding '{}'", server));
+                added.extend(event.added);
+            }
+            added
+        };
+
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("mcp.json"), r#"{"mcpServers": {"a": {"command": "a"}}}"#).unwrap();
+        assert_eq!(wait_for("a"), vec!["a"]);
+
+        // Targets added later are watched too
+        let targets = vec![
+            (Some(ClientId::Cursor), dir.join("mcp.json")),
+            (Some(ClientId::ClaudeCode), late.clone()),
+        ];
+        watcher.set_targets(targets).unwrap();
+        fs::create_dir(late.parent().unwrap()).unwrap();
+        fs::write(&late, r#"{"mcpServers": {"b": {"command": "b"}}}"#).unwrap();
+        assert_eq!(wait_for("b"), vec!["b"]);
+    }
+
+    #[test]
+    fn test_watched_file_parse_error_keeps_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        fs::write(&path, r#"{"mcpServers": {"a": {"command": "a"}}}"#).unwrap();
+        let mut file = WatchedFile::new(Some(ClientId::ClaudeCode), path.clone());
+
+        fs::write(&path, "{ broken").unwrap();
+        let event = file.refresh().unwrap();
+        assert!(event.error.is_some());
+        assert!(event.removed.is_empty());
+
+        fs::write(&path, r#"{"mcpServers": {"a": {"command": "a"}}}"#).unwrap();
+        let event = file.refresh().unwrap();
+        assert!(event.added.is_empty() && event.removed.is_empty());
+    }
+
+    #[test]
+    fn test_watched_central_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.json");
+        let mut config = McpHubConfig::default();
+        fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+        let mut file = WatchedFile::new(None, path.clone());
+
+        config.servers.push(crate::models::McpServer::new(
+            "fs".to_string(),
+            crate::models::ServerSource::Local {
+                path: "/bin/fs".to_string(),
+            },
+            crate::models::Transport::Stdio {
+                command: "/bin/fs".to_string(),
+                args: vec![],
+                env: Default::default(),
+            },
+        ));
+        fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+
+        let event = file.refresh().unwrap();
+        assert_eq!(event.client_id, None);
+        assert_eq!(event.added, vec!["fs"]);
+
+        // A config from a newer release is reported instead of being misread
+        let mut newer = serde_json::to_value(&config).unwrap();
+        newer["version"] = serde_json::json!("99.0");
+        fs::write(&path, newer.to_string()).unwrap();
+        let event = file.refresh().unwrap();
+        assert!(event.error.unwrap().contains("99.0"));
+
+        // An older one is migrated before its servers are read
+        let mut older = serde_json::to_value(&config).unwrap();
+        older["version"] = serde_json::json!("1.0");
+        fs::write(&path, older.to_string()).unwrap();
+        let event = file.refresh().unwrap();
+        assert!(event.error.is_none());
+        assert!(event.added.is_empty() && event.removed.is_empty());
+    }
+}
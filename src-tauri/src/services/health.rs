@@ -1,7 +1,8 @@
 // src-tauri/src/services/health.rs
-//! Server health check functionality for both stdio and SSE servers
+//! Server health check functionality for stdio, SSE and Streamable HTTP servers
 
 use crate::models::{McpServer, Transport};
+use crate::services::keychain::resolve_keychain_reference;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
@@ -91,6 +92,96 @@ pub async fn check_sse_health(url: &str, timeout_secs: u64) -> HealthCheckResult
     }
 }
 
+/// Perform a health check on a Streamable HTTP server.
+///
+/// Streamable HTTP endpoints only answer POSTed JSON-RPC, so this sends an
+/// `initialize` request rather than a plain GET.
+pub async fn check_http_health(
+    url: &str,
+    headers: &HashMap<String, String>,
+    timeout_secs: u64,
+) -> HealthCheckResult {
+    let now = chrono::Utc::now();
+    let start = std::time::Instant::now();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build();
+
+    let client = match client {
+        Ok(c) => c,
+        Err(e) => {
+            return HealthCheckResult {
+                server_id: String::new(),
+                status: HealthStatus::Unknown,
+                message: Some(format!("Failed to create HTTP client: {}", e)),
+                checked_at: now.to_rfc3339(),
+                response_time_ms: None,
+            };
+        }
+    };
+
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": {
+                "name": "mcp-nexus",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }
+    });
+
+    let mut request = client
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&initialize);
+
+    for (key, value) in headers {
+        // Unresolvable keychain references are sent as-is; the server will reject them
+        let value = resolve_keychain_reference(value).unwrap_or_else(|_| value.clone());
+        request = request.header(key.as_str(), value);
+    }
+
+    match request.send().await {
+        Ok(response) => {
+            let elapsed = start.elapsed().as_millis() as u64;
+            let status = response.status();
+
+            if status.is_success() {
+                HealthCheckResult {
+                    server_id: String::new(),
+                    status: HealthStatus::Healthy,
+                    message: Some(format!("HTTP {}", status.as_u16())),
+                    checked_at: now.to_rfc3339(),
+                    response_time_ms: Some(elapsed),
+                }
+            } else {
+                HealthCheckResult {
+                    server_id: String::new(),
+                    status: HealthStatus::Unhealthy,
+                    message: Some(format!("HTTP {} - {}", status.as_u16(), status.canonical_reason().unwrap_or("Unknown"))),
+                    checked_at: now.to_rfc3339(),
+                    response_time_ms: Some(elapsed),
+                }
+            }
+        }
+        Err(e) => {
+            let elapsed = start.elapsed().as_millis() as u64;
+            HealthCheckResult {
+                server_id: String::new(),
+                status: HealthStatus::Unhealthy,
+                message: Some(format!("Connection failed: {}", e)),
+                checked_at: now.to_rfc3339(),
+                response_time_ms: Some(elapsed),
+            }
+        }
+    }
+}
+
 /// Perform a health check on a stdio server by attempting to spawn and check for response
 pub async fn check_stdio_health(
     command: &str,
@@ -181,6 +272,7 @@ pub async fn check_stdio_health(
 pub async fn check_server_health(server: &McpServer, timeout_secs: u64) -> HealthCheckResult {
     let mut result = match &server.transport {
         Transport::Sse { url, .. } => check_sse_health(url, timeout_secs).await,
+        Transport::Http { url, headers } => check_http_health(url, headers, timeout_secs).await,
         Transport::Stdio { command, args, env } => {
             check_stdio_health(command, args, env, timeout_secs).await
         }
@@ -98,15 +98,29 @@ pub enum InstallSource {
         #[serde(default)]
         docker_args: Vec<String>,
     },
-    /// Remote SSE server (no local installation needed)
+    /// Remote server over SSE or Streamable HTTP (no local installation needed)
     Remote {
         url: String,
-        /// Headers to send with SSE requests
+        /// Headers to send with every request
         #[serde(default)]
         headers: HashMap<String, String>,
+        /// Wire protocol spoken by the server (defaults to SSE)
+        #[serde(default)]
+        protocol: RemoteProtocol,
     },
 }
 
+/// Wire protocol of a remote MCP server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteProtocol {
+    /// Legacy HTTP+SSE transport
+    #[default]
+    Sse,
+    /// Streamable HTTP transport
+    Http,
+}
+
 /// Result of server installation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -445,9 +459,19 @@ pub fn build_transport(source: &InstallSource, env: &HashMap<String, String>) ->
                 env: env.clone(),
             }
         }
-        InstallSource::Remote { url, headers } => Transport::Sse {
-            url: url.clone(),
-            headers: headers.clone(),
+        InstallSource::Remote {
+            url,
+            headers,
+            protocol,
+        } => match protocol {
+            RemoteProtocol::Sse => Transport::Sse {
+                url: url.clone(),
+                headers: headers.clone(),
+            },
+            RemoteProtocol::Http => Transport::Http {
+                url: url.clone(),
+                headers: headers.clone(),
+            },
         },
     }
 }
@@ -509,6 +533,8 @@ pub fn install_server(
         installed_version: extract_version(&request.source),
         source_url: request.source_url.clone(),
         tags: vec![],
+        client_overrides: HashMap::new(),
+        client_options: HashMap::new(),
     };
 
     // Enable for specified clients
@@ -674,6 +700,7 @@ mod tests {
         let source = InstallSource::Remote {
             url: "https://api.example.com/mcp".to_string(),
             headers: headers.clone(),
+            protocol: RemoteProtocol::Sse,
         };
         let env = HashMap::new();
 
@@ -688,6 +715,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_transport_remote_http() {
+        let source: InstallSource = serde_json::from_value(serde_json::json!({
+            "type": "remote",
+            "url": "https://api.example.com/mcp",
+            "protocol": "http"
+        }))
+        .unwrap();
+
+        let transport = build_transport(&source, &HashMap::new());
+
+        match transport {
+            Transport::Http { url, headers } => {
+                assert_eq!(url, "https://api.example.com/mcp");
+                assert!(headers.is_empty());
+            }
+            _ => panic!("Expected Http transport"),
+        }
+    }
+
     #[test]
     fn test_build_server_source() {
         let npm_source = InstallSource::Npm {
@@ -712,6 +759,7 @@ mod tests {
         let source = InstallSource::Remote {
             url: "https://api.example.com/mcp".to_string(),
             headers: HashMap::new(),
+            protocol: RemoteProtocol::default(),
         };
 
         let report = run_doctor();
@@ -725,6 +773,7 @@ mod tests {
         let source = InstallSource::Remote {
             url: "not-a-valid-url".to_string(),
             headers: HashMap::new(),
+            protocol: RemoteProtocol::default(),
         };
 
         let report = run_doctor();
@@ -0,0 +1,436 @@
+// src-tauri/src/services/jsonc.rs
+//! JSONC (JSON with comments) support for client config files
+//!
+//! Editors like VS Code and Zed keep their settings in JSONC: `//` and `/* */`
+//! comments plus trailing commas. [`parse`] accepts that dialect, and
+//! [`replace_at`] rewrites one subtree of a document in place so comments,
+//! key order and formatting everywhere else in the file are kept.
+
+use serde_json::Value;
+
+/// Blank out comments and trailing commas so the text is plain JSON.
+///
+/// Every removed byte becomes a space (newlines are kept), so byte offsets
+/// and line numbers match the original text.
+fn strip(input: &str) -> String {
+    let mut out = input.as_bytes().to_vec();
+    let len = out.len();
+    let mut i = 0;
+
+    // Pass 1: comments
+    while i < len {
+        match out[i] {
+            b'"' => i = skip_string(&out, i),
+            b'/' if i + 1 < len && out[i + 1] == b'/' => {
+                while i < len && out[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+            }
+            b'/' if i + 1 < len && out[i + 1] == b'*' => {
+                let mut j = i + 2;
+                while j < len && !(out[j] == b'*' && j + 1 < len && out[j + 1] == b'/') {
+                    j += 1;
+                }
+                let end = (j + 2).min(len);
+                for byte in &mut out[i..end] {
+                    if *byte != b'\n' && *byte != b'\r' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    // Pass 2: trailing commas (comments are already whitespace)
+    i = 0;
+    while i < len {
+        match out[i] {
+            b'"' => i = skip_string(&out, i),
+            b',' => {
+                let next = out[i + 1..].iter().find(|b| !b.is_ascii_whitespace());
+                if matches!(next, Some(b'}') | Some(b']')) {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    // Only ASCII bytes were replaced with ASCII, so the text is still valid UTF-8
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Index just past the string literal starting at `start`
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Parse a JSON or JSONC document
+pub fn parse(content: &str) -> Result<Value, serde_json::Error> {
+    serde_json::from_str(&strip(content))
+}
+
+/// Byte range of a member inside an object
+struct MemberSpan {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+/// Byte range and members of an object
+struct ObjectSpan {
+    close: usize,
+    members: Vec<MemberSpan>,
+}
+
+/// Minimal scanner over comment-free JSON that records where things are
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str, pos: usize) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Skip over one value, returning its byte range
+    fn skip_value(&mut self) -> Option<(usize, usize)> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        match self.peek()? {
+            b'"' => self.pos = skip_string(self.bytes, self.pos),
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                while self.pos < self.bytes.len() {
+                    match self.bytes[self.pos] {
+                        b'"' => {
+                            self.pos = skip_string(self.bytes, self.pos);
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.pos += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                if depth != 0 {
+                    return None;
+                }
+            }
+            _ => {
+                // Number, true, false or null
+                while self.pos < self.bytes.len()
+                    && !matches!(self.bytes[self.pos], b',' | b'}' | b']')
+                    && !self.bytes[self.pos].is_ascii_whitespace()
+                {
+                    self.pos += 1;
+                }
+            }
+        }
+
+        (self.pos > start).then_some((start, self.pos))
+    }
+
+    /// Scan the object whose opening brace is at the current position
+    fn scan_object(&mut self, text: &str) -> Option<ObjectSpan> {
+        self.skip_whitespace();
+        if self.peek()? != b'{' {
+            return None;
+        }
+        self.pos += 1;
+
+        let mut members = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                b'}' => {
+                    return Some(ObjectSpan {
+                        close: self.pos,
+                        members,
+                    })
+                }
+                b',' => self.pos += 1,
+                b'"' => {
+                    let key_start = self.pos;
+                    self.pos = skip_string(self.bytes, self.pos);
+                    let key: String = serde_json::from_str(&text[key_start..self.pos]).ok()?;
+
+                    self.skip_whitespace();
+                    if self.peek()? != b':' {
+                        return None;
+                    }
+                    self.pos += 1;
+
+                    let (value_start, value_end) = self.skip_value()?;
+                    members.push(MemberSpan {
+                        key,
+                        key_start,
+                        value_start,
+                        value_end,
+                    });
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// Leading whitespace of the line containing `pos`
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line_start = text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = &text[line_start..];
+    let width = line.len() - line.trim_start_matches([' ', '\t']).len();
+    &line[..width]
+}
+
+/// Indentation unit used by the document (defaults to two spaces)
+fn indent_unit(text: &str) -> String {
+    text.lines()
+        .find_map(|line| {
+            let trimmed = line.trim_start_matches([' ', '\t']);
+            let width = line.len() - trimmed.len();
+            (width > 0 && !trimmed.is_empty()).then(|| line[..width].to_string())
+        })
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+/// Pretty-print `value` using `unit` for each level, continuing lines at `base`
+fn render(value: &Value, unit: &str, base: &str) -> Option<String> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    serde::Serialize::serialize(value, &mut serializer).ok()?;
+
+    let pretty = String::from_utf8(buf).ok()?;
+    Some(pretty.replace('\n', &format!("\n{}", base)))
+}
+
+/// Wrap `value` in nested objects for each key in `path`
+fn wrap(path: &[String], value: &Value) -> Value {
+    path.iter().rev().fold(value.clone(), |inner, key| {
+        let mut map = serde_json::Map::new();
+        map.insert(key.clone(), inner);
+        Value::Object(map)
+    })
+}
+
+/// Apply `(position, removed length, inserted text)` edits, highest offset first
+fn apply_edits(text: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|(pos, _, _)| std::cmp::Reverse(*pos));
+
+    let mut out = text.to_string();
+    for (pos, len, insert) in edits {
+        out.replace_range(pos..pos + len, &insert);
+    }
+    out
+}
+
+/// Set the value at `pointer` (a list of object keys), touching nothing else.
+///
+/// Missing objects along the path are created. Returns `None` if the document
+/// cannot be parsed or its root is not an object, in which case the caller
+/// should fall back to writing the whole document.
+pub fn replace_at(content: &str, pointer: &[String], value: &Value) -> Option<String> {
+    let stripped = strip(content);
+    // Make sure the document is valid before editing it
+    serde_json::from_str::<Value>(&stripped).ok()?;
+
+    let unit = indent_unit(content);
+    let mut scanner = Scanner::new(&stripped, 0);
+    scanner.skip_whitespace();
+    let mut object_start = scanner.pos;
+
+    for (depth, key) in pointer.iter().enumerate() {
+        let object = Scanner::new(&stripped, object_start).scan_object(&stripped)?;
+        let rest = &pointer[depth + 1..];
+
+        match object.members.iter().find(|m| &m.key == key) {
+            // Keep descending through existing objects
+            Some(member) if !rest.is_empty() && stripped.as_bytes()[member.value_start] == b'{' => {
+                object_start = member.value_start;
+            }
+            // Replace the existing value (or the non-object in the way)
+            Some(member) => {
+                let base = line_indent(content, member.key_start);
+                let rendered = render(&wrap(rest, value), &unit, base)?;
+                let len = member.value_end - member.value_start;
+                return Some(apply_edits(content, vec![(member.value_start, len, rendered)]));
+            }
+            // Insert a new member at the end of this object
+            None => {
+                let outer = line_indent(content, object_start);
+                let indent = match object.members.first() {
+                    Some(first) if content[object_start..first.key_start].contains('\n') => {
+                        line_indent(content, first.key_start).to_string()
+                    }
+                    _ => format!("{}{}", outer, unit),
+                };
+
+                let rendered = render(&wrap(rest, value), &unit, &indent)?;
+                let key_json = serde_json::to_string(key).ok()?;
+                let mut edits = Vec::new();
+
+                if let Some(last) = object.members.last() {
+                    let mut after = Scanner::new(&stripped, last.value_end);
+                    after.skip_whitespace();
+                    if after.peek() != Some(b',') {
+                        edits.push((last.value_end, 0, ",".to_string()));
+                    }
+                }
+
+                // After the last thing in the object, including any trailing comment
+                let insert_at = object_start
+                    + 1
+                    + content[object_start + 1..object.close].trim_end().len();
+                let mut member = format!("\n{}{}: {}", indent, key_json, rendered);
+                if !content[insert_at..object.close].contains('\n') {
+                    member.push('\n');
+                    member.push_str(outer);
+                }
+                edits.push((insert_at, 0, member));
+
+                return Some(apply_edits(content, edits));
+            }
+        }
+    }
+
+    // Empty pointer: replace the whole root value
+    let (start, end) = Scanner::new(&stripped, object_start).skip_value()?;
+    let rendered = render(value, &unit, "")?;
+    Some(apply_edits(content, vec![(start, end - start, rendered)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pointer(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_jsonc() {
+        let content = r#"{
+            // Line comment with "quotes"
+            "editor.fontSize": 14, /* block */
+            "url": "https://example.com/*not-a-comment*/",
+            "list": [1, 2, 3,],
+        }"#;
+
+        let value = parse(content).unwrap();
+        assert_eq!(value["editor.fontSize"], 14);
+        assert_eq!(value["url"], "https://example.com/*not-a-comment*/");
+        assert_eq!(value["list"], json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_parse_error_keeps_line_numbers() {
+        let content = "{\n  // comment\n  \"a\": 1,\n  \"b\": oops\n}";
+        let err = parse(content).unwrap_err();
+        assert_eq!(err.line(), 4);
+    }
+
+    #[test]
+    fn test_replace_existing_subtree_preserves_rest() {
+        let content = r#"{
+    // Editor settings
+    "editor.fontSize": 14,
+    "mcp": {
+        /* managed by MCP Nexus */
+        "servers": {
+            "old": {"command": "old"}
+        }
+    },
+    "zeta": true, // trailing comment
+}
+"#;
+
+        let servers = json!({"new": {"command": "npx"}});
+        let patched = replace_at(content, &pointer(&["mcp", "servers"]), &servers).unwrap();
+
+        assert!(patched.contains("// Editor settings"));
+        assert!(patched.contains("/* managed by MCP Nexus */"));
+        assert!(patched.contains("\"zeta\": true, // trailing comment"));
+        // Original key order outside the subtree is kept
+        assert!(patched.find("editor.fontSize").unwrap() < patched.find("\"zeta\"").unwrap());
+
+        let value = parse(&patched).unwrap();
+        assert_eq!(value["mcp"]["servers"], servers);
+        assert_eq!(value["editor.fontSize"], 14);
+
+        // The new subtree uses the file's own four-space indentation
+        assert!(patched.contains("        \"servers\": {\n            \"new\": {\n"));
+    }
+
+    #[test]
+    fn test_replace_inserts_missing_path() {
+        let content = "{\n  \"theme\": \"dark\" // keep me\n}\n";
+
+        let servers = json!({"fs": {"command": "npx"}});
+        let patched = replace_at(content, &pointer(&["mcp", "servers"]), &servers).unwrap();
+
+        assert!(patched.contains("\"theme\": \"dark\", // keep me"));
+        let value = parse(&patched).unwrap();
+        assert_eq!(value["theme"], "dark");
+        assert_eq!(value["mcp"]["servers"], servers);
+    }
+
+    #[test]
+    fn test_replace_inserts_into_empty_object() {
+        let patched = replace_at("{}", &pointer(&["mcpServers"]), &json!({})).unwrap();
+        assert_eq!(patched, "{\n  \"mcpServers\": {}\n}");
+
+        let patched = replace_at("{\n  // nothing yet\n}", &pointer(&["mcpServers"]), &json!({}))
+            .unwrap();
+        assert!(patched.contains("// nothing yet"));
+        assert_eq!(parse(&patched).unwrap(), json!({"mcpServers": {}}));
+    }
+
+    #[test]
+    fn test_replace_non_object_in_path() {
+        let content = "{\n  \"mcp\": null\n}";
+        let patched = replace_at(content, &pointer(&["mcp", "servers"]), &json!({})).unwrap();
+        assert_eq!(parse(&patched).unwrap(), json!({"mcp": {"servers": {}}}));
+    }
+
+    #[test]
+    fn test_replace_rejects_invalid_documents() {
+        assert!(replace_at("[1, 2]", &pointer(&["a"]), &json!(1)).is_none());
+        assert!(replace_at("{ not json", &pointer(&["a"]), &json!(1)).is_none());
+    }
+}
@@ -1,28 +1,48 @@
+mod app_log;
+mod auto_sync;
+pub mod backups;
+mod client_descriptors;
 mod client_detector;
+mod config_journal;
 mod config_manager;
+mod config_watcher;
 mod doctor;
 pub mod health;
 pub mod installation;
+mod jsonc;
 pub mod keychain;
 mod marketplace_client;
+mod server_validation;
 pub mod sync_engine;
+mod toml_config;
 mod updates;
+mod yaml_config;
 
+pub use app_log::log_warning;
+pub use auto_sync::{
+    affected_clients, auto_sync_clients, AutoSyncProgress, AutoSyncScheduler, AUTO_SYNC_DEBOUNCE,
+    AUTO_SYNC_EVENT,
+};
+pub use client_descriptors::{reload_custom_clients, DescriptorLoadError, DescriptorLoadResult};
 pub use client_detector::{
-    detect_all_clients, detect_client, get_client_config_info, get_client_config_path,
+    detect_all_clients, detect_client, detect_project_targets, get_client_config_candidates,
+    get_client_config_info, get_client_config_path, get_project_config_path,
 };
+pub use config_journal::{JournalAction, JournalSummary};
 pub use config_manager::{ConfigError, ConfigManager};
+pub use config_watcher::{watch_targets, ConfigChangeEvent, ConfigWatcher, CONFIG_CHANGED_EVENT};
 pub use doctor::run_doctor;
 pub use health::{check_server_health, HealthCheckResult, HealthStatus};
 pub use installation::{
     cleanup_server, install_server, validate_runtime, InstallResult, InstallServerRequest,
-    InstallSource, InstallationError,
+    InstallSource, InstallationError, RemoteProtocol,
 };
 pub use keychain::{
     credential_exists, delete_credential, extract_credential_name, get_credential,
     is_keychain_reference, list_credentials, store_credential, StoreCredentialResult,
 };
 pub use marketplace_client::MarketplaceClient;
+pub use server_validation::{has_errors, validate_server, validate_server_with};
 pub use updates::{
     check_for_updates_detailed, check_npm_version, check_pypi_version, is_newer_version,
     ServerUpdate, UpdateCheckResult,
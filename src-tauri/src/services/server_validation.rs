@@ -0,0 +1,402 @@
+// src-tauri/src/services/server_validation.rs
+//! Checking server definitions before they are saved
+//!
+//! [`validate_server`] returns one [`ServerDiagnostic`] per problem, keyed by
+//! the field it concerns so the editor can show it next to the input. Errors
+//! block saving; warnings are advice.
+
+use crate::models::{McpServer, ServerDiagnostic, ServerSource, Transport};
+use crate::services::keychain::{credential_exists, extract_credential_name, is_keychain_reference};
+use reqwest::header::{HeaderName, HeaderValue};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Env var and header names that usually hold secrets
+const SECRET_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "AUTHORIZATION"];
+
+/// Validate `server` against the other configured servers, checking keychain
+/// references against the stored credentials
+pub fn validate_server(server: &McpServer, others: &[McpServer]) -> Vec<ServerDiagnostic> {
+    validate_server_with(server, others, |name| {
+        credential_exists(name).map_err(|e| e.to_string())
+    })
+}
+
+/// Validate `server`, asking `credential_exists` whether a keychain credential is stored.
+///
+/// `others` may include `server` itself; entries with the same ID are skipped.
+pub fn validate_server_with<F>(
+    server: &McpServer,
+    others: &[McpServer],
+    credential_exists: F,
+) -> Vec<ServerDiagnostic>
+where
+    F: Fn(&str) -> Result<bool, String>,
+{
+    let mut diagnostics = Vec::new();
+
+    let name = server.name.trim();
+    if name.is_empty() {
+        diagnostics.push(ServerDiagnostic::error("name", "Name cannot be empty"));
+    } else if others.iter().any(|s| s.id != server.id && s.name.trim() == name) {
+        // Client configs key entries by name, so duplicates overwrite each other,
+        // and names differing only in surrounding spaces are too easily confused
+        diagnostics.push(ServerDiagnostic::error(
+            "name",
+            format!("Another server is already named '{}'", server.name),
+        ));
+    }
+
+    // Only informational: servers run from the transport, and imports record
+    // the client's command line here (e.g. `node server.js`)
+    if let ServerSource::Local { path } = &server.source {
+        if !path.trim().is_empty() && !Path::new(path).is_absolute() {
+            diagnostics.push(ServerDiagnostic::warning(
+                "source.path",
+                format!("Path is not absolute: {}", path),
+            ));
+        }
+    }
+
+    match &server.transport {
+        Transport::Stdio { command, env, .. } => {
+            if command.trim().is_empty() {
+                diagnostics.push(ServerDiagnostic::error(
+                    "transport.command",
+                    "Command cannot be empty",
+                ));
+            } else if is_relative_path(command) {
+                // Clients start servers from their own working directory
+                diagnostics.push(ServerDiagnostic::error(
+                    "transport.command",
+                    format!("Command path must be absolute or a bare program name: {}", command),
+                ));
+            }
+            check_env(&mut diagnostics, "transport.env", env, &credential_exists);
+        }
+        Transport::Sse { url, headers } | Transport::Http { url, headers } => {
+            check_url(&mut diagnostics, url, &credential_exists);
+            check_headers(&mut diagnostics, "transport.headers", headers, &credential_exists);
+        }
+    }
+
+    let mut clients: Vec<_> = server.client_overrides.iter().collect();
+    clients.sort_by(|a, b| a.0.cmp(b.0));
+    for (client_id, client_override) in clients {
+        let prefix = format!("clientOverrides.{}", client_id);
+        check_env(
+            &mut diagnostics,
+            &format!("{}.env", prefix),
+            &client_override.env,
+            &credential_exists,
+        );
+        check_headers(
+            &mut diagnostics,
+            &format!("{}.headers", prefix),
+            &client_override.headers,
+            &credential_exists,
+        );
+    }
+
+    diagnostics
+}
+
+/// Whether any diagnostic blocks saving
+pub fn has_errors(diagnostics: &[ServerDiagnostic]) -> bool {
+    diagnostics.iter().any(ServerDiagnostic::is_error)
+}
+
+/// A command with a directory part that is not absolute, e.g. `./server` or `bin/server`
+fn is_relative_path(command: &str) -> bool {
+    (command.contains('/') || command.contains('\\')) && !Path::new(command).is_absolute()
+}
+
+/// Whether a variable or header name suggests a secret value
+fn looks_secret(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SECRET_MARKERS.iter().any(|marker| upper.contains(marker))
+}
+
+/// Env var names must be usable from a shell: letters, digits and underscores
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn sorted(values: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut values: Vec<_> = values.iter().collect();
+    values.sort_by(|a, b| a.0.cmp(b.0));
+    values
+}
+
+/// Check a value that is or may be a keychain reference
+fn check_value<F>(
+    diagnostics: &mut Vec<ServerDiagnostic>,
+    field: &str,
+    name: &str,
+    value: &str,
+    credential_exists: &F,
+) where
+    F: Fn(&str) -> Result<bool, String>,
+{
+    if is_keychain_reference(value) {
+        let Some(credential) = extract_credential_name(value) else {
+            return;
+        };
+        match credential_exists(&credential) {
+            Ok(true) => {}
+            Ok(false) => diagnostics.push(ServerDiagnostic::error(
+                field,
+                format!("Keychain credential not found: {}", credential),
+            )),
+            Err(e) => diagnostics.push(ServerDiagnostic::warning(
+                field,
+                format!("Could not check keychain credential '{}': {}", credential, e),
+            )),
+        }
+    } else if looks_secret(name) && !value.is_empty() {
+        diagnostics.push(ServerDiagnostic::warning(
+            field,
+            "Looks like a secret stored in plain text; consider a keychain reference",
+        ));
+    }
+}
+
+fn check_env<F>(
+    diagnostics: &mut Vec<ServerDiagnostic>,
+    prefix: &str,
+    env: &HashMap<String, String>,
+    credential_exists: &F,
+) where
+    F: Fn(&str) -> Result<bool, String>,
+{
+    for (name, value) in sorted(env) {
+        let field = format!("{}.{}", prefix, name);
+        if !is_valid_env_name(name) {
+            diagnostics.push(ServerDiagnostic::error(
+                &field,
+                format!("Invalid environment variable name: '{}'", name),
+            ));
+        }
+        check_value(diagnostics, &field, name, value, credential_exists);
+    }
+}
+
+fn check_headers<F>(
+    diagnostics: &mut Vec<ServerDiagnostic>,
+    prefix: &str,
+    headers: &HashMap<String, String>,
+    credential_exists: &F,
+) where
+    F: Fn(&str) -> Result<bool, String>,
+{
+    for (name, value) in sorted(headers) {
+        let field = format!("{}.{}", prefix, name);
+        if HeaderName::from_bytes(name.as_bytes()).is_err() {
+            diagnostics.push(ServerDiagnostic::error(
+                &field,
+                format!("Invalid header name: '{}'", name),
+            ));
+        }
+        if HeaderValue::from_str(value).is_err() {
+            diagnostics.push(ServerDiagnostic::error(
+                &field,
+                "Header value contains characters not allowed in HTTP headers",
+            ));
+        }
+        check_value(diagnostics, &field, name, value, credential_exists);
+    }
+}
+
+fn check_url<F>(diagnostics: &mut Vec<ServerDiagnostic>, url: &str, credential_exists: &F)
+where
+    F: Fn(&str) -> Result<bool, String>,
+{
+    const FIELD: &str = "transport.url";
+
+    if is_keychain_reference(url) {
+        check_value(diagnostics, FIELD, "url", url, credential_exists);
+        return;
+    }
+
+    let parsed = match reqwest::Url::parse(url.trim()) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            diagnostics.push(ServerDiagnostic::error(FIELD, format!("Invalid URL: {}", e)));
+            return;
+        }
+    };
+
+    match parsed.scheme() {
+        "https" => {}
+        "http" => {
+            let local = matches!(
+                parsed.host_str(),
+                Some("localhost" | "127.0.0.1" | "[::1]" | "::1")
+            );
+            if !local {
+                diagnostics.push(ServerDiagnostic::warning(
+                    FIELD,
+                    "Plain HTTP sends requests unencrypted; use https for remote hosts",
+                ));
+            }
+        }
+        scheme => diagnostics.push(ServerDiagnostic::error(
+            FIELD,
+            format!("URL must use http or https, not '{}'", scheme),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ClientOverride, IssueSeverity};
+
+    fn stdio(name: &str, command: &str, env: &[(&str, &str)]) -> McpServer {
+        McpServer::new(
+            name.to_string(),
+            ServerSource::Local {
+                path: "/opt/servers/fs".to_string(),
+            },
+            Transport::Stdio {
+                command: command.to_string(),
+                args: vec![],
+                env: env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            },
+        )
+    }
+
+    fn remote(url: &str, headers: &[(&str, &str)]) -> McpServer {
+        McpServer::new(
+            "remote".to_string(),
+            ServerSource::Remote {
+                url: url.to_string(),
+            },
+            Transport::Http {
+                url: url.to_string(),
+                headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            },
+        )
+    }
+
+    fn stored(name: &str) -> Result<bool, String> {
+        Ok(name == "github-token")
+    }
+
+    fn fields(diagnostics: &[ServerDiagnostic], severity: IssueSeverity) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .map(|d| d.field.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_server_has_no_diagnostics() {
+        let server = stdio("fs", "npx", &[("GITHUB_TOKEN", "keychain:github-token")]);
+        assert!(validate_server_with(&server, &[], stored).is_empty());
+
+        let headers = [("Authorization", "keychain:github-token")];
+        let server = remote("https://example.com/mcp", &headers);
+        assert!(validate_server_with(&server, &[], stored).is_empty());
+    }
+
+    #[test]
+    fn test_name_must_be_present_and_unique() {
+        let existing = stdio("fs", "npx", &[]);
+        let duplicate = stdio("fs", "npx", &[]);
+        let saved = std::slice::from_ref(&existing);
+        let diagnostics = validate_server_with(&duplicate, saved, stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Error), vec!["name"]);
+
+        // Updating a server does not clash with its own saved copy
+        assert!(validate_server_with(&existing, saved, stored).is_empty());
+
+        let padded = stdio(" fs ", "npx", &[]);
+        let diagnostics = validate_server_with(&padded, saved, stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Error), vec!["name"]);
+
+        let unnamed = stdio("  ", "npx", &[]);
+        let diagnostics = validate_server_with(&unnamed, &[], stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Error), vec!["name"]);
+    }
+
+    #[test]
+    fn test_stdio_fields() {
+        let mut server = stdio(
+            "fs",
+            "./bin/server",
+            &[("1BAD", "x"), ("API_KEY", "sk-123"), ("MISSING", "keychain:nope")],
+        );
+        server.source = ServerSource::Local {
+            path: "servers/fs".to_string(),
+        };
+        server.set_client_override(
+            "cursor",
+            Some(ClientOverride {
+                env: HashMap::from([("BAD-NAME".to_string(), "1".to_string())]),
+                ..Default::default()
+            }),
+        );
+
+        let diagnostics = validate_server_with(&server, &[], stored);
+        assert_eq!(
+            fields(&diagnostics, IssueSeverity::Error),
+            vec![
+                "transport.command",
+                "transport.env.1BAD",
+                "transport.env.MISSING",
+                "clientOverrides.cursor.env.BAD-NAME",
+            ]
+        );
+        assert_eq!(
+            fields(&diagnostics, IssueSeverity::Warning),
+            vec!["source.path", "transport.env.API_KEY"]
+        );
+        assert!(has_errors(&diagnostics));
+    }
+
+    #[test]
+    fn test_remote_fields() {
+        let diagnostics = validate_server_with(&remote("not a url", &[]), &[], stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Error), vec!["transport.url"]);
+
+        let diagnostics = validate_server_with(&remote("ftp://example.com", &[]), &[], stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Error), vec!["transport.url"]);
+
+        // Plain HTTP is fine locally but flagged for remote hosts
+        let local = remote("http://localhost:8080/mcp", &[]);
+        assert!(validate_server_with(&local, &[], stored).is_empty());
+        let diagnostics = validate_server_with(&remote("http://example.com", &[]), &[], stored);
+        assert_eq!(fields(&diagnostics, IssueSeverity::Warning), vec!["transport.url"]);
+        assert!(!has_errors(&diagnostics));
+
+        let server = remote(
+            "https://example.com",
+            &[("Bad Header", "x"), ("X-Trace", "a\nb"), ("Authorization", "Bearer abc")],
+        );
+        let diagnostics = validate_server_with(&server, &[], stored);
+        assert_eq!(
+            fields(&diagnostics, IssueSeverity::Error),
+            vec!["transport.headers.Bad Header", "transport.headers.X-Trace"]
+        );
+        assert_eq!(
+            fields(&diagnostics, IssueSeverity::Warning),
+            vec!["transport.headers.Authorization"]
+        );
+    }
+
+    #[test]
+    fn test_keychain_errors_are_warnings() {
+        let server = stdio("fs", "npx", &[("TOKEN", "${keychain:github-token}")]);
+        let diagnostics =
+            validate_server_with(&server, &[], |_| Err("keychain locked".to_string()));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, IssueSeverity::Warning);
+        assert_eq!(diagnostics[0].field, "transport.env.TOKEN");
+    }
+}
@@ -1,15 +1,25 @@
 use crate::models::{
-    ClientId, ClientSettings, ConfigFormat, McpHubConfig, McpServer, ServerSource, SyncMode,
-    Transport,
+    custom_client, ClientCapabilities, ClientDescriptor, ClientId, ClientOptions, ClientSettings,
+    ClientSyncStatus, ConfigFormat, DetectedClient, FieldMapping, McpHubConfig, McpServer,
+    ProjectSettings, ProjectTarget, ServerSource, SyncMode, Transport,
 };
-use crate::services::client_detector::{detect_client, get_client_config_path};
+use crate::services::backups::{
+    client_backup_dir, create_snapshot, list_snapshots, project_backup_dir, snapshot_path,
+    BackupEntry, RestoreResult,
+};
+use crate::services::client_detector::{
+    config_format_for_path, detect_client, get_client_config_path, get_project_config_path,
+};
+use crate::services::config_watcher::note_own_write;
+use crate::services::{jsonc, toml_config, yaml_config};
 use crate::services::keychain::{is_keychain_reference, resolve_keychain_reference};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -39,6 +49,8 @@ pub enum SyncError {
     JsonError(#[from] serde_json::Error),
     #[error("Failed to resolve credential: {0}")]
     CredentialError(String),
+    #[error("Project not registered: {0}")]
+    ProjectNotFound(String),
 }
 
 /// Result of syncing to a single client
@@ -51,6 +63,12 @@ pub struct ClientSyncResult {
     pub backup_path: Option<String>,
     pub error: Option<String>,
     pub manual_config: Option<String>,
+    /// Servers that were bridged or skipped because the client cannot run them as-is
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Names of the server entries written to the client's config
+    #[serde(default)]
+    pub synced_servers: Vec<String>,
 }
 
 /// Result of syncing to all clients
@@ -62,6 +80,28 @@ pub struct SyncResult {
     pub failed: usize,
     pub manual_required: usize,
     pub results: Vec<ClientSyncResult>,
+    /// Whether a transactional sync failed and restored already-written clients
+    #[serde(default)]
+    pub rolled_back: bool,
+    /// Clients that could not be restored during a rollback
+    #[serde(default)]
+    pub rollback_errors: Vec<String>,
+    /// Project-level configs synced alongside the clients
+    #[serde(default)]
+    pub project_results: Vec<ProjectSyncResult>,
+}
+
+/// Result of syncing one project-level config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSyncResult {
+    pub project_path: String,
+    pub target: ProjectTarget,
+    pub success: bool,
+    pub servers_synced: usize,
+    pub config_path: Option<String>,
+    pub backup_path: Option<String>,
+    pub error: Option<String>,
 }
 
 /// Result of importing from a client
@@ -74,6 +114,101 @@ pub struct ImportResult {
     pub skipped_existing: usize,
 }
 
+/// Before/after values of a single changed field in a server entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Key-level changes to a string map field such as `env` or `headers`.
+/// Values are deliberately omitted so a preview never exposes credentials.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl KeyChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Field-level diff of a server present both on disk and in the central config
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerChange {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<FieldChange>,
+    /// Remote protocol change between SSE and Streamable HTTP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<KeyChanges>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<KeyChanges>,
+}
+
+/// Preview of what a sync would change in a single client's config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientSyncPreview {
+    pub client_id: ClientId,
+    pub config_path: Option<String>,
+    /// Servers that would be written but are not on disk yet
+    pub added: Vec<String>,
+    /// Servers on disk that the sync would drop
+    pub removed: Vec<String>,
+    /// Servers present on both sides whose definition differs
+    pub changed: Vec<ServerChange>,
+    /// Servers present on both sides with identical definitions
+    pub unchanged: Vec<String>,
+    pub error: Option<String>,
+    pub manual_config: Option<String>,
+    /// Servers that would be bridged or skipped because the client cannot run them as-is
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl ClientSyncPreview {
+    fn new(client_id: ClientId) -> Self {
+        Self {
+            client_id,
+            config_path: None,
+            added: vec![],
+            removed: vec![],
+            changed: vec![],
+            unchanged: vec![],
+            error: None,
+            manual_config: None,
+            warnings: vec![],
+        }
+    }
+
+    /// Whether syncing this client would modify its server list
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+/// Preview of a sync across all enabled clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPreview {
+    pub total_clients: usize,
+    pub clients_with_changes: usize,
+    pub results: Vec<ClientSyncPreview>,
+}
+
 /// Options for transforming server config
 #[derive(Debug, Clone, Default)]
 pub struct TransformOptions {
@@ -81,16 +216,254 @@ pub struct TransformOptions {
     /// Set to true when syncing to client configs that will run servers
     /// Set to false when generating display output (like Warp manual config)
     pub resolve_credentials: bool,
+    /// Client the output is generated for; decides client-specific key names
+    /// such as Windsurf's `serverUrl`. `None` emits the generic form.
+    pub client: Option<ClientId>,
+    /// Option keys Nexus wrote for each server at the client's last sync,
+    /// see [`ClientSettings::option_keys`]
+    pub written_option_keys: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// How a client spells a remote server entry
+struct RemoteEntryStyle {
+    /// Key holding the server URL
+    url_key: &'static str,
+    /// Value of the `type` key for Streamable HTTP servers, if the client uses one
+    http_type: Option<&'static str>,
+    /// Key holding the URL of Streamable HTTP servers, if it differs from `url_key`
+    http_url_key: Option<&'static str>,
+}
+
+/// Remote entry conventions for each client
+fn remote_entry_style(client: Option<ClientId>) -> RemoteEntryStyle {
+    match client {
+        // Windsurf uses `serverUrl` and infers the protocol
+        Some(ClientId::Windsurf) => RemoteEntryStyle {
+            url_key: "serverUrl",
+            http_type: None,
+            http_url_key: None,
+        },
+        // Cursor infers Streamable HTTP from a bare `url`
+        Some(ClientId::Cursor) => RemoteEntryStyle {
+            url_key: "url",
+            http_type: None,
+            http_url_key: None,
+        },
+        Some(ClientId::Cline) => RemoteEntryStyle {
+            url_key: "url",
+            http_type: Some("streamableHttp"),
+            http_url_key: None,
+        },
+        Some(ClientId::Continue) | Some(ClientId::RooCode) => RemoteEntryStyle {
+            url_key: "url",
+            http_type: Some("streamable-http"),
+            http_url_key: None,
+        },
+        // Goose names the URL `uri` and spells out the transport in `type`
+        Some(ClientId::Goose) => RemoteEntryStyle {
+            url_key: "uri",
+            http_type: Some("streamable_http"),
+            http_url_key: None,
+        },
+        // Gemini CLI tells the protocols apart by key: `url` for SSE, `httpUrl` for HTTP
+        Some(ClientId::GeminiCli) => RemoteEntryStyle {
+            url_key: "url",
+            http_type: None,
+            http_url_key: Some("httpUrl"),
+        },
+        // Codex and Zed only speak Streamable HTTP to remotes and have no `type` key
+        Some(ClientId::Codex) | Some(ClientId::Zed) => RemoteEntryStyle {
+            url_key: "url",
+            http_type: None,
+            http_url_key: None,
+        },
+        // Claude Code, Claude Desktop, VS Code, Amazon Q and the generic form
+        _ => RemoteEntryStyle {
+            url_key: "url",
+            http_type: Some("http"),
+            http_url_key: None,
+        },
+    }
+}
+
+/// Resolve a single value if it is a keychain reference and resolution is enabled
+fn resolve_value(value: &str, options: &TransformOptions, label: &str) -> Result<String, SyncError> {
+    if options.resolve_credentials && is_keychain_reference(value) {
+        resolve_keychain_reference(value)
+            .map_err(|e| SyncError::CredentialError(format!("Failed to resolve {}: {}", label, e)))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Keys a client uses for [`ClientOptions`] (`None` where it has no equivalent)
+#[derive(Default)]
+struct ClientOptionKeys {
+    disabled: Option<&'static str>,
+    auto_approve: Option<&'static str>,
+    disabled_tools: Option<&'static str>,
+    timeout: Option<&'static str>,
+}
+
+/// Entry option keys for each client
+fn client_option_keys(client: Option<ClientId>) -> ClientOptionKeys {
+    match client {
+        Some(ClientId::Cline) => ClientOptionKeys {
+            disabled: Some("disabled"),
+            auto_approve: Some("autoApprove"),
+            disabled_tools: None,
+            timeout: Some("timeout"),
+        },
+        Some(ClientId::RooCode) => ClientOptionKeys {
+            disabled: Some("disabled"),
+            auto_approve: Some("alwaysAllow"),
+            disabled_tools: None,
+            timeout: Some("timeout"),
+        },
+        Some(ClientId::Windsurf) => ClientOptionKeys {
+            disabled: Some("disabled"),
+            disabled_tools: Some("disabledTools"),
+            ..Default::default()
+        },
+        _ => ClientOptionKeys::default(),
+    }
+}
+
+impl ClientOptionKeys {
+    /// Every key this client understands
+    fn keys(&self) -> impl Iterator<Item = &'static str> {
+        [self.disabled, self.auto_approve, self.disabled_tools, self.timeout]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Keys the given options set a value for
+    fn set_keys(&self, options: &ClientOptions) -> Vec<String> {
+        let set = [
+            (self.disabled, options.disabled.is_some()),
+            (self.auto_approve, options.auto_approve.is_some()),
+            (self.disabled_tools, options.disabled_tools.is_some()),
+            (self.timeout, options.timeout.is_some()),
+        ];
+        set.into_iter()
+            .filter_map(|(key, is_set)| key.filter(|_| is_set).map(str::to_string))
+            .collect()
+    }
+
+    /// Write the options the client has keys for into an entry
+    fn write(&self, options: &ClientOptions, entry: &mut Map<String, Value>) {
+        let values = [
+            (self.disabled, options.disabled.map(|v| json!(v))),
+            (self.auto_approve, options.auto_approve.as_ref().map(|v| json!(v))),
+            (self.disabled_tools, options.disabled_tools.as_ref().map(|v| json!(v))),
+            (self.timeout, options.timeout.map(|v| json!(v))),
+        ];
+        for (key, value) in values {
+            if let (Some(key), Some(value)) = (key, value) {
+                entry.insert(key.to_string(), value);
+            }
+        }
+    }
+
+    /// Read the client's options from an entry
+    fn read(&self, entry: &Value) -> ClientOptions {
+        let get = |key: Option<&str>| key.and_then(|k| entry.get(k));
+        let list = |key: Option<&str>| {
+            get(key).and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+        };
+
+        ClientOptions {
+            disabled: get(self.disabled).and_then(Value::as_bool),
+            auto_approve: list(self.auto_approve),
+            disabled_tools: list(self.disabled_tools),
+            timeout: get(self.timeout).and_then(Value::as_u64),
+        }
+    }
+}
+
+/// Keep options set in the client wherever Nexus leaves them unset.
+///
+/// Merged per key: a value set in Nexus wins, and a key Nexus wrote at the last
+/// sync but no longer sets was cleared in Nexus, so it is dropped. Any other
+/// option key keeps the client's value; otherwise a sync would erase e.g.
+/// Cline's `autoApprove` list.
+///
+/// Only clients with [`ClientOptionKeys`] have options to keep, and all of them
+/// use the standard format, so the other format writers do not call this.
+fn carry_over_client_options(
+    managed: &mut Map<String, Value>,
+    existing: Option<&Value>,
+    servers: &[&McpServer],
+    options: &TransformOptions,
+) {
+    let (Some(existing), Some(client)) = (existing, options.client) else {
+        return;
+    };
+    let keys = client_option_keys(Some(client));
+
+    for server in servers {
+        let (Some(previous), Some(Value::Object(entry))) =
+            (existing.get(&server.name), managed.get_mut(&server.name))
+        else {
+            continue;
+        };
+        let written = options
+            .written_option_keys
+            .as_ref()
+            .and_then(|written| written.get(&server.name));
+        for key in keys.keys() {
+            // Keys already in the entry were written from Nexus options
+            if written.is_some_and(|w| w.iter().any(|k| k == key)) {
+                continue;
+            }
+            if let Some(value) = previous.get(key) {
+                entry.entry(key).or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
+
+/// Build the `url`/`headers` part of a remote server entry
+fn remote_entry(
+    url: &str,
+    headers: &HashMap<String, String>,
+    url_key: &str,
+    options: &TransformOptions,
+) -> Result<Map<String, Value>, SyncError> {
+    let mut obj = Map::new();
+
+    // Resolve URL if it's a keychain reference (rare but possible)
+    obj.insert(url_key.to_string(), json!(resolve_value(url, options, "URL")?));
+
+    if !headers.is_empty() {
+        let mut resolved_headers: HashMap<String, String> = HashMap::new();
+
+        for (k, v) in headers {
+            let label = format!("header '{}'", k);
+            resolved_headers.insert(k.clone(), resolve_value(v, options, &label)?);
+        }
+
+        obj.insert("headers".to_string(), json!(resolved_headers));
+    }
+
+    Ok(obj)
 }
 
 /// Transform a server to the standard MCP config format
 /// Output: {"command": "...", "args": [...], "env": {...}} for stdio
 /// Output: {"url": "...", "transport": "sse"} for SSE
+/// Output: {"url": "...", "type": "http"} for Streamable HTTP (spelling varies by client)
 ///
 /// If `resolve_credentials` is true, keychain references in env vars will be
 /// resolved to actual values. This is used when syncing to client configs.
+/// The server's override for `options.client`, if any, is applied first.
 fn transform_server_to_standard(server: &McpServer, options: &TransformOptions) -> Result<Value, SyncError> {
-    match &server.transport {
+    let transport = match options.client {
+        Some(client) => server.transport_for_client(client.as_str()),
+        None => server.transport.clone(),
+    };
+
+    let mut obj = match &transport {
         Transport::Stdio { command, args, env } => {
             let mut obj = Map::new();
             obj.insert("command".to_string(), json!(command));
@@ -118,44 +491,207 @@ fn transform_server_to_standard(server: &McpServer, options: &TransformOptions)
                 obj.insert("env".to_string(), json!(transformed_env));
             }
 
-            Ok(Value::Object(obj))
+            obj
         }
         Transport::Sse { url, headers } => {
-            let mut obj = Map::new();
+            let style = remote_entry_style(options.client);
+            let mut obj = remote_entry(url, headers, style.url_key, options)?;
+            obj.insert("transport".to_string(), json!("sse"));
+
+            obj
+        }
+        Transport::Http { url, headers } => {
+            let style = remote_entry_style(options.client);
+            let url_key = style.http_url_key.unwrap_or(style.url_key);
+            let mut obj = remote_entry(url, headers, url_key, options)?;
+            if let Some(http_type) = style.http_type {
+                obj.insert("type".to_string(), json!(http_type));
+            }
+
+            obj
+        }
+    };
 
-            // Resolve URL if it's a keychain reference (rare but possible)
-            let resolved_url = if options.resolve_credentials && is_keychain_reference(url) {
-                resolve_keychain_reference(url)
-                    .map_err(|e| SyncError::CredentialError(format!("Failed to resolve URL: {}", e)))?
+    let client_options = options
+        .client
+        .and_then(|client| server.client_options.get(client.as_str()));
+    if let Some(client_options) = client_options {
+        client_option_keys(options.client).write(client_options, &mut obj);
+    }
+
+    Ok(Value::Object(obj))
+}
+
+/// npm package that exposes a remote server to stdio-only clients
+const MCP_REMOTE_PACKAGE: &str = "mcp-remote";
+
+/// Env var carrying a header into an `mcp-remote` bridge, e.g. `MCP_REMOTE_HEADER_AUTHORIZATION`
+fn bridge_header_var(header: &str) -> String {
+    let name: String = header
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
             } else {
-                url.clone()
-            };
-            obj.insert("url".to_string(), json!(resolved_url));
-            obj.insert("transport".to_string(), json!("sse"));
+                '_'
+            }
+        })
+        .collect();
+    format!("MCP_REMOTE_HEADER_{}", name)
+}
 
-            if !headers.is_empty() {
-                let mut resolved_headers: HashMap<String, String> = HashMap::new();
+/// Stdio transport running `npx mcp-remote <url>` in place of a remote transport.
+///
+/// Header values go through env vars so secrets stay out of the process list
+/// and keychain references are resolved like any other env value. Returns
+/// `None` if the client cannot run the bridge either.
+fn mcp_remote_bridge(
+    transport: &Transport,
+    capabilities: &ClientCapabilities,
+) -> Option<Transport> {
+    let (url, headers, strategy) = match transport {
+        Transport::Stdio { .. } => return None,
+        Transport::Sse { url, headers } => (url, headers, "sse-only"),
+        Transport::Http { url, headers } => (url, headers, "http-only"),
+    };
+    let needs_env = !headers.is_empty();
+    if !capabilities.stdio || (needs_env && !capabilities.env) || is_keychain_reference(url) {
+        return None;
+    }
 
-                for (k, v) in headers {
-                    let resolved_value = if options.resolve_credentials && is_keychain_reference(v) {
-                        resolve_keychain_reference(v)
-                            .map_err(|e| SyncError::CredentialError(format!("Failed to resolve header '{}': {}", k, e)))?
-                    } else {
-                        v.clone()
-                    };
-                    resolved_headers.insert(k.clone(), resolved_value);
-                }
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),
+        MCP_REMOTE_PACKAGE.to_string(),
+        url.clone(),
+        "--transport".to_string(),
+        strategy.to_string(),
+    ];
+    let mut env = HashMap::new();
+
+    let mut names: Vec<&String> = headers.keys().collect();
+    names.sort();
+    for name in names {
+        let var = bridge_header_var(name);
+        args.push("--header".to_string());
+        args.push(format!("{}:${{{}}}", name, var));
+        env.insert(var, headers[name].clone());
+    }
+
+    Some(Transport::Stdio {
+        command: "npx".to_string(),
+        args,
+        env,
+    })
+}
+
+/// Short description of a transport for warnings
+fn transport_label(transport: &Transport, capabilities: &ClientCapabilities) -> &'static str {
+    match transport {
+        Transport::Stdio { .. } if capabilities.stdio => "local servers with env vars",
+        Transport::Stdio { .. } => "local servers",
+        Transport::Sse { .. } if capabilities.sse => "SSE servers with headers",
+        Transport::Sse { .. } => "SSE servers",
+        Transport::Http { .. } if capabilities.http => "Streamable HTTP servers with headers",
+        Transport::Http { .. } => "Streamable HTTP servers",
+    }
+}
+
+/// Fit servers to what a client supports.
+///
+/// Remote servers the client cannot reach are rewritten into an `mcp-remote`
+/// bridge (with the client's override already applied); servers that cannot
+/// be bridged are dropped. Each change is described in the returned warnings.
+fn adapt_servers_for_client(
+    client_id: ClientId,
+    servers: &[&McpServer],
+) -> (Vec<McpServer>, Vec<String>) {
+    let capabilities = client_id.capabilities();
+    let mut adapted = Vec::with_capacity(servers.len());
+    let mut warnings = vec![];
+
+    for server in servers {
+        let transport = server.transport_for_client(client_id.as_str());
+        if capabilities.supports(&transport) {
+            adapted.push((*server).clone());
+            continue;
+        }
+
+        let label = transport_label(&transport, &capabilities);
+        match mcp_remote_bridge(&transport, &capabilities) {
+            Some(bridge) => {
+                let mut bridged = (*server).clone();
+                bridged.transport = bridge;
+                bridged.client_overrides.remove(client_id.as_str());
+                adapted.push(bridged);
+                warnings.push(format!(
+                    "'{}': {} does not support {}, bridged through {}",
+                    server.name,
+                    client_id.display_name(),
+                    label,
+                    MCP_REMOTE_PACKAGE
+                ));
+            }
+            None => warnings.push(format!(
+                "'{}' skipped: {} does not support {}",
+                server.name,
+                client_id.display_name(),
+                label
+            )),
+        }
+    }
+
+    (adapted, warnings)
+}
 
-                obj.insert("headers".to_string(), json!(resolved_headers));
+/// Merge Nexus-managed entries into a client's existing server collection.
+///
+/// Entries named in `owned` belong to Nexus and are replaced or dropped;
+/// every other entry was added by the user (or another tool) and is kept as-is.
+/// Continue.dev may store servers as an array of named objects, in which case
+/// the array shape is preserved.
+fn merge_server_entries(
+    existing: Option<&Value>,
+    managed: Map<String, Value>,
+    owned: &HashSet<String>,
+) -> Value {
+    match existing {
+        Some(Value::Array(items)) => {
+            let mut merged: Vec<Value> = items
+                .iter()
+                .filter(|item| match item.get("name").and_then(|n| n.as_str()) {
+                    Some(name) => !owned.contains(name) && !managed.contains_key(name),
+                    None => true,
+                })
+                .cloned()
+                .collect();
+
+            for (name, mut entry) in managed {
+                if let Some(obj) = entry.as_object_mut() {
+                    obj.insert("name".to_string(), json!(name));
+                }
+                merged.push(entry);
             }
 
-            Ok(Value::Object(obj))
+            Value::Array(merged)
+        }
+        Some(Value::Object(entries)) => {
+            let mut merged = entries.clone();
+            merged.retain(|name, _| !owned.contains(name));
+            merged.extend(managed);
+            Value::Object(merged)
         }
+        _ => Value::Object(managed),
     }
 }
 
-/// Transform servers to standard format: {"mcpServers": {"name": {...}, ...}}
-fn transform_to_standard_format(servers: &[&McpServer], options: &TransformOptions) -> Result<Value, SyncError> {
+/// Transform servers to standard format: {"mcpServers": {"name": {...}, ...}},
+/// merging with the existing config
+fn transform_to_standard_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
     let mut mcp_servers = Map::new();
 
     for server in servers {
@@ -163,11 +699,30 @@ fn transform_to_standard_format(servers: &[&McpServer], options: &TransformOptio
         mcp_servers.insert(server.name.clone(), server_config);
     }
 
+    // Files like ~/.claude.json hold much more than MCP servers, so merge
+    // into the existing document instead of replacing it
+    if let Some(existing) = existing_config {
+        let mut config = existing.clone();
+        if let Some(obj) = config.as_object_mut() {
+            let existing_servers = obj.get("mcpServers");
+            carry_over_client_options(&mut mcp_servers, existing_servers, servers, options);
+            let merged = merge_server_entries(existing_servers, mcp_servers, owned);
+            obj.insert("mcpServers".to_string(), merged);
+            return Ok(Value::Object(obj.clone()));
+        }
+    }
+
+    // No existing config
     Ok(json!({ "mcpServers": mcp_servers }))
 }
 
 /// Transform servers to VS Code format: {"mcp": {"servers": {"name": {...}, ...}}}
-fn transform_to_vscode_format(servers: &[&McpServer], existing_config: Option<&Value>, options: &TransformOptions) -> Result<Value, SyncError> {
+fn transform_to_vscode_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
     let mut mcp_servers = Map::new();
 
     for server in servers {
@@ -184,7 +739,8 @@ fn transform_to_vscode_format(servers: &[&McpServer], existing_config: Option<&V
                 obj.insert("mcp".to_string(), json!({}));
             }
             if let Some(mcp) = obj.get_mut("mcp").and_then(|v| v.as_object_mut()) {
-                mcp.insert("servers".to_string(), Value::Object(mcp_servers));
+                let merged = merge_server_entries(mcp.get("servers"), mcp_servers, owned);
+                mcp.insert("servers".to_string(), merged);
             }
             return Ok(Value::Object(obj.clone()));
         }
@@ -199,7 +755,12 @@ fn transform_to_vscode_format(servers: &[&McpServer], existing_config: Option<&V
 }
 
 /// Transform servers to Continue.dev format, merging with existing config
-fn transform_to_continue_format(servers: &[&McpServer], existing_config: Option<&Value>, options: &TransformOptions) -> Result<Value, SyncError> {
+fn transform_to_continue_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
     let mut mcp_servers = Map::new();
 
     for server in servers {
@@ -211,7 +772,8 @@ fn transform_to_continue_format(servers: &[&McpServer], existing_config: Option<
     if let Some(existing) = existing_config {
         let mut config = existing.clone();
         if let Some(obj) = config.as_object_mut() {
-            obj.insert("mcpServers".to_string(), Value::Object(mcp_servers));
+            let merged = merge_server_entries(obj.get("mcpServers"), mcp_servers, owned);
+            obj.insert("mcpServers".to_string(), merged);
             return Ok(Value::Object(obj.clone()));
         }
     }
@@ -220,707 +782,3860 @@ fn transform_to_continue_format(servers: &[&McpServer], existing_config: Option<
     Ok(json!({ "mcpServers": mcp_servers }))
 }
 
-/// Generate manual config JSON for Warp
-/// Note: Does NOT resolve credentials - shows keychain references for manual copy
-fn generate_warp_config(servers: &[&McpServer]) -> String {
-    // Don't resolve credentials for display - user needs to see references
-    let options = TransformOptions { resolve_credentials: false };
-    let config = match transform_to_standard_format(servers, &options) {
-        Ok(c) => c,
-        Err(_) => return "{}".to_string(),
+/// Rewrite a standard entry with Codex key names (`http_headers`, no `type`/`transport`)
+fn to_codex_entry(entry: Value) -> Value {
+    let Value::Object(mut obj) = entry else {
+        return entry;
     };
-    serde_json::to_string_pretty(&config).unwrap_or_default()
-}
-
-/// Read existing client config file, if it exists
-fn read_existing_config(path: &PathBuf) -> Result<Option<Value>, SyncError> {
-    if !path.exists() {
-        return Ok(None);
-    }
 
-    let mut file = File::open(path).map_err(|e| SyncError::ReadError(e.to_string()))?;
-    let mut content = String::new();
-    file.read_to_string(&mut content).map_err(|e| SyncError::ReadError(e.to_string()))?;
-
-    // Handle empty files
-    if content.trim().is_empty() {
-        return Ok(None);
+    obj.remove("type");
+    obj.remove("transport");
+    if let Some(headers) = obj.remove("headers") {
+        obj.insert("http_headers".to_string(), headers);
     }
 
-    let value: Value = serde_json::from_str(&content)
-        .map_err(|e| SyncError::ParseError(e.to_string()))?;
-
-    Ok(Some(value))
+    Value::Object(obj)
 }
 
-/// Create a backup of an existing config file
-fn create_backup(path: &PathBuf) -> Result<Option<PathBuf>, SyncError> {
-    if !path.exists() {
-        return Ok(None);
+/// Transform servers to Codex CLI format: `[mcp_servers.<name>]` tables in config.toml,
+/// merging with the existing config
+fn transform_to_codex_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut mcp_servers = Map::new();
+
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        mcp_servers.insert(server.name.clone(), to_codex_entry(server_config));
     }
 
-    let backup_path = path.with_extension("json.bak");
-    fs::copy(path, &backup_path).map_err(|e| SyncError::BackupError(e.to_string()))?;
+    // config.toml also holds model, profile and sandbox settings
+    if let Some(existing) = existing_config {
+        let mut config = existing.clone();
+        if let Some(obj) = config.as_object_mut() {
+            let merged = merge_server_entries(obj.get("mcp_servers"), mcp_servers, owned);
+            obj.insert("mcp_servers".to_string(), merged);
+            return Ok(Value::Object(obj.clone()));
+        }
+    }
 
-    Ok(Some(backup_path))
+    // No existing config
+    Ok(json!({ "mcp_servers": mcp_servers }))
 }
 
-/// Write config to file with atomic write and proper permissions
-fn write_config_file(path: &PathBuf, content: &str) -> Result<(), SyncError> {
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| SyncError::DirectoryError(e.to_string()))?;
+/// Rewrite a standard entry in Zed's shape: stdio servers nest their launch
+/// details as `command: {path, args, env}`
+fn to_zed_entry(entry: Value) -> Value {
+    let Value::Object(mut obj) = entry else {
+        return entry;
+    };
 
-            // Set directory permissions to 0700
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let permissions = fs::Permissions::from_mode(0o700);
-                fs::set_permissions(parent, permissions)?;
+    obj.remove("type");
+    obj.remove("transport");
+    if let Some(path) = obj.remove("command") {
+        let mut command = Map::new();
+        command.insert("path".to_string(), path);
+        for key in ["args", "env"] {
+            if let Some(value) = obj.remove(key) {
+                command.insert(key.to_string(), value);
             }
         }
+        obj.insert("command".to_string(), Value::Object(command));
     }
 
-    // Write to temp file first
-    let temp_path = path.with_extension("json.tmp");
-    {
-        let mut file = File::create(&temp_path)?;
-        file.write_all(content.as_bytes())?;
-        file.sync_all()?;
-    }
-
-    // Set file permissions to 0600 (user-only read/write)
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let permissions = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(&temp_path, permissions)?;
-    }
+    Value::Object(obj)
+}
 
-    // Atomic rename
-    fs::rename(&temp_path, path)?;
+/// Transform servers to Zed format: {"context_servers": {"name": {...}, ...}},
+/// merging with the existing settings
+fn transform_to_zed_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut context_servers = Map::new();
 
-    Ok(())
-}
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        context_servers.insert(server.name.clone(), to_zed_entry(server_config));
+    }
 
-/// Compute checksum of file content
-pub fn compute_checksum(content: &[u8]) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    // settings.json holds all of the editor's settings
+    if let Some(existing) = existing_config {
+        let mut config = existing.clone();
+        if let Some(obj) = config.as_object_mut() {
+            let merged = merge_server_entries(obj.get("context_servers"), context_servers, owned);
+            obj.insert("context_servers".to_string(), merged);
+            return Ok(Value::Object(obj.clone()));
+        }
+    }
 
-    let mut hasher = DefaultHasher::new();
-    content.hash(&mut hasher);
-    format!("{:x}", hasher.finish())
+    // No existing config
+    Ok(json!({ "context_servers": context_servers }))
 }
 
-/// Sync configuration to a single client
-pub fn sync_to_client(
-    client_id: ClientId,
-    config: &McpHubConfig,
-) -> ClientSyncResult {
-    // Check if client requires manual configuration
-    let detected = detect_client(client_id);
+/// Default request timeout (seconds) Goose applies to extensions
+const GOOSE_EXTENSION_TIMEOUT: u64 = 300;
 
-    if detected.sync_mode == SyncMode::ManualOnly {
-        // Generate manual config for display
-        let servers: Vec<&McpServer> = config.get_servers_for_client(client_id.as_str());
-        let manual_config = generate_warp_config(&servers);
+/// Rewrite a standard entry as a Goose extension: `cmd`/`envs` instead of
+/// `command`/`env`, an explicit `type`, and the extension's own name.
+///
+/// `enabled` and `timeout` are kept from the `previous` entry, since users set
+/// them in Goose; new entries get Goose's defaults.
+fn to_goose_entry(name: &str, entry: Value, previous: Option<&Value>) -> Value {
+    let Value::Object(mut obj) = entry else {
+        return entry;
+    };
 
-        return ClientSyncResult {
-            client_id,
-            success: true, // Not a failure, just manual
-            servers_synced: servers.len(),
-            backup_path: None,
-            error: None,
-            manual_config: Some(manual_config),
-        };
+    if let Some(command) = obj.remove("command") {
+        obj.insert("cmd".to_string(), command);
+        obj.insert("type".to_string(), json!("stdio"));
     }
+    if let Some(transport) = obj.remove("transport") {
+        obj.insert("type".to_string(), transport);
+    }
+    let envs = obj.remove("env").unwrap_or_else(|| json!({}));
+    obj.insert("envs".to_string(), envs);
+    obj.insert("name".to_string(), json!(name));
 
-    // Get config path
-    let config_path = match get_client_config_path(client_id) {
-        Ok(path) => path,
-        Err(e) => {
-            return ClientSyncResult {
-                client_id,
-                success: false,
-                servers_synced: 0,
-                backup_path: None,
-                error: Some(e.to_string()),
-                manual_config: None,
-            };
-        }
+    let previous = |key: &str, valid: fn(&Value) -> bool| {
+        previous.and_then(|p| p.get(key)).filter(|v| valid(v)).cloned()
     };
+    let enabled = previous("enabled", Value::is_boolean).unwrap_or(json!(true));
+    let timeout = previous("timeout", Value::is_u64).unwrap_or(json!(GOOSE_EXTENSION_TIMEOUT));
+    obj.insert("enabled".to_string(), enabled);
+    obj.insert("timeout".to_string(), timeout);
 
-    // Get servers enabled for this client
-    let servers: Vec<&McpServer> = config.get_servers_for_client(client_id.as_str());
+    Value::Object(obj)
+}
 
-    // Read existing config (to preserve other settings)
-    let existing_config = match read_existing_config(&config_path) {
-        Ok(config) => config,
-        Err(e) => {
-            return ClientSyncResult {
-                client_id,
-                success: false,
-                servers_synced: 0,
-                backup_path: None,
-                error: Some(format!("Failed to read existing config: {}", e)),
-                manual_config: None,
-            };
+/// Transform servers to Goose format: {"extensions": {"name": {...}, ...}},
+/// merging with the existing config
+fn transform_to_goose_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut extensions = Map::new();
+    let existing_extensions = existing_config.and_then(|c| c.get("extensions"));
+
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        let previous = existing_extensions.and_then(|e| e.get(&server.name));
+        let entry = to_goose_entry(&server.name, server_config, previous);
+        extensions.insert(server.name.clone(), entry);
+    }
+
+    // config.yaml also holds the provider, model and builtin extensions
+    if let Some(existing) = existing_config {
+        let mut config = existing.clone();
+        if let Some(obj) = config.as_object_mut() {
+            let merged = merge_server_entries(obj.get("extensions"), extensions, owned);
+            obj.insert("extensions".to_string(), merged);
+            return Ok(Value::Object(obj.clone()));
         }
-    };
+    }
 
-    // Create backup if file exists
-    let backup_path = match create_backup(&config_path) {
-        Ok(path) => path.map(|p| p.to_string_lossy().to_string()),
-        Err(e) => {
-            return ClientSyncResult {
-                client_id,
-                success: false,
-                servers_synced: 0,
-                backup_path: None,
-                error: Some(format!("Failed to create backup: {}", e)),
-                manual_config: None,
-            };
+    // No existing config
+    Ok(json!({ "extensions": extensions }))
+}
+
+/// Transform servers to Continue.dev's config.yaml: a named `mcpServers` list,
+/// merging with the existing config
+fn transform_to_continue_yaml_format(
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut mcp_servers = Map::new();
+
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        mcp_servers.insert(server.name.clone(), server_config);
+    }
+
+    // config.yaml always lists servers, so start from an empty list
+    let empty = json!([]);
+
+    if let Some(existing) = existing_config {
+        let mut config = existing.clone();
+        if let Some(obj) = config.as_object_mut() {
+            let current = obj.get("mcpServers").unwrap_or(&empty);
+            let merged = merge_server_entries(Some(current), mcp_servers, owned);
+            obj.insert("mcpServers".to_string(), merged);
+            return Ok(Value::Object(obj.clone()));
         }
-    };
+    }
 
-    // Transform servers to client-specific format
-    // Resolve credentials when syncing to client configs
-    let options = TransformOptions { resolve_credentials: true };
+    // No existing config: a new config.yaml needs Continue's required header
+    Ok(json!({
+        "name": "Local Config",
+        "version": "1.0.0",
+        "schema": "v1",
+        "mcpServers": merge_server_entries(Some(&empty), mcp_servers, owned),
+    }))
+}
 
-    let output = match detected.config_format {
-        ConfigFormat::Standard => transform_to_standard_format(&servers, &options),
-        ConfigFormat::Vscode => transform_to_vscode_format(&servers, existing_config.as_ref(), &options),
-        ConfigFormat::Continue => transform_to_continue_format(&servers, existing_config.as_ref(), &options),
+/// Rewrite a standard entry with a custom client's field names
+fn to_descriptor_entry(fields: &FieldMapping, entry: Value) -> Value {
+    let Value::Object(obj) = entry else {
+        return entry;
     };
 
-    let output = match output {
-        Ok(o) => o,
-        Err(e) => {
-            return ClientSyncResult {
-                client_id,
-                success: false,
-                servers_synced: 0,
-                backup_path,
-                error: Some(format!("Failed to transform config: {}", e)),
-                manual_config: None,
-            };
-        }
+    let kind_type = if obj.contains_key("command") {
+        &fields.stdio_type
+    } else if obj.get("transport").and_then(|v| v.as_str()) == Some("sse") {
+        &fields.sse_type
+    } else {
+        &fields.http_type
     };
 
-    // Serialize and write
-    let json_content = match serde_json::to_string_pretty(&output) {
-        Ok(content) => content,
-        Err(e) => {
-            return ClientSyncResult {
-                client_id,
-                success: false,
-                servers_synced: 0,
-                backup_path,
-                error: Some(format!("Failed to serialize config: {}", e)),
-                manual_config: None,
-            };
+    let mut mapped = Map::new();
+    for (from, to) in [
+        ("command", &fields.command),
+        ("args", &fields.args),
+        ("env", &fields.env),
+        ("url", &fields.url),
+        ("headers", &fields.headers),
+    ] {
+        if let Some(value) = obj.get(from) {
+            mapped.insert(to.clone(), value.clone());
         }
+    }
+    if let (Some(type_key), Some(kind)) = (&fields.r#type, kind_type) {
+        mapped.insert(type_key.clone(), json!(kind));
+    }
+
+    Value::Object(mapped)
+}
+
+/// Read a custom client's entry back into the standard key layout
+fn from_descriptor_entry(fields: &FieldMapping, entry: &Value) -> Value {
+    let Some(obj) = entry.as_object() else {
+        return entry.clone();
     };
 
-    if let Err(e) = write_config_file(&config_path, &json_content) {
-        return ClientSyncResult {
-            client_id,
-            success: false,
-            servers_synced: 0,
-            backup_path,
-            error: Some(format!("Failed to write config: {}", e)),
-            manual_config: None,
-        };
+    let mut standard = Map::new();
+    for (from, to) in [
+        (&fields.command, "command"),
+        (&fields.args, "args"),
+        (&fields.env, "env"),
+        (&fields.url, "url"),
+        (&fields.headers, "headers"),
+    ] {
+        if let Some(value) = obj.get(from) {
+            standard.insert(to.to_string(), value.clone());
+        }
     }
 
-    ClientSyncResult {
-        client_id,
-        success: true,
-        servers_synced: servers.len(),
-        backup_path,
-        error: None,
-        manual_config: None,
+    let declared = fields
+        .r#type
+        .as_ref()
+        .and_then(|key| obj.get(key))
+        .and_then(|v| v.as_str());
+    if let Some(declared) = declared {
+        if fields.sse_type.as_deref() == Some(declared) {
+            standard.insert("type".to_string(), json!("sse"));
+        } else if fields.http_type.as_deref() == Some(declared) {
+            standard.insert("type".to_string(), json!("http"));
+        }
     }
+
+    Value::Object(standard)
 }
 
-/// Sync configuration to all enabled clients
-pub fn sync_to_all_clients(config: &McpHubConfig) -> SyncResult {
-    let mut results = Vec::new();
-    let mut successful = 0;
-    let mut failed = 0;
-    let mut manual_required = 0;
+/// Entries as a client stores them, rewritten in the standard key layout
+fn standard_entries(client_id: ClientId, entries: Map<String, Value>) -> Map<String, Value> {
+    let ClientId::Custom(id) = client_id else {
+        return entries;
+    };
+    let Some(descriptor) = custom_client(id) else {
+        return entries;
+    };
 
-    for client_id in ClientId::all() {
-        // Check if sync is enabled for this client
-        let client_settings = config.clients.get(client_id.as_str());
-        let enabled = client_settings.map(|s| s.enabled).unwrap_or(true);
+    entries
+        .into_iter()
+        .map(|(name, entry)| {
+            let standard = from_descriptor_entry(&descriptor.fields, &entry);
+            (name, standard)
+        })
+        .collect()
+}
 
-        if !enabled {
-            continue;
-        }
+/// Transform servers to a custom client's layout, merging into the existing document
+/// at the descriptor's server pointer
+fn transform_to_descriptor_format(
+    descriptor: &ClientDescriptor,
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut entries = Map::new();
 
-        let result = sync_to_client(client_id, config);
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        entries.insert(
+            server.name.clone(),
+            to_descriptor_entry(&descriptor.fields, server_config),
+        );
+    }
 
-        if result.manual_config.is_some() {
-            manual_required += 1;
-            successful += 1; // Manual configs are considered successful
-        } else if result.success {
-            successful += 1;
-        } else {
-            failed += 1;
-        }
+    let pointer = descriptor.servers_keys();
+    let mut config = match existing_config {
+        Some(existing) if existing.is_object() => existing.clone(),
+        _ => json!({}),
+    };
+    let merged = merge_server_entries(value_at(&config, &pointer), entries, owned);
+    set_value_at(&mut config, &pointer, merged);
 
-        results.push(result);
-    }
+    Ok(config)
+}
 
-    SyncResult {
-        total_clients: results.len(),
-        successful,
-        failed,
-        manual_required,
-        results,
+/// Generate manual config JSON for Warp
+/// Note: Does NOT resolve credentials - shows keychain references for manual copy
+fn generate_warp_config(servers: &[&McpServer]) -> String {
+    // Don't resolve credentials for display - user needs to see references
+    let options = TransformOptions {
+        resolve_credentials: false,
+        client: Some(ClientId::Warp),
+        ..Default::default()
+    };
+    let config = match transform_to_standard_format(servers, None, &HashSet::new(), &options) {
+        Ok(c) => c,
+        Err(_) => return "{}".to_string(),
+    };
+    serde_json::to_string_pretty(&config).unwrap_or_default()
+}
+
+/// Build the full document to write for a client in its config format
+fn build_client_output(
+    format: ConfigFormat,
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    match format {
+        ConfigFormat::Standard => {
+            transform_to_standard_format(servers, existing_config, owned, options)
+        }
+        ConfigFormat::Vscode => transform_to_vscode_format(servers, existing_config, owned, options),
+        ConfigFormat::Continue => {
+            transform_to_continue_format(servers, existing_config, owned, options)
+        }
+        ConfigFormat::CodexToml => {
+            transform_to_codex_format(servers, existing_config, owned, options)
+        }
+        ConfigFormat::Zed => transform_to_zed_format(servers, existing_config, owned, options),
+        ConfigFormat::ContinueYaml => {
+            transform_to_continue_yaml_format(servers, existing_config, owned, options)
+        }
+        ConfigFormat::GooseYaml => {
+            transform_to_goose_format(servers, existing_config, owned, options)
+        }
+        ConfigFormat::Custom => {
+            let id = options.client.map(|c| c.to_string()).unwrap_or_default();
+            let descriptor = custom_client(&id).ok_or(SyncError::ClientNotFound(id))?;
+            transform_to_descriptor_format(&descriptor, servers, existing_config, owned, options)
+        }
     }
 }
 
-/// Parse servers from a client's raw config
-fn parse_servers_from_raw_config(
-    raw_config: &HashMap<String, Value>,
-    source_client: ClientId,
-) -> Vec<McpServer> {
-    let mut servers = Vec::new();
+/// Names of the entries in a client's config that Nexus owns and may rewrite or remove
+/// Option keys Nexus wrote for each server at the client's last sync
+fn written_option_keys(
+    config: &McpHubConfig,
+    client_id: ClientId,
+) -> Option<BTreeMap<String, Vec<String>>> {
+    config.clients.get(client_id.as_str())?.option_keys.clone()
+}
 
-    for (name, config) in raw_config {
-        if let Some(server) = parse_single_server(name, config, source_client) {
-            servers.push(server);
+fn owned_server_names(config: &McpHubConfig, client_id: ClientId) -> HashSet<String> {
+    let ledger = config
+        .clients
+        .get(client_id.as_str())
+        .and_then(|s| s.managed_servers.as_ref());
+
+    match ledger {
+        Some(names) => names.iter().cloned().collect(),
+        // Synced before ownership tracking existed: claim only entries that
+        // match a server in the central config
+        None => config.servers.iter().map(|s| s.name.clone()).collect(),
+    }
+}
+
+/// Keys leading from the document root to a client format's server collection
+fn format_servers_pointer(format: ConfigFormat) -> Vec<String> {
+    match format {
+        ConfigFormat::Standard | ConfigFormat::Continue | ConfigFormat::ContinueYaml => {
+            vec!["mcpServers".to_string()]
         }
+        ConfigFormat::Vscode => vec!["mcp".to_string(), "servers".to_string()],
+        ConfigFormat::CodexToml => vec!["mcp_servers".to_string()],
+        ConfigFormat::Zed => vec!["context_servers".to_string()],
+        ConfigFormat::GooseYaml => vec!["extensions".to_string()],
+        // Custom layouts come from the client's descriptor, see `client_servers_pointer`
+        ConfigFormat::Custom => vec![],
     }
+}
 
-    servers
+/// Keys leading from the document root to a client's server collection
+fn client_servers_pointer(client_id: ClientId, format: ConfigFormat) -> Vec<String> {
+    match client_id {
+        ClientId::Custom(id) => custom_client(id)
+            .map(|d| d.servers_keys())
+            .unwrap_or_default(),
+        _ => format_servers_pointer(format),
+    }
 }
 
-/// Parse a single server configuration
-fn parse_single_server(name: &str, config: &Value, source_client: ClientId) -> Option<McpServer> {
-    let obj = config.as_object()?;
+/// Extract the server entries (name -> definition) stored at `pointer` in a client document
+fn extract_servers_at(config: &Value, pointer: &[String]) -> Map<String, Value> {
+    let servers = value_at(config, pointer);
+
+    match servers {
+        Some(Value::Object(map)) => map.clone(),
+        // Continue.dev may store servers as an array of named objects
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                let name = item.get("name").and_then(|n| n.as_str())?;
+                Some((name.to_string(), item.clone()))
+            })
+            .collect(),
+        _ => Map::new(),
+    }
+}
 
-    // Determine transport type
-    let transport = if let Some(url) = obj.get("url").and_then(|v| v.as_str()) {
-        // SSE transport
-        let headers: HashMap<String, String> = obj
-            .get("headers")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or_default();
+/// Compare a single top-level field of two server entries
+fn diff_field(current: &Value, desired: &Value, field: &str) -> Option<FieldChange> {
+    let before = current.get(field).cloned();
+    let after = desired.get(field).cloned();
 
-        Transport::Sse {
-            url: url.to_string(),
-            headers,
-        }
-    } else if let Some(command) = obj.get("command").and_then(|v| v.as_str()) {
-        // Stdio transport
-        let args: Vec<String> = obj
-            .get("args")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or_default();
+    if before == after {
+        None
+    } else {
+        Some(FieldChange { before, after })
+    }
+}
 
-        let env: HashMap<String, String> = obj
-            .get("env")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or_default();
+/// Compare the URL of two remote entries, whichever key each one uses
+fn diff_url(current: &Value, desired: &Value) -> Option<FieldChange> {
+    let before = entry_url(current).map(|u| json!(u));
+    let after = entry_url(desired).map(|u| json!(u));
 
-        Transport::Stdio {
-            command: command.to_string(),
-            args,
-            env,
-        }
+    if before == after {
+        None
     } else {
-        // Unknown format
-        return None;
-    };
+        Some(FieldChange { before, after })
+    }
+}
 
-    // Determine source type from command/args if possible
-    let source = match &transport {
-        Transport::Sse { url, .. } => ServerSource::Remote { url: url.clone() },
-        Transport::Stdio { command, args, .. } => {
-            if command == "npx" || command.ends_with("/npx") {
-                // NPM package
-                let package = args.iter()
-                    .skip_while(|a| a.starts_with('-'))
-                    .next()
-                    .cloned()
-                    .unwrap_or_else(|| name.to_string());
-                ServerSource::Npm { package, version: None }
-            } else if command == "uvx" || command.ends_with("/uvx") {
-                // Python uvx package
-                let package = args.first().cloned().unwrap_or_else(|| name.to_string());
-                ServerSource::Uvx { package }
-            } else if command == "docker" || command.ends_with("/docker") {
-                // Docker image
-                let image = args.iter()
-                    .skip_while(|a| *a != "run")
-                    .skip(1)
-                    .skip_while(|a| a.starts_with('-'))
-                    .next()
-                    .cloned()
-                    .unwrap_or_else(|| name.to_string());
-                ServerSource::Docker { image }
+/// Compare the remote protocol (SSE vs Streamable HTTP) of two entries
+fn diff_transport(current: &Value, desired: &Value) -> Option<FieldChange> {
+    let kind = |entry: &Value| {
+        entry_url(entry).map(|url| {
+            if is_http_entry(entry, url) {
+                json!("http")
             } else {
-                // Assume local path
-                ServerSource::Local {
-                    path: format!("{} {}", command, args.join(" ")),
-                }
+                json!("sse")
             }
-        }
+        })
     };
 
-    let mut server = McpServer::new(name.to_string(), source, transport);
-    server.description = Some(format!("Imported from {}", source_client.display_name()));
-
-    // Enable for the source client by default
-    server.enable_for_client(source_client.as_str());
+    let before = kind(current);
+    let after = kind(desired);
 
-    Some(server)
+    // Only meaningful when both sides are remote; otherwise command/url show it
+    if before.is_some() && after.is_some() && before != after {
+        Some(FieldChange { before, after })
+    } else {
+        None
+    }
 }
 
-/// Import servers from a client's configuration
-pub fn import_from_client(
-    client_id: ClientId,
-    config: &mut McpHubConfig,
-    overwrite_existing: bool,
-) -> Result<ImportResult, SyncError> {
-    use crate::services::client_detector::get_client_config_info;
-
-    // Get client config info
-    let config_info = get_client_config_info(client_id)
-        .map_err(|e| SyncError::ReadError(e.to_string()))?
-        .ok_or_else(|| SyncError::ReadError("No config found for client".to_string()))?;
-
-    let raw_config = config_info.raw_config
-        .ok_or_else(|| SyncError::ParseError("Could not parse server configs".to_string()))?;
+/// Compare a string map field (env/headers) by key.
+///
+/// Keychain references in the desired entry are resolved only at write time,
+/// so their values are not compared against the resolved value on disk.
+fn diff_keys(current: &Value, desired: &Value, field: &str) -> Option<KeyChanges> {
+    let empty = Map::new();
+    let before = current.get(field).and_then(|v| v.as_object()).unwrap_or(&empty);
+    let after = desired.get(field).and_then(|v| v.as_object()).unwrap_or(&empty);
+
+    let mut changes = KeyChanges::default();
+
+    for (key, value) in after {
+        match before.get(key) {
+            None => changes.added.push(key.clone()),
+            Some(old) => {
+                let is_reference = value.as_str().map(is_keychain_reference).unwrap_or(false);
+                if !is_reference && old != value {
+                    changes.modified.push(key.clone());
+                }
+            }
+        }
+    }
 
-    // Parse servers from raw config
-    let imported_servers = parse_servers_from_raw_config(&raw_config, client_id);
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            changes.removed.push(key.clone());
+        }
+    }
 
-    let mut servers_imported = 0;
-    let mut skipped_existing = 0;
-    let mut server_names = Vec::new();
+    changes.added.sort();
+    changes.removed.sort();
+    changes.modified.sort();
 
-    for server in imported_servers {
-        // Check if server with same name already exists
-        let exists = config.servers.iter().any(|s| s.name == server.name);
+    if changes.is_empty() {
+        None
+    } else {
+        Some(changes)
+    }
+}
 
-        if exists && !overwrite_existing {
-            skipped_existing += 1;
-            continue;
+/// A client entry rewritten with the standard key layout.
+///
+/// Codex's `http_headers` becomes `headers`, Goose's `cmd`/`envs`/`uri`
+/// become `command`/`env`/`url`, and Zed's nested `command: {path, args, env}`
+/// is flattened to `command`/`args`/`env`.
+fn standard_entry(entry: &Value) -> Value {
+    let Some(obj) = entry.as_object() else {
+        return entry.clone();
+    };
+    let mut obj = obj.clone();
+
+    for (alias, key) in [
+        ("http_headers", "headers"),
+        ("cmd", "command"),
+        ("envs", "env"),
+        ("uri", "url"),
+    ] {
+        if !obj.contains_key(key) {
+            if let Some(value) = obj.remove(alias) {
+                obj.insert(key.to_string(), value);
+            }
         }
+    }
 
-        if exists {
-            // Remove existing server with same name
-            config.servers.retain(|s| s.name != server.name);
+    if let Some(Value::Object(command)) = obj.get("command").cloned() {
+        match command.get("path") {
+            Some(path) => obj.insert("command".to_string(), path.clone()),
+            None => obj.remove("command"),
+        };
+        for key in ["args", "env"] {
+            if let Some(value) = command.get(key) {
+                obj.insert(key.to_string(), value.clone());
+            }
         }
+    }
 
-        server_names.push(server.name.clone());
-        config.servers.push(server);
-        servers_imported += 1;
+    Value::Object(obj)
+}
+
+/// Field-level diff of a server entry, or None if nothing relevant changed
+fn diff_server_entry(name: &str, current: &Value, desired: &Value) -> Option<ServerChange> {
+    let current = &standard_entry(current);
+    let desired = &standard_entry(desired);
+    let change = ServerChange {
+        name: name.to_string(),
+        command: diff_field(current, desired, "command"),
+        args: diff_field(current, desired, "args"),
+        url: diff_url(current, desired),
+        transport: diff_transport(current, desired),
+        env: diff_keys(current, desired, "env"),
+        headers: diff_keys(current, desired, "headers"),
+    };
+
+    let unchanged = change.command.is_none()
+        && change.args.is_none()
+        && change.url.is_none()
+        && change.transport.is_none()
+        && change.env.is_none()
+        && change.headers.is_none();
+
+    if unchanged {
+        None
+    } else {
+        Some(change)
     }
+}
 
-    Ok(ImportResult {
+/// Diff the servers currently on disk against the ones a sync would write
+fn diff_client_servers(
+    current: &Map<String, Value>,
+    desired: &Map<String, Value>,
+    preview: &mut ClientSyncPreview,
+) {
+    for (name, desired_entry) in desired {
+        match current.get(name) {
+            None => preview.added.push(name.clone()),
+            Some(current_entry) => match diff_server_entry(name, current_entry, desired_entry) {
+                Some(change) => preview.changed.push(change),
+                None => preview.unchanged.push(name.clone()),
+            },
+        }
+    }
+
+    for name in current.keys() {
+        if !desired.contains_key(name) {
+            preview.removed.push(name.clone());
+        }
+    }
+
+    preview.added.sort();
+    preview.removed.sort();
+    preview.unchanged.sort();
+    preview.changed.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+/// Compute the preview for a client config file without writing anything
+fn preview_config_file(
+    client_id: ClientId,
+    format: ConfigFormat,
+    config_path: &PathBuf,
+    servers: &[&McpServer],
+    owned: &HashSet<String>,
+    written_option_keys: Option<&BTreeMap<String, Vec<String>>>,
+) -> ClientSyncPreview {
+    let mut preview = ClientSyncPreview::new(client_id);
+    preview.config_path = Some(config_path.to_string_lossy().to_string());
+
+    let existing_config = match read_existing_config(config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            preview.error = Some(format!("Failed to read existing config: {}", e));
+            return preview;
+        }
+    };
+
+    // Credentials stay unresolved: a preview must not touch the keychain
+    let options = TransformOptions {
+        resolve_credentials: false,
+        client: Some(client_id),
+        written_option_keys: written_option_keys.cloned(),
+    };
+    let output = match build_client_output(
+        format,
+        servers,
+        existing_config.as_ref(),
+        owned,
+        &options,
+    ) {
+        Ok(o) => o,
+        Err(e) => {
+            preview.error = Some(format!("Failed to transform config: {}", e));
+            return preview;
+        }
+    };
+
+    let pointer = client_servers_pointer(client_id, format);
+    let current = existing_config
+        .as_ref()
+        .map(|c| standard_entries(client_id, extract_servers_at(c, &pointer)))
+        .unwrap_or_default();
+    let desired = standard_entries(client_id, extract_servers_at(&output, &pointer));
+
+    diff_client_servers(&current, &desired, &mut preview);
+    preview
+}
+
+/// Raw content of a config file, if it exists
+fn read_original_content(path: &Path) -> Result<Option<String>, SyncError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| SyncError::ReadError(e.to_string()))
+}
+
+/// Whether a config file is TOML rather than JSON(C)
+fn is_toml_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+/// Whether a config file is YAML rather than JSON(C)
+fn is_yaml_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+/// Read existing client config file, if it exists
+fn read_existing_config(path: &PathBuf) -> Result<Option<Value>, SyncError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut file = File::open(path).map_err(|e| SyncError::ReadError(e.to_string()))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).map_err(|e| SyncError::ReadError(e.to_string()))?;
+
+    // Handle empty files
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let value: Value = if is_toml_path(path) {
+        toml_config::parse(&content).map_err(|e| SyncError::ParseError(e.to_string()))?
+    } else if is_yaml_path(path) {
+        // A YAML file holding only comments is an empty (null) document
+        match yaml_config::parse(&content).map_err(|e| SyncError::ParseError(e.to_string()))? {
+            Value::Null => return Ok(None),
+            value => value,
+        }
+    } else {
+        // Editor settings files are often JSONC (comments, trailing commas)
+        jsonc::parse(&content).map_err(|e| SyncError::ParseError(e.to_string()))?
+    };
+
+    Ok(Some(value))
+}
+
+/// Render a client document for writing.
+///
+/// When the file already has content, only the server collection at `pointer`
+/// is rewritten, so comments, key order and formatting elsewhere survive.
+fn render_config(
+    original: Option<&str>,
+    output: &Value,
+    pointer: &[String],
+) -> Result<String, SyncError> {
+    if let (Some(text), Some(servers)) = (original, value_at(output, pointer)) {
+        if !text.trim().is_empty() {
+            if let Some(patched) = jsonc::replace_at(text, pointer, servers) {
+                return Ok(patched);
+            }
+        }
+    }
+
+    serde_json::to_string_pretty(output).map_err(|e| SyncError::SerializeError(e.to_string()))
+}
+
+/// Render a TOML client document for writing, patching only the table at `pointer`
+fn render_toml_config(
+    original: Option<&str>,
+    output: &Value,
+    pointer: &[String],
+) -> Result<String, SyncError> {
+    if let (Some(text), Some(servers)) = (original, value_at(output, pointer)) {
+        if !text.trim().is_empty() {
+            if let Some(patched) = toml_config::replace_at(text, pointer, servers) {
+                return Ok(patched);
+            }
+        }
+    }
+
+    toml_config::to_string(output)
+        .ok_or_else(|| SyncError::SerializeError("Config is not a TOML table".to_string()))
+}
+
+/// Render a YAML client document for writing, patching only the block at `pointer`
+fn render_yaml_config(
+    original: Option<&str>,
+    output: &Value,
+    pointer: &[String],
+) -> Result<String, SyncError> {
+    if let (Some(text), Some(servers)) = (original, value_at(output, pointer)) {
+        if !text.trim().is_empty() {
+            if let Some(patched) = yaml_config::replace_at(text, pointer, servers) {
+                return Ok(patched);
+            }
+        }
+    }
+
+    yaml_config::to_string(output).map_err(|e| SyncError::SerializeError(e.to_string()))
+}
+
+/// Render a client document in the syntax matching the file's extension
+fn render_for_path(
+    path: &Path,
+    original: Option<&str>,
+    output: &Value,
+    pointer: &[String],
+) -> Result<String, SyncError> {
+    if is_toml_path(path) {
+        render_toml_config(original, output, pointer)
+    } else if is_yaml_path(path) {
+        render_yaml_config(original, output, pointer)
+    } else {
+        render_config(original, output, pointer)
+    }
+}
+
+/// Snapshot a client's existing config file into its backup history
+fn create_backup(
+    client_id: ClientId,
+    path: &Path,
+    retention: usize,
+) -> Result<Option<PathBuf>, SyncError> {
+    let backup_dir =
+        client_backup_dir(client_id).map_err(|e| SyncError::BackupError(e.to_string()))?;
+
+    create_snapshot(&backup_dir, path, retention).map_err(|e| SyncError::BackupError(e.to_string()))
+}
+
+/// Write config to file with atomic write and proper permissions
+fn write_config_file(path: &PathBuf, content: &str) -> Result<(), SyncError> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| SyncError::DirectoryError(e.to_string()))?;
+
+            // Set directory permissions to 0700
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let permissions = fs::Permissions::from_mode(0o700);
+                fs::set_permissions(parent, permissions)?;
+            }
+        }
+    }
+
+    // Write to temp file first, named after the target so a client's
+    // config.toml and config.yaml in one directory never share it
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!("{}.tmp", file_name));
+    {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    // Set file permissions to 0600 (user-only read/write)
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let permissions = fs::Permissions::from_mode(0o600);
+        fs::set_permissions(&temp_path, permissions)?;
+    }
+
+    // Atomic rename
+    note_own_write(path, Some(content));
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+/// Marks a SHA-256 checksum; older releases stored unprefixed, unstable hashes
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// Compute a checksum of content that stays stable across releases and platforms
+pub fn compute_checksum(content: &[u8]) -> String {
+    format!("{}{:x}", CHECKSUM_PREFIX, Sha256::digest(content))
+}
+
+/// Hash each server entry in a client config, keyed by server name.
+///
+/// Entries are hashed in canonical form, so reformatting the file does not
+/// change them. Returns `None` if the file cannot be read or parsed.
+fn server_entry_hashes(
+    client_id: ClientId,
+    format: ConfigFormat,
+    path: &PathBuf,
+) -> Option<BTreeMap<String, String>> {
+    let config = match read_existing_config(path).ok()? {
+        Some(config) => config,
+        None => return Some(BTreeMap::new()),
+    };
+    let pointer = client_servers_pointer(client_id, format);
+
+    Some(
+        extract_servers_at(&config, &pointer)
+            .into_iter()
+            .map(|(name, entry)| (name, compute_checksum(entry.to_string().as_bytes())))
+            .collect(),
+    )
+}
+
+/// Sync status of a detected client, comparing its config file with the last sync.
+///
+/// Checksums recorded by older releases cannot be compared and are treated as
+/// unchanged until the next sync replaces them.
+pub fn client_sync_status(
+    detected: &DetectedClient,
+    settings: Option<&ClientSettings>,
+) -> ClientSyncStatus {
+    let mut status = ClientSyncStatus {
+        client_id: detected.id,
+        enabled: settings.map(|s| s.enabled).unwrap_or(true),
+        last_sync: settings.and_then(|s| s.last_sync.clone()),
+        last_sync_checksum: settings.and_then(|s| s.last_sync_checksum.clone()),
+        externally_modified: false,
+        modified_servers: vec![],
+        removed_servers: vec![],
+        sync_error: detected.error.clone(),
+    };
+
+    let (Some(settings), Some(path)) = (settings, &detected.config_path) else {
+        return status;
+    };
+    let last_checksum = match &settings.last_sync_checksum {
+        Some(checksum) if checksum.starts_with(CHECKSUM_PREFIX) => checksum,
+        _ => return status,
+    };
+    let Ok(content) = fs::read(path) else {
+        return status;
+    };
+
+    status.externally_modified = &compute_checksum(&content) != last_checksum;
+    if !status.externally_modified {
+        return status;
+    }
+
+    // Trace the change to individual entries using the per-server ledger
+    let current = server_entry_hashes(detected.id, detected.config_format, path);
+    if let (Some(recorded), Some(current)) = (&settings.server_hashes, current) {
+        for (name, hash) in recorded {
+            match current.get(name) {
+                Some(current_hash) if current_hash == hash => {}
+                Some(_) => status.modified_servers.push(name.clone()),
+                None => status.removed_servers.push(name.clone()),
+            }
+        }
+    }
+
+    status
+}
+
+/// A client's new config content, computed but not yet written
+struct StagedClientWrite {
+    client_id: ClientId,
+    config_path: PathBuf,
+    content: String,
+    servers_synced: usize,
+    /// Names of the server entries in `content`
+    synced_servers: Vec<String>,
+    /// File content before the sync (`None` if the file did not exist)
+    original: Option<String>,
+    /// Servers bridged or skipped for the client's capabilities
+    warnings: Vec<String>,
+}
+
+/// Build a failed sync result for a client
+fn failed_sync_result(
+    client_id: ClientId,
+    backup_path: Option<String>,
+    error: String,
+) -> ClientSyncResult {
+    ClientSyncResult {
         client_id,
-        servers_imported,
-        server_names,
-        skipped_existing,
+        success: false,
+        servers_synced: 0,
+        backup_path,
+        error: Some(error),
+        manual_config: None,
+        warnings: vec![],
+        synced_servers: vec![],
+    }
+}
+
+/// Build the result for a manual-only client, whose config is shown rather than written
+fn manual_sync_result(client_id: ClientId, config: &McpHubConfig) -> ClientSyncResult {
+    let servers: Vec<&McpServer> = config.get_servers_for_client(client_id.as_str());
+    let manual_config = generate_warp_config(&servers);
+
+    ClientSyncResult {
+        client_id,
+        success: true, // Not a failure, just manual
+        servers_synced: servers.len(),
+        backup_path: None,
+        error: None,
+        manual_config: Some(manual_config),
+        warnings: vec![],
+        synced_servers: vec![],
+    }
+}
+
+/// Compute the content to write for a file-based client, without touching disk
+fn stage_client_write(
+    client_id: ClientId,
+    format: ConfigFormat,
+    config: &McpHubConfig,
+) -> Result<StagedClientWrite, String> {
+    // Get config path
+    let config_path = get_client_config_path(client_id).map_err(|e| e.to_string())?;
+
+    // Get servers enabled for this client, fitted to what it can run
+    let (adapted, warnings) =
+        adapt_servers_for_client(client_id, &config.get_servers_for_client(client_id.as_str()));
+    let servers: Vec<&McpServer> = adapted.iter().collect();
+
+    // Keep the raw content so a transactional sync can roll back
+    let original = read_original_content(&config_path)
+        .map_err(|e| format!("Failed to read existing config: {}", e))?;
+
+    // Read existing config (to preserve other settings)
+    let existing_config = read_existing_config(&config_path)
+        .map_err(|e| format!("Failed to read existing config: {}", e))?;
+
+    // Transform servers to client-specific format
+    // Resolve credentials when syncing to client configs
+    let options = TransformOptions {
+        resolve_credentials: true,
+        client: Some(client_id),
+        written_option_keys: written_option_keys(config, client_id),
+    };
+
+    let owned = owned_server_names(config, client_id);
+    let output = build_client_output(format, &servers, existing_config.as_ref(), &owned, &options)
+        .map_err(|e| format!("Failed to transform config: {}", e))?;
+
+    let pointer = client_servers_pointer(client_id, format);
+    let content = render_for_path(&config_path, original.as_deref(), &output, &pointer)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    Ok(StagedClientWrite {
+        client_id,
+        config_path,
+        content,
+        servers_synced: servers.len(),
+        synced_servers: servers.iter().map(|s| s.name.clone()).collect(),
+        original,
+        warnings,
     })
 }
 
-/// Update client settings after successful sync
-pub fn update_client_sync_status(
-    config: &mut McpHubConfig,
+/// Back up and write a staged client config
+fn apply_staged_write(staged: &StagedClientWrite, retention: usize) -> ClientSyncResult {
+    // Create backup if file exists
+    let backup_path = match create_backup(staged.client_id, &staged.config_path, retention) {
+        Ok(path) => path.map(|p| p.to_string_lossy().to_string()),
+        Err(e) => {
+            return failed_sync_result(
+                staged.client_id,
+                None,
+                format!("Failed to create backup: {}", e),
+            );
+        }
+    };
+
+    if let Err(e) = write_config_file(&staged.config_path, &staged.content) {
+        return failed_sync_result(
+            staged.client_id,
+            backup_path,
+            format!("Failed to write config: {}", e),
+        );
+    }
+
+    ClientSyncResult {
+        client_id: staged.client_id,
+        success: true,
+        servers_synced: staged.servers_synced,
+        backup_path,
+        error: None,
+        manual_config: None,
+        warnings: staged.warnings.clone(),
+        synced_servers: staged.synced_servers.clone(),
+    }
+}
+
+/// Put a client's config file back the way it was before a sync
+fn rollback_staged_write(staged: &StagedClientWrite) -> Result<(), SyncError> {
+    match &staged.original {
+        Some(content) => write_config_file(&staged.config_path, content),
+        // The sync created the file, so remove it again
+        None => {
+            note_own_write(&staged.config_path, None);
+            fs::remove_file(&staged.config_path).map_err(SyncError::from)
+        }
+    }
+}
+
+/// Sync configuration to a single client
+pub fn sync_to_client(
     client_id: ClientId,
-    config_path: &str,
-) {
-    let now = chrono::Utc::now().to_rfc3339();
+    config: &McpHubConfig,
+) -> ClientSyncResult {
+    // Check if client requires manual configuration
+    let detected = detect_client(client_id);
 
-    // Compute checksum of the written file
-    let checksum = if let Ok(content) = fs::read(config_path) {
-        Some(compute_checksum(&content))
-    } else {
-        None
+    if detected.sync_mode == SyncMode::ManualOnly {
+        return manual_sync_result(client_id, config);
+    }
+
+    let staged = match stage_client_write(client_id, detected.config_format, config) {
+        Ok(staged) => staged,
+        Err(e) => return failed_sync_result(client_id, None, e),
     };
 
-    let settings = config
-        .clients
-        .entry(client_id.as_str().to_string())
-        .or_insert_with(|| ClientSettings {
-            enabled: true,
-            config_path: config_path.to_string(),
-            last_sync: None,
-            last_sync_checksum: None,
-        });
+    apply_staged_write(&staged, config.preferences.backup_retention as usize)
+}
+
+/// Clients with sync enabled, in display order
+pub(crate) fn enabled_clients(config: &McpHubConfig) -> Vec<ClientId> {
+    ClientId::all_with_custom()
+        .into_iter()
+        .filter(|id| {
+            config
+                .clients
+                .get(id.as_str())
+                .map(|s| s.enabled)
+                .unwrap_or(true)
+        })
+        .collect()
+}
+
+/// Tally per-client results into a SyncResult
+pub(crate) fn summarize_sync_results(results: Vec<ClientSyncResult>) -> SyncResult {
+    let mut successful = 0;
+    let mut failed = 0;
+    let mut manual_required = 0;
+
+    for result in &results {
+        if result.manual_config.is_some() {
+            manual_required += 1;
+            successful += 1; // Manual configs are considered successful
+        } else if result.success {
+            successful += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    SyncResult {
+        total_clients: results.len(),
+        successful,
+        failed,
+        manual_required,
+        results,
+        rolled_back: false,
+        rollback_errors: vec![],
+        project_results: vec![],
+    }
+}
+
+/// Sync configuration to all enabled clients
+pub fn sync_to_all_clients(config: &McpHubConfig) -> SyncResult {
+    let results = enabled_clients(config)
+        .into_iter()
+        .map(|client_id| sync_to_client(client_id, config))
+        .collect();
+
+    let mut summary = summarize_sync_results(results);
+    summary.project_results = sync_to_all_projects(config);
+    summary
+}
+
+/// Sync configuration to all enabled clients as a single all-or-nothing operation.
+///
+/// Every client's new content is staged first; nothing is written unless all of
+/// them stage cleanly. If a write then fails, every client already written is
+/// restored to its pre-sync content and the rollback is reported in the result.
+/// Registered projects are synced only once every client has committed.
+pub fn sync_to_all_clients_transactional(config: &McpHubConfig) -> SyncResult {
+    let retention = config.preferences.backup_retention as usize;
+    let mut results = Vec::new();
+    let mut staged_writes = Vec::new();
+    let mut staging_error = None;
+
+    // Stage every client before writing anything
+    for client_id in enabled_clients(config) {
+        let detected = detect_client(client_id);
+
+        if detected.sync_mode == SyncMode::ManualOnly {
+            results.push(manual_sync_result(client_id, config));
+            continue;
+        }
+
+        match stage_client_write(client_id, detected.config_format, config) {
+            Ok(staged) => staged_writes.push(staged),
+            Err(e) => {
+                staging_error.get_or_insert_with(|| format!("{}: {}", client_id, e));
+                results.push(failed_sync_result(client_id, None, e));
+            }
+        }
+    }
+
+    if let Some(cause) = staging_error {
+        for staged in &staged_writes {
+            results.push(failed_sync_result(
+                staged.client_id,
+                None,
+                format!("Not synced: staging failed for {}", cause),
+            ));
+        }
+        return summarize_sync_results(results);
+    }
+
+    let outcome = commit_staged_writes(&staged_writes, |staged| {
+        apply_staged_write(staged, retention)
+    });
+    results.extend(outcome.results);
+
+    let mut summary = summarize_sync_results(results);
+    summary.rolled_back = outcome.rolled_back;
+    summary.rollback_errors = outcome.rollback_errors;
+    if !summary.rolled_back {
+        summary.project_results = sync_to_all_projects(config);
+    }
+    summary
+}
+
+/// Outcome of writing a batch of staged client configs
+struct CommitOutcome {
+    results: Vec<ClientSyncResult>,
+    rolled_back: bool,
+    rollback_errors: Vec<String>,
+}
+
+/// Write staged configs in order, rolling back every written one if any write fails
+fn commit_staged_writes<F>(staged_writes: &[StagedClientWrite], mut apply: F) -> CommitOutcome
+where
+    F: FnMut(&StagedClientWrite) -> ClientSyncResult,
+{
+    let mut written: Vec<(&StagedClientWrite, ClientSyncResult)> = Vec::new();
+    let mut write_failure = None;
+
+    // Stop at the first failure
+    for (index, staged) in staged_writes.iter().enumerate() {
+        let result = apply(staged);
+        if result.success {
+            written.push((staged, result));
+        } else {
+            write_failure = Some((index, result));
+            break;
+        }
+    }
+
+    let (failed_index, failed_result) = match write_failure {
+        Some(failure) => failure,
+        None => {
+            return CommitOutcome {
+                results: written.into_iter().map(|(_, result)| result).collect(),
+                rolled_back: false,
+                rollback_errors: vec![],
+            };
+        }
+    };
+
+    // Roll back every client that was already written, newest first
+    let cause = format!(
+        "{}: {}",
+        failed_result.client_id,
+        failed_result.error.as_deref().unwrap_or("Unknown sync error")
+    );
+    let mut rollback_errors = Vec::new();
+    let mut results = Vec::new();
+
+    for (staged, result) in written.into_iter().rev() {
+        let error = match rollback_staged_write(staged) {
+            Ok(()) => format!("Rolled back after {}", cause),
+            Err(e) => {
+                let message = format!("Failed to roll back {}: {}", staged.client_id, e);
+                rollback_errors.push(message.clone());
+                message
+            }
+        };
+        results.push(failed_sync_result(staged.client_id, result.backup_path, error));
+    }
+    results.reverse();
+
+    results.push(failed_result);
+    for staged in &staged_writes[failed_index + 1..] {
+        results.push(failed_sync_result(
+            staged.client_id,
+            None,
+            format!("Not synced: rolled back after {}", cause),
+        ));
+    }
+
+    CommitOutcome {
+        results,
+        rolled_back: true,
+        rollback_errors,
+    }
+}
+
+/// Preview what syncing a single client would change, without writing anything
+pub fn preview_sync_to_client(client_id: ClientId, config: &McpHubConfig) -> ClientSyncPreview {
+    let detected = detect_client(client_id);
+    let servers: Vec<&McpServer> = config.get_servers_for_client(client_id.as_str());
+
+    if detected.sync_mode == SyncMode::ManualOnly {
+        let mut preview = ClientSyncPreview::new(client_id);
+        preview.manual_config = Some(generate_warp_config(&servers));
+        return preview;
+    }
+
+    let (adapted, warnings) = adapt_servers_for_client(client_id, &servers);
+    let servers: Vec<&McpServer> = adapted.iter().collect();
+
+    let owned = owned_server_names(config, client_id);
+    let written = written_option_keys(config, client_id);
+    match get_client_config_path(client_id) {
+        Ok(path) => {
+            let format = detected.config_format;
+            let mut preview =
+                preview_config_file(client_id, format, &path, &servers, &owned, written.as_ref());
+            preview.warnings = warnings;
+            preview
+        }
+        Err(e) => {
+            let mut preview = ClientSyncPreview::new(client_id);
+            preview.error = Some(e.to_string());
+            preview
+        }
+    }
+}
+
+/// Preview what syncing all enabled clients would change
+pub fn preview_sync_to_all_clients(config: &McpHubConfig) -> SyncPreview {
+    let results: Vec<ClientSyncPreview> = enabled_clients(config)
+        .into_iter()
+        .map(|id| preview_sync_to_client(id, config))
+        .collect();
+
+    SyncPreview {
+        total_clients: results.len(),
+        clients_with_changes: results.iter().filter(|r| r.has_changes()).count(),
+        results,
+    }
+}
+
+/// List the backup history of a client's config file, newest first
+pub fn list_client_backups(client_id: ClientId) -> Result<Vec<BackupEntry>, SyncError> {
+    let backup_dir =
+        client_backup_dir(client_id).map_err(|e| SyncError::BackupError(e.to_string()))?;
+
+    list_snapshots(&backup_dir, client_id).map_err(|e| SyncError::BackupError(e.to_string()))
+}
+
+/// Restore a client's config file from one of its backups.
+///
+/// The current file is snapshotted first, so a restore can itself be undone.
+pub fn restore_client_backup(
+    client_id: ClientId,
+    backup_id: &str,
+    retention: usize,
+) -> Result<RestoreResult, SyncError> {
+    let backup_dir =
+        client_backup_dir(client_id).map_err(|e| SyncError::BackupError(e.to_string()))?;
+    let config_path =
+        get_client_config_path(client_id).map_err(|e| SyncError::ClientNotFound(e.to_string()))?;
+
+    restore_snapshot(client_id, &backup_dir, backup_id, &config_path, retention)
+}
+
+/// Restore `target` from a snapshot in `backup_dir`, backing up the current file first
+fn restore_snapshot(
+    client_id: ClientId,
+    backup_dir: &Path,
+    backup_id: &str,
+    target: &PathBuf,
+    retention: usize,
+) -> Result<RestoreResult, SyncError> {
+    let source = snapshot_path(backup_dir, backup_id)
+        .map_err(|e| SyncError::BackupError(e.to_string()))?;
+    let content = fs::read_to_string(&source).map_err(|e| SyncError::ReadError(e.to_string()))?;
+
+    // Keep at least one more snapshot than usual so the restored-from one
+    // isn't pruned by the safety backup
+    let safety_backup = create_snapshot(backup_dir, target, retention.max(1) + 1)
+        .map_err(|e| SyncError::BackupError(e.to_string()))?;
+
+    write_config_file(target, &content)?;
+
+    Ok(RestoreResult {
+        client_id,
+        restored_from: source.to_string_lossy().to_string(),
+        config_path: target.to_string_lossy().to_string(),
+        safety_backup: safety_backup.map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+/// Parse servers from a client's raw config
+fn parse_servers_from_raw_config(
+    raw_config: &HashMap<String, Value>,
+    source_client: ClientId,
+) -> Vec<McpServer> {
+    let mut servers = Vec::new();
+
+    for (name, config) in raw_config {
+        if let Some(server) = parse_single_server(name, config, source_client) {
+            servers.push(server);
+        }
+    }
+
+    servers
+}
+
+/// URL of a remote server entry, under whichever key the client uses
+fn entry_url(entry: &Value) -> Option<&str> {
+    ["url", "serverUrl", "httpUrl"]
+        .iter()
+        .find_map(|key| entry.get(*key).and_then(|v| v.as_str()))
+}
+
+/// Whether a remote entry speaks Streamable HTTP rather than SSE.
+///
+/// An explicit `type`/`transport` wins; untyped entries (Cursor, Windsurf)
+/// are treated as SSE only when the URL ends in `/sse`. Gemini CLI's
+/// `httpUrl` key always means Streamable HTTP.
+fn is_http_entry(entry: &Value, url: &str) -> bool {
+    if entry.get("httpUrl").is_some() {
+        return true;
+    }
+
+    let declared = entry
+        .get("type")
+        .or_else(|| entry.get("transport"))
+        .and_then(|v| v.as_str());
+
+    match declared {
+        Some("sse") => false,
+        Some("http") | Some("streamable-http") | Some("streamableHttp") | Some("streamable_http") => {
+            true
+        }
+        _ => !url.trim_end_matches('/').ends_with("/sse"),
+    }
+}
+
+/// Parse a single server configuration
+fn parse_single_server(name: &str, entry: &Value, source_client: ClientId) -> Option<McpServer> {
+    let config = &match source_client {
+        ClientId::Custom(id) => {
+            let descriptor = custom_client(id)?;
+            from_descriptor_entry(&descriptor.fields, entry)
+        }
+        _ => standard_entry(entry),
+    };
+    let obj = config.as_object()?;
+
+    // Determine transport type
+    let transport = if let Some(url) = entry_url(config) {
+        // Remote transport: SSE or Streamable HTTP
+        let headers: HashMap<String, String> = obj
+            .get("headers")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
+        let is_http = match source_client {
+            // Codex has no SSE support, so its remotes are always Streamable HTTP
+            ClientId::Codex => true,
+            // Gemini CLI's plain `url` always means SSE
+            ClientId::GeminiCli => obj.contains_key("httpUrl"),
+            _ => is_http_entry(config, url),
+        };
+
+        if is_http {
+            Transport::Http {
+                url: url.to_string(),
+                headers,
+            }
+        } else {
+            Transport::Sse {
+                url: url.to_string(),
+                headers,
+            }
+        }
+    } else if let Some(command) = obj.get("command").and_then(|v| v.as_str()) {
+        // Stdio transport
+        let args: Vec<String> = obj
+            .get("args")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
+        let env: HashMap<String, String> = obj
+            .get("env")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
+        Transport::Stdio {
+            command: command.to_string(),
+            args,
+            env,
+        }
+    } else {
+        // Unknown format
+        return None;
+    };
+
+    // Determine source type from command/args if possible
+    let source = match &transport {
+        Transport::Sse { url, .. } | Transport::Http { url, .. } => {
+            ServerSource::Remote { url: url.clone() }
+        }
+        Transport::Stdio { command, args, .. } => {
+            if command == "npx" || command.ends_with("/npx") {
+                // NPM package
+                let package = args.iter()
+                    .skip_while(|a| a.starts_with('-'))
+                    .next()
+                    .cloned()
+                    .unwrap_or_else(|| name.to_string());
+                ServerSource::Npm { package, version: None }
+            } else if command == "uvx" || command.ends_with("/uvx") {
+                // Python uvx package
+                let package = args.first().cloned().unwrap_or_else(|| name.to_string());
+                ServerSource::Uvx { package }
+            } else if command == "docker" || command.ends_with("/docker") {
+                // Docker image
+                let image = args.iter()
+                    .skip_while(|a| *a != "run")
+                    .skip(1)
+                    .skip_while(|a| a.starts_with('-'))
+                    .next()
+                    .cloned()
+                    .unwrap_or_else(|| name.to_string());
+                ServerSource::Docker { image }
+            } else {
+                // Assume local path
+                ServerSource::Local {
+                    path: format!("{} {}", command, args.join(" ")),
+                }
+            }
+        }
+    };
+
+    let mut server = McpServer::new(name.to_string(), source, transport);
+    server.description = Some(format!("Imported from {}", source_client.display_name()));
+
+    // Enable for the source client by default
+    server.enable_for_client(source_client.as_str());
+
+    let options = client_option_keys(Some(source_client)).read(entry);
+    server.set_client_options(source_client.as_str(), Some(options));
+
+    Some(server)
+}
+
+/// Import servers from a client's configuration
+pub fn import_from_client(
+    client_id: ClientId,
+    config: &mut McpHubConfig,
+    overwrite_existing: bool,
+) -> Result<ImportResult, SyncError> {
+    use crate::services::client_detector::get_client_config_info;
+
+    // Get client config info
+    let config_info = get_client_config_info(client_id)
+        .map_err(|e| SyncError::ReadError(e.to_string()))?
+        .ok_or_else(|| SyncError::ReadError("No config found for client".to_string()))?;
+
+    let raw_config = config_info.raw_config
+        .ok_or_else(|| SyncError::ParseError("Could not parse server configs".to_string()))?;
+
+    // Parse servers from raw config
+    let imported_servers = parse_servers_from_raw_config(&raw_config, client_id);
+
+    Ok(merge_client_servers(config, client_id, imported_servers, overwrite_existing))
+}
+
+/// Add servers imported from a client's config to the central config.
+///
+/// A server whose name already exists is skipped, or with `overwrite_existing`
+/// updated in place: it takes the imported transport and source and is enabled
+/// for the client, keeping its ID (referenced by projects and profiles),
+/// enablement and per-client settings.
+fn merge_client_servers(
+    config: &mut McpHubConfig,
+    client_id: ClientId,
+    imported_servers: Vec<McpServer>,
+    overwrite_existing: bool,
+) -> ImportResult {
+    let mut servers_imported = 0;
+    let mut skipped_existing = 0;
+    let mut server_names = Vec::new();
+
+    for server in imported_servers {
+        if let Some(existing) = config.servers.iter_mut().find(|s| s.name == server.name) {
+            if !overwrite_existing {
+                skipped_existing += 1;
+                continue;
+            }
+
+            existing.transport = server.transport;
+            existing.source = server.source;
+            existing.updated_at = chrono::Utc::now().to_rfc3339();
+            // The entry is in the client's config, so keep syncing it there
+            existing.enable_for_client(client_id.as_str());
+        } else {
+            config.servers.push(server.clone());
+        }
+
+        server_names.push(server.name);
+        servers_imported += 1;
+    }
+
+    ImportResult {
+        client_id,
+        servers_imported,
+        server_names,
+        skipped_existing,
+    }
+}
+
+/// Update client settings after a successful sync that wrote `synced_servers`
+pub fn update_client_sync_status(
+    config: &mut McpHubConfig,
+    client_id: ClientId,
+    config_path: &str,
+    synced_servers: &[String],
+) {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    // Compute checksum of the written file
+    let checksum = if let Ok(content) = fs::read(config_path) {
+        Some(compute_checksum(&content))
+    } else {
+        None
+    };
+
+    // Record which entries Nexus now owns: only those it wrote, not servers
+    // skipped for the client's capabilities (a hand-added entry may share the name)
+    let managed = synced_servers.to_vec();
+
+    // Hash each managed entry as written, for per-server drift detection
+    let path = PathBuf::from(config_path);
+    let format = config_format_for_path(client_id, &path);
+    let server_hashes = server_entry_hashes(client_id, format, &path).map(|hashes| {
+        hashes
+            .into_iter()
+            .filter(|(name, _)| managed.contains(name))
+            .collect()
+    });
+
+    // Remember which option keys came from Nexus, so clearing one there later
+    // removes it from the entry instead of keeping the old value
+    let keys = client_option_keys(Some(client_id));
+    let option_keys = config
+        .servers
+        .iter()
+        .filter(|s| managed.contains(&s.name))
+        .filter_map(|s| {
+            let options = s.client_options.get(client_id.as_str())?;
+            Some((s.name.clone(), keys.set_keys(options)))
+        })
+        .filter(|(_, keys)| !keys.is_empty())
+        .collect();
+    let option_keys = Some(option_keys);
+
+    let settings = config
+        .clients
+        .entry(client_id.as_str().to_string())
+        .or_insert_with(|| ClientSettings {
+            enabled: true,
+            config_path: config_path.to_string(),
+            last_sync: None,
+            last_sync_checksum: None,
+            managed_servers: None,
+            server_hashes: None,
+            option_keys: None,
+        });
+
+    settings.config_path = config_path.to_string();
+    settings.last_sync = Some(now);
+    settings.last_sync_checksum = checksum;
+    settings.managed_servers = Some(managed);
+    settings.server_hashes = server_hashes;
+    settings.option_keys = option_keys;
+}
+
+/// Record sync status for every client that was written successfully
+pub fn record_sync_results(config: &mut McpHubConfig, result: &SyncResult) {
+    for client_result in &result.results {
+        if client_result.success && client_result.manual_config.is_none() {
+            if let Ok(path) = get_client_config_path(client_result.client_id) {
+                let path = path.to_string_lossy();
+                let synced = &client_result.synced_servers;
+                update_client_sync_status(config, client_result.client_id, &path, synced);
+            }
+        }
+    }
+
+    record_project_sync_results(config, &result.project_results);
+}
+
+/// Value at `pointer` (a list of object keys) inside a document
+fn value_at<'a>(doc: &'a Value, pointer: &[String]) -> Option<&'a Value> {
+    pointer.iter().try_fold(doc, |value, key| value.get(key))
+}
+
+/// Set the value at `pointer`, creating (or replacing non-object) parents as needed
+fn set_value_at(doc: &mut Value, pointer: &[String], value: Value) {
+    match pointer.split_first() {
+        None => *doc = value,
+        Some((key, rest)) => {
+            if !doc.is_object() {
+                *doc = json!({});
+            }
+            if let Value::Object(map) = doc {
+                let child = map.entry(key.clone()).or_insert(Value::Null);
+                set_value_at(child, rest, value);
+            }
+        }
+    }
+}
+
+/// Whether a transport carries keychain references that sync would resolve
+fn uses_keychain_credentials(transport: &Transport) -> bool {
+    let values = match transport {
+        Transport::Stdio { env, .. } => env,
+        Transport::Sse { headers, .. } | Transport::Http { headers, .. } => headers,
+    };
+
+    values.values().any(|v| is_keychain_reference(v))
+}
+
+/// Build the full document to write for a project-level config,
+/// merging into the existing document at the target's server map
+fn build_project_output(
+    project_path: &str,
+    target: ProjectTarget,
+    servers: &[&McpServer],
+    existing_config: Option<&Value>,
+    owned: &HashSet<String>,
+    options: &TransformOptions,
+) -> Result<Value, SyncError> {
+    let mut mcp_servers = Map::new();
+
+    for server in servers {
+        let server_config = transform_server_to_standard(server, options)?;
+        mcp_servers.insert(server.name.clone(), server_config);
+    }
+
+    let pointer = target.servers_pointer(project_path);
+    let mut config = existing_config.cloned().unwrap_or_else(|| json!({}));
+    let merged = merge_server_entries(value_at(&config, &pointer), mcp_servers, owned);
+    set_value_at(&mut config, &pointer, merged);
+
+    Ok(config)
+}
+
+/// Sync a project's servers to one of its project-level config files
+fn sync_project_target(
+    project: &ProjectSettings,
+    target: ProjectTarget,
+    servers: &[&McpServer],
+    retention: usize,
+) -> ProjectSyncResult {
+    let mut result = ProjectSyncResult {
+        project_path: project.path.clone(),
+        target,
+        success: false,
+        servers_synced: 0,
+        config_path: None,
+        backup_path: None,
+        error: None,
+    };
+
+    let config_path = match get_project_config_path(target, Path::new(&project.path)) {
+        Ok(path) => path,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    result.config_path = Some(config_path.to_string_lossy().to_string());
+
+    let client_id = target.client_id();
+
+    // Files in the project tree tend to get committed, so never put secrets in them
+    if target.is_shared() {
+        let with_secrets = servers
+            .iter()
+            .find(|s| uses_keychain_credentials(&s.transport_for_client(client_id.as_str())));
+        if let Some(server) = with_secrets {
+            result.error = Some(format!(
+                "Server '{}' uses keychain credentials, which would be written in plain text \
+                 to a shared project file; use the claude-code-local target instead",
+                server.name
+            ));
+            return result;
+        }
+    }
+
+    let original = match read_original_content(&config_path) {
+        Ok(content) => content,
+        Err(e) => {
+            result.error = Some(format!("Failed to read existing config: {}", e));
+            return result;
+        }
+    };
+    let existing_config = match read_existing_config(&config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            result.error = Some(format!("Failed to read existing config: {}", e));
+            return result;
+        }
+    };
+
+    let options = TransformOptions {
+        resolve_credentials: true,
+        client: Some(client_id),
+        ..Default::default()
+    };
+    let owned: HashSet<String> = project
+        .managed_servers
+        .get(target.as_str())
+        .map(|names| names.iter().cloned().collect())
+        .unwrap_or_default();
+
+    let output = match build_project_output(
+        &project.path,
+        target,
+        servers,
+        existing_config.as_ref(),
+        &owned,
+        &options,
+    ) {
+        Ok(o) => o,
+        Err(e) => {
+            result.error = Some(format!("Failed to transform config: {}", e));
+            return result;
+        }
+    };
+
+    let pointer = target.servers_pointer(&project.path);
+    let content = match render_config(original.as_deref(), &output, &pointer) {
+        Ok(c) => c,
+        Err(e) => {
+            result.error = Some(format!("Failed to serialize config: {}", e));
+            return result;
+        }
+    };
+
+    let backup = project_backup_dir(&project.path, target)
+        .and_then(|dir| create_snapshot(&dir, &config_path, retention));
+    match backup {
+        Ok(path) => result.backup_path = path.map(|p| p.to_string_lossy().to_string()),
+        Err(e) => {
+            result.error = Some(format!("Failed to create backup: {}", e));
+            return result;
+        }
+    }
+
+    if let Err(e) = write_config_file(&config_path, &content) {
+        result.error = Some(format!("Failed to write config: {}", e));
+        return result;
+    }
+
+    result.success = true;
+    result.servers_synced = servers.len();
+    result
+}
+
+/// Sync a registered project's selected servers to each of its project-level configs
+pub fn sync_to_project(
+    config: &McpHubConfig,
+    project_path: &str,
+) -> Result<Vec<ProjectSyncResult>, SyncError> {
+    let project = config
+        .get_project(project_path)
+        .ok_or_else(|| SyncError::ProjectNotFound(project_path.to_string()))?;
+
+    let servers = config.get_servers_for_project(project_path);
+    let retention = config.preferences.backup_retention as usize;
+
+    Ok(project
+        .targets
+        .iter()
+        .map(|target| sync_project_target(project, *target, &servers, retention))
+        .collect())
+}
+
+/// Sync every registered project
+pub fn sync_to_all_projects(config: &McpHubConfig) -> Vec<ProjectSyncResult> {
+    config
+        .projects
+        .iter()
+        .flat_map(|project| sync_to_project(config, &project.path).unwrap_or_default())
+        .collect()
+}
+
+/// Record ownership and sync time for every project config that was written successfully
+pub fn record_project_sync_results(config: &mut McpHubConfig, results: &[ProjectSyncResult]) {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    for result in results.iter().filter(|r| r.success) {
+        let managed: Vec<String> = config
+            .get_servers_for_project(&result.project_path)
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+
+        if let Some(project) = config.get_project_mut(&result.project_path) {
+            project
+                .managed_servers
+                .insert(result.target.as_str().to_string(), managed);
+            project.last_sync = Some(now.clone());
+        }
+    }
+}
+
+/// Import servers from one of a project's config files and select them for the project.
+///
+/// Imported servers are not enabled for any client globally, so they only
+/// end up in this project's configs.
+pub fn import_from_project(
+    config: &mut McpHubConfig,
+    project_path: &str,
+    target: ProjectTarget,
+    overwrite_existing: bool,
+) -> Result<ImportResult, SyncError> {
+    if config.get_project(project_path).is_none() {
+        return Err(SyncError::ProjectNotFound(project_path.to_string()));
+    }
+
+    let config_path = get_project_config_path(target, Path::new(project_path))
+        .map_err(|e| SyncError::ReadError(e.to_string()))?;
+    let document = read_existing_config(&config_path)?
+        .ok_or_else(|| SyncError::ReadError("No config found for project".to_string()))?;
+
+    let raw_config: HashMap<String, Value> =
+        match value_at(&document, &target.servers_pointer(project_path)) {
+            Some(Value::Object(map)) => map.clone().into_iter().collect(),
+            _ => HashMap::new(),
+        };
+
+    let mut servers_imported = 0;
+    let mut skipped_existing = 0;
+    let mut server_names = Vec::new();
+    let mut selected = Vec::new();
+
+    for mut server in parse_servers_from_raw_config(&raw_config, target.client_id()) {
+        if let Some(existing) = config.servers.iter_mut().find(|s| s.name == server.name) {
+            selected.push(existing.id.to_string());
+            if !overwrite_existing {
+                // Reuse the central definition for this project
+                skipped_existing += 1;
+                continue;
+            }
+
+            // Keep the ID, enablement and per-client settings other clients and projects rely on
+            existing.transport = server.transport;
+            existing.updated_at = chrono::Utc::now().to_rfc3339();
+            server_names.push(server.name);
+            servers_imported += 1;
+            continue;
+        }
+
+        server.enabled_clients.clear();
+        selected.push(server.id.to_string());
+        server_names.push(server.name.clone());
+        config.servers.push(server);
+        servers_imported += 1;
+    }
+
+    if let Some(project) = config.get_project_mut(project_path) {
+        for id in selected {
+            if !project.servers.contains(&id) {
+                project.servers.push(id);
+            }
+        }
+    }
+
+    Ok(ImportResult {
+        client_id: target.client_id(),
+        servers_imported,
+        server_names,
+        skipped_existing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ArgsOverride, ClientOverride};
+    use tempfile::TempDir;
+
+    fn create_test_server(name: &str) -> McpServer {
+        McpServer::new(
+            name.to_string(),
+            ServerSource::Npm {
+                package: format!("@test/{}", name),
+                version: None,
+            },
+            Transport::Stdio {
+                command: "npx".to_string(),
+                args: vec!["-y".to_string(), format!("@test/{}", name)],
+                env: HashMap::new(),
+            },
+        )
+    }
+
+    fn create_test_sse_server(name: &str) -> McpServer {
+        McpServer::new(
+            name.to_string(),
+            ServerSource::Remote {
+                url: format!("https://api.example.com/{}", name),
+            },
+            Transport::Sse {
+                url: format!("https://api.example.com/{}/sse", name),
+                headers: HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]),
+            },
+        )
+    }
+
+    fn default_options() -> TransformOptions {
+        TransformOptions {
+            resolve_credentials: false,
+            client: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transform_server_to_standard_stdio() {
+        let server = create_test_server("filesystem");
+        let result = transform_server_to_standard(&server, &default_options()).unwrap();
+
+        assert!(result.is_object());
+        let obj = result.as_object().unwrap();
+        assert_eq!(obj.get("command").unwrap(), "npx");
+        assert!(obj.get("args").unwrap().is_array());
+    }
+
+    #[test]
+    fn test_transform_server_to_standard_sse() {
+        let server = create_test_sse_server("remote");
+        let result = transform_server_to_standard(&server, &default_options()).unwrap();
+
+        assert!(result.is_object());
+        let obj = result.as_object().unwrap();
+        assert_eq!(obj.get("transport").unwrap(), "sse");
+        assert!(obj.get("url").is_some());
+        assert!(obj.get("headers").is_some());
+    }
+
+    #[test]
+    fn test_transform_to_standard_format() {
+        let server1 = create_test_server("server1");
+        let server2 = create_test_server("server2");
+        let servers = vec![&server1, &server2];
+
+        let result = transform_to_standard_format(&servers, None, &HashSet::new(), &default_options()).unwrap();
+
+        assert!(result.get("mcpServers").is_some());
+        let mcp_servers = result.get("mcpServers").unwrap().as_object().unwrap();
+        assert!(mcp_servers.contains_key("server1"));
+        assert!(mcp_servers.contains_key("server2"));
+    }
+
+    #[test]
+    fn test_transform_to_vscode_format() {
+        let server = create_test_server("test");
+        let servers = vec![&server];
+
+        let result = transform_to_vscode_format(&servers, None, &HashSet::new(), &default_options()).unwrap();
+
+        assert!(result.get("mcp").is_some());
+        let mcp = result.get("mcp").unwrap();
+        assert!(mcp.get("servers").is_some());
+    }
+
+    #[test]
+    fn test_transform_to_vscode_format_preserves_existing() {
+        let server = create_test_server("test");
+        let servers = vec![&server];
+
+        let existing = json!({
+            "editor.fontSize": 14,
+            "mcp": {
+                "enabled": true
+            }
+        });
+
+        let result = transform_to_vscode_format(&servers, Some(&existing), &HashSet::new(), &default_options()).unwrap();
+
+        // Should preserve editor.fontSize
+        assert_eq!(result.get("editor.fontSize").unwrap(), 14);
+        // Should have mcp.servers
+        assert!(result.get("mcp").unwrap().get("servers").is_some());
+    }
+
+    #[test]
+    fn test_transform_to_continue_format() {
+        let server = create_test_server("test");
+        let servers = vec![&server];
+
+        let existing = json!({
+            "models": [{"title": "GPT-4"}],
+            "tabAutocompleteModel": {"title": "Codestral"}
+        });
+
+        let result = transform_to_continue_format(&servers, Some(&existing), &HashSet::new(), &default_options()).unwrap();
+
+        // Should preserve existing settings
+        assert!(result.get("models").is_some());
+        assert!(result.get("tabAutocompleteModel").is_some());
+        // Should have mcpServers
+        assert!(result.get("mcpServers").is_some());
+    }
+
+    #[test]
+    fn test_generate_warp_config() {
+        let server = create_test_server("test");
+        let servers = vec![&server];
+
+        let result = generate_warp_config(&servers);
+
+        assert!(result.contains("mcpServers"));
+        assert!(result.contains("test"));
+    }
+
+    #[test]
+    fn test_write_and_read_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("test.json");
+
+        let content = r#"{"test": "value"}"#;
+        write_config_file(&config_path, content).unwrap();
+
+        // Verify file exists and has correct content
+        let read_content = fs::read_to_string(&config_path).unwrap();
+        assert_eq!(read_content, content);
+    }
+
+    #[test]
+    fn test_write_config_file_keeps_sibling_temp_files_apart() {
+        let temp_dir = TempDir::new().unwrap();
+        let toml_path = temp_dir.path().join("config.toml");
+        let yaml_path = temp_dir.path().join("config.yaml");
+        // A leftover temp file of the other format must not be picked up
+        fs::write(temp_dir.path().join("config.yaml.tmp"), "stale: true").unwrap();
+
+        write_config_file(&toml_path, "a = 1").unwrap();
+        write_config_file(&yaml_path, "a: 1").unwrap();
+
+        assert_eq!(fs::read_to_string(&toml_path).unwrap(), "a = 1");
+        assert_eq!(fs::read_to_string(&yaml_path).unwrap(), "a: 1");
+        assert!(!temp_dir.path().join("config.toml.tmp").exists());
+        assert!(!temp_dir.path().join("config.json.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_config_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("test.json");
+
+        write_config_file(&config_path, "{}").unwrap();
+
+        let metadata = fs::metadata(&config_path).unwrap();
+        let mode = metadata.permissions().mode();
+
+        // Check that file is 0600 (user read/write only)
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_restore_snapshot_takes_safety_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+        let backup_dir = temp_dir.path().join("backups");
+
+        fs::write(&config_path, "good").unwrap();
+        let good = create_snapshot(&backup_dir, &config_path, 10).unwrap().unwrap();
+        fs::write(&config_path, "bad").unwrap();
+
+        let backup_id = good.file_name().unwrap().to_string_lossy().to_string();
+        let result =
+            restore_snapshot(ClientId::Cursor, &backup_dir, &backup_id, &config_path, 10).unwrap();
+
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "good");
+        let safety = result.safety_backup.unwrap();
+        assert_eq!(fs::read_to_string(&safety).unwrap(), "bad");
+        assert_eq!(list_snapshots(&backup_dir, ClientId::Cursor).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_restore_snapshot_unknown_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+        fs::write(&config_path, "current").unwrap();
+
+        let result = restore_snapshot(
+            ClientId::Cursor,
+            temp_dir.path(),
+            "20260101T000000000Z_mcp.json",
+            &config_path,
+            10,
+        );
+
+        assert!(matches!(result, Err(SyncError::BackupError(_))));
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "current");
+    }
+
+    #[test]
+    fn test_parse_single_server_stdio() {
+        let config = json!({
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+            "env": {"DEBUG": "true"}
+        });
+
+        let server = parse_single_server("filesystem", &config, ClientId::ClaudeCode).unwrap();
+
+        assert_eq!(server.name, "filesystem");
+        assert!(server.is_enabled_for_client("claude-code"));
+
+        match &server.transport {
+            Transport::Stdio { command, args, env } => {
+                assert_eq!(command, "npx");
+                assert_eq!(args.len(), 3);
+                assert_eq!(env.get("DEBUG").unwrap(), "true");
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
+
+    #[test]
+    fn test_imported_local_server_can_be_edited() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = crate::services::ConfigManager::with_path(temp_dir.path().to_path_buf());
+        manager.initialize().unwrap();
+
+        let entry = json!({"command": "node", "args": ["server.js"]});
+        let server = parse_single_server("local", &entry, ClientId::Cursor).unwrap();
+        assert_eq!(
+            server.source,
+            ServerSource::Local {
+                path: "node server.js".to_string()
+            }
+        );
+
+        // Imports save the server as parsed
+        manager
+            .modify(|config| {
+                config.add_server(server.clone());
+                Ok::<_, crate::services::ConfigError>(((), "Import".to_string()))
+            })
+            .unwrap();
+
+        let mut renamed = server;
+        renamed.name = "renamed".to_string();
+        manager.update_server(renamed).unwrap();
+        assert_eq!(manager.get_servers().unwrap()[0].name, "renamed");
+    }
+
+    #[test]
+    fn test_parse_single_server_sse() {
+        let config = json!({
+            "url": "https://api.example.com/mcp",
+            "transport": "sse",
+            "headers": {"Authorization": "Bearer token"}
+        });
+
+        let server = parse_single_server("remote", &config, ClientId::Cursor).unwrap();
+
+        assert_eq!(server.name, "remote");
+        assert!(server.is_enabled_for_client("cursor"));
+
+        match &server.transport {
+            Transport::Sse { url, headers } => {
+                assert_eq!(url, "https://api.example.com/mcp");
+                assert_eq!(headers.get("Authorization").unwrap(), "Bearer token");
+            }
+            _ => panic!("Expected Sse transport"),
+        }
+    }
+
+    #[test]
+    fn test_compute_checksum() {
+        let content1 = b"hello world";
+        let content2 = b"hello world";
+        let content3 = b"different";
+
+        let checksum1 = compute_checksum(content1);
+        let checksum2 = compute_checksum(content2);
+        let checksum3 = compute_checksum(content3);
+
+        assert_eq!(checksum1, checksum2);
+        assert_ne!(checksum1, checksum3);
+
+        // SHA-256, so values survive toolchain upgrades
+        assert_eq!(
+            checksum1,
+            "sha256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    fn synced_cursor_client(path: &Path) -> DetectedClient {
+        let mut detected = DetectedClient::new(ClientId::Cursor);
+        detected.config_path = Some(path.to_path_buf());
+        detected.config_exists = true;
+        detected
+    }
+
+    #[test]
+    fn test_client_sync_status_reports_drifted_servers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        fs::write(
+            &path,
+            r#"{"mcpServers": {"a": {"command": "a"}, "b": {"command": "b"}, "mine": {}}}"#,
+        )
+        .unwrap();
+
+        let mut config = McpHubConfig::default();
+        for name in ["a", "b"] {
+            let mut server = create_test_server(name);
+            server.enable_for_client("cursor");
+            config.add_server(server);
+        }
+        let synced = vec!["a".to_string(), "b".to_string()];
+        update_client_sync_status(&mut config, ClientId::Cursor, &path.to_string_lossy(), &synced);
+
+        // Only managed entries are recorded
+        let settings = config.clients.get("cursor").unwrap();
+        let recorded: Vec<&String> = settings.server_hashes.as_ref().unwrap().keys().collect();
+        assert_eq!(recorded, vec!["a", "b"]);
+
+        let detected = synced_cursor_client(&path);
+        let status = client_sync_status(&detected, Some(settings));
+        assert!(!status.externally_modified);
+
+        // Reformatting alone changes the file but no entry
+        fs::write(
+            &path,
+            r#"{"mcpServers": {"a": {"command": "a"},
+                "b": {"command": "b"}, "mine": {}}}"#,
+        )
+        .unwrap();
+        let status = client_sync_status(&detected, Some(settings));
+        assert!(status.externally_modified);
+        assert!(status.modified_servers.is_empty() && status.removed_servers.is_empty());
+
+        fs::write(&path, r#"{"mcpServers": {"a": {"command": "edited"}, "mine": {"x": 1}}}"#)
+            .unwrap();
+        let status = client_sync_status(&detected, Some(settings));
+        assert_eq!(status.modified_servers, vec!["a"]);
+        assert_eq!(status.removed_servers, vec!["b"]);
+    }
+
+    #[test]
+    fn test_sync_status_only_claims_written_servers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        // "remote" was skipped for the client, so this entry is the user's own
+        fs::write(
+            &path,
+            r#"{"mcpServers": {"a": {"command": "a"}, "remote": {"command": "mine"}}}"#,
+        )
+        .unwrap();
+
+        let mut config = McpHubConfig::default();
+        for mut server in [create_test_server("a"), create_test_sse_server("remote")] {
+            server.enable_for_client("cursor");
+            config.add_server(server);
+        }
+        let synced = vec!["a".to_string()];
+        update_client_sync_status(&mut config, ClientId::Cursor, &path.to_string_lossy(), &synced);
+
+        let settings = config.clients.get("cursor").unwrap();
+        assert_eq!(settings.managed_servers, Some(vec!["a".to_string()]));
+        let recorded: Vec<&String> = settings.server_hashes.as_ref().unwrap().keys().collect();
+        assert_eq!(recorded, vec!["a"]);
+        assert_eq!(owned_server_names(&config, ClientId::Cursor), owned(&["a"]));
+    }
+
+    #[test]
+    fn test_client_sync_status_ignores_legacy_checksum() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.json");
+        fs::write(&path, r#"{"mcpServers": {}}"#).unwrap();
+
+        let settings = ClientSettings {
+            enabled: true,
+            last_sync_checksum: Some("1f2e3d4c5b6a7988".to_string()),
+            ..Default::default()
+        };
+        let status = client_sync_status(&synced_cursor_client(&path), Some(&settings));
+        assert!(!status.externally_modified);
+        assert_eq!(status.last_sync_checksum.as_deref(), Some("1f2e3d4c5b6a7988"));
+    }
+
+    #[test]
+    fn test_sync_to_client_manual_only() {
+        let config = McpHubConfig::default();
+
+        let result = sync_to_client(ClientId::Warp, &config);
+
+        assert!(result.success);
+        assert!(result.manual_config.is_some());
+        assert!(result.error.is_none());
+    }
+
+    #[test]
+    fn test_extract_client_servers_continue_array() {
+        let config = json!({
+            "mcpServers": [
+                {"name": "filesystem", "command": "npx"},
+                {"command": "unnamed"}
+            ]
+        });
+
+        let servers = extract_servers_at(&config, &format_servers_pointer(ConfigFormat::Continue));
+        assert_eq!(servers.len(), 1);
+        assert!(servers.contains_key("filesystem"));
+    }
+
+    #[test]
+    fn test_preview_config_file_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+        let server = create_test_server("filesystem");
+
+        let preview = preview_config_file(
+            ClientId::Cursor,
+            ConfigFormat::Standard,
+            &config_path,
+            &[&server],
+            &HashSet::new(),
+            None,
+        );
+
+        assert!(preview.error.is_none());
+        assert_eq!(preview.added, vec!["filesystem".to_string()]);
+        assert!(preview.has_changes());
+        assert!(!config_path.exists());
+    }
+
+    #[test]
+    fn test_preview_config_file_field_level_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+
+        let mut changed = create_test_server("changed");
+        if let Transport::Stdio { env, .. } = &mut changed.transport {
+            env.insert("DEBUG".to_string(), "false".to_string());
+            env.insert("API_KEY".to_string(), "keychain:api-key".to_string());
+        }
+        let same = create_test_server("same");
+
+        let on_disk = json!({
+            "mcpServers": {
+                "changed": {
+                    "command": "node",
+                    "args": ["-y", "@test/changed"],
+                    "env": {"DEBUG": "true", "API_KEY": "secret", "OLD": "1"}
+                },
+                "same": {"command": "npx", "args": ["-y", "@test/same"]},
+                "stale": {"command": "uvx", "args": ["stale"]}
+            }
+        });
+        let original = serde_json::to_string_pretty(&on_disk).unwrap();
+        fs::write(&config_path, &original).unwrap();
+
+        let owned: HashSet<String> = ["changed", "same", "stale"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        let preview = preview_config_file(
+            ClientId::Cursor,
+            ConfigFormat::Standard,
+            &config_path,
+            &[&changed, &same],
+            &owned,
+            None,
+        );
+
+        assert!(preview.added.is_empty());
+        assert_eq!(preview.removed, vec!["stale".to_string()]);
+        assert_eq!(preview.unchanged, vec!["same".to_string()]);
+        assert_eq!(preview.changed.len(), 1);
+
+        let change = &preview.changed[0];
+        assert_eq!(change.name, "changed");
+        assert_eq!(
+            change.command,
+            Some(FieldChange {
+                before: Some(json!("node")),
+                after: Some(json!("npx")),
+            })
+        );
+        assert!(change.args.is_none());
+        assert!(change.url.is_none());
+
+        // API_KEY is a keychain reference, so only DEBUG counts as modified
+        let env = change.env.as_ref().unwrap();
+        assert!(env.added.is_empty());
+        assert_eq!(env.removed, vec!["OLD".to_string()]);
+        assert_eq!(env.modified, vec!["DEBUG".to_string()]);
+
+        // Preview must never write to disk
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_preview_config_file_transport_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+        fs::write(
+            &config_path,
+            r#"{"mcp": {"servers": {"remote": {"command": "npx", "args": ["remote"]}}}}"#,
+        )
+        .unwrap();
+
+        let server = create_test_sse_server("remote");
+        let preview = preview_config_file(
+            ClientId::Vscode,
+            ConfigFormat::Vscode,
+            &config_path,
+            &[&server],
+            &HashSet::new(),
+            None,
+        );
+
+        let change = &preview.changed[0];
+        assert_eq!(change.command.as_ref().unwrap().after, None);
+        assert_eq!(
+            change.url.as_ref().unwrap().after,
+            Some(json!("https://api.example.com/remote/sse"))
+        );
+        assert_eq!(
+            change.headers.as_ref().unwrap().added,
+            vec!["Authorization".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_preview_config_file_invalid_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("mcp.json");
+        fs::write(&config_path, "{ not json").unwrap();
+
+        let preview = preview_config_file(
+            ClientId::Cursor,
+            ConfigFormat::Standard,
+            &config_path,
+            &[],
+            &HashSet::new(),
+            None,
+        );
+
+        assert!(preview.error.is_some());
+        assert!(!preview.has_changes());
+    }
+
+    #[test]
+    fn test_preview_sync_to_client_manual_only() {
+        let config = McpHubConfig::default();
+
+        let preview = preview_sync_to_client(ClientId::Warp, &config);
+
+        assert!(preview.manual_config.is_some());
+        assert!(preview.config_path.is_none());
+        assert!(!preview.has_changes());
+    }
+
+    /// Trimmed-down ~/.claude.json as written by Claude Code itself
+    fn claude_code_fixture() -> Value {
+        json!({
+            "numStartups": 42,
+            "installMethod": "npm-global",
+            "autoUpdates": true,
+            "hasCompletedOnboarding": true,
+            "lastOnboardingVersion": "1.0.17",
+            "userID": "a1b2c3d4e5f6",
+            "tipsHistory": {"memory-command": 12, "shift-tab": 7},
+            "projects": {
+                "/Users/dev/work/api": {
+                    "allowedTools": ["Bash(npm test)"],
+                    "history": [
+                        {"display": "fix the flaky test", "pastedContents": {}}
+                    ],
+                    "mcpServers": {
+                        "project-db": {"command": "uvx", "args": ["mcp-server-postgres"]}
+                    },
+                    "hasTrustDialogAccepted": true
+                }
+            },
+            "mcpServers": {
+                "filesystem": {"command": "npx", "args": ["-y", "old-filesystem"]},
+                "hand-added": {"command": "node", "args": ["/opt/mine.js"]}
+            }
+        })
+    }
+
+    #[test]
+    fn test_standard_format_preserves_claude_code_state() {
+        let server = create_test_server("filesystem");
+        let existing = claude_code_fixture();
+
+        let result = transform_to_standard_format(
+            &[&server],
+            Some(&existing),
+            &HashSet::from(["filesystem".to_string()]),
+            &default_options(),
+        )
+        .unwrap();
+
+        // Every non-MCP key survives untouched, including nested project state
+        for key in [
+            "numStartups",
+            "installMethod",
+            "autoUpdates",
+            "hasCompletedOnboarding",
+            "lastOnboardingVersion",
+            "userID",
+            "tipsHistory",
+            "projects",
+        ] {
+            assert_eq!(result[key], existing[key], "key '{}' changed", key);
+        }
+
+        // Project-scoped servers are not the user-level mcpServers block
+        assert!(result["projects"]["/Users/dev/work/api"]["mcpServers"]
+            .get("filesystem")
+            .is_none());
+
+        let servers = result["mcpServers"].as_object().unwrap();
+        assert_eq!(servers["filesystem"]["args"], json!(["-y", "@test/filesystem"]));
+        assert!(servers.contains_key("hand-added"));
+    }
+
+    #[test]
+    fn test_standard_format_sync_round_trip_on_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".claude.json");
+        let existing = claude_code_fixture();
+        fs::write(&config_path, serde_json::to_string_pretty(&existing).unwrap()).unwrap();
+
+        let server = create_test_server("new-server");
+        let on_disk = read_existing_config(&config_path).unwrap();
+        let output = build_client_output(
+            ConfigFormat::Standard,
+            &[&server],
+            on_disk.as_ref(),
+            &HashSet::new(),
+            &default_options(),
+        )
+        .unwrap();
+        write_config_file(&config_path, &serde_json::to_string_pretty(&output).unwrap()).unwrap();
+
+        let written = read_existing_config(&config_path).unwrap().unwrap();
+        assert_eq!(written["projects"], existing["projects"]);
+        assert_eq!(written["numStartups"], 42);
+        assert_eq!(written["mcpServers"].as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_standard_format_without_existing_config() {
+        let server = create_test_server("filesystem");
+
+        let result =
+            transform_to_standard_format(&[&server], None, &HashSet::new(), &default_options())
+                .unwrap();
+
+        assert_eq!(result.as_object().unwrap().len(), 1);
+        assert!(result["mcpServers"].get("filesystem").is_some());
+    }
+
+    fn owned(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merge_server_entries_keeps_foreign_entries() {
+        let existing = json!({
+            "hand-added": {"command": "node", "args": ["mine.js"]},
+            "managed": {"command": "npx", "args": ["old"]},
+            "retired": {"command": "npx", "args": ["retired"]}
+        });
+        let mut managed = Map::new();
+        managed.insert("managed".to_string(), json!({"command": "npx", "args": ["new"]}));
+
+        let merged = merge_server_entries(
+            Some(&existing),
+            managed,
+            &owned(&["managed", "retired"]),
+        );
+
+        let merged = merged.as_object().unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["hand-added"], existing["hand-added"]);
+        assert_eq!(merged["managed"]["args"], json!(["new"]));
+        assert!(!merged.contains_key("retired"));
+    }
+
+    #[test]
+    fn test_merge_server_entries_array_shape() {
+        let existing = json!([
+            {"name": "hand-added", "command": "node"},
+            {"name": "managed", "command": "old"}
+        ]);
+        let mut managed = Map::new();
+        managed.insert("managed".to_string(), json!({"command": "npx"}));
+
+        let merged = merge_server_entries(Some(&existing), managed, &owned(&["managed"]));
+
+        let items = merged.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["name"], "hand-added");
+        assert_eq!(items[1], json!({"name": "managed", "command": "npx"}));
+    }
+
+    #[test]
+    fn test_standard_format_preserves_unmanaged_servers() {
+        let server = create_test_server("managed");
+        let existing = json!({
+            "mcpServers": {
+                "hand-added": {"command": "node", "args": ["mine.js"]},
+                "removed-from-hub": {"command": "npx"}
+            }
+        });
+
+        let result = transform_to_standard_format(
+            &[&server],
+            Some(&existing),
+            &owned(&["managed", "removed-from-hub"]),
+            &default_options(),
+        )
+        .unwrap();
+
+        let servers = result["mcpServers"].as_object().unwrap();
+        assert!(servers.contains_key("hand-added"));
+        assert!(servers.contains_key("managed"));
+        assert!(!servers.contains_key("removed-from-hub"));
+    }
+
+    #[test]
+    fn test_client_options_written_with_client_keys() {
+        let mut server = create_test_server("fs");
+        let options = ClientOptions {
+            disabled: Some(true),
+            auto_approve: Some(vec!["read_file".to_string()]),
+            timeout: Some(120),
+            ..Default::default()
+        };
+        server.set_client_options("cline", Some(options.clone()));
+        server.set_client_options("roo-code", Some(options));
+
+        let cline = transform_server_to_standard(&server, &options_for(ClientId::Cline)).unwrap();
+        assert_eq!(cline["disabled"], true);
+        assert_eq!(cline["autoApprove"], json!(["read_file"]));
+        assert_eq!(cline["timeout"], 120);
+
+        let roo = transform_server_to_standard(&server, &options_for(ClientId::RooCode)).unwrap();
+        assert_eq!(roo["alwaysAllow"], json!(["read_file"]));
+        assert!(roo.get("autoApprove").is_none());
+
+        // Options are per client
+        let cursor = transform_server_to_standard(&server, &options_for(ClientId::Cursor)).unwrap();
+        assert!(cursor.get("disabled").is_none());
+    }
+
+    #[test]
+    fn test_parse_single_server_captures_client_options() {
+        let entry = json!({
+            "command": "npx",
+            "args": ["-y", "@test/fs"],
+            "disabled": true,
+            "disabledTools": ["write_file"]
+        });
+
+        let server = parse_single_server("fs", &entry, ClientId::Windsurf).unwrap();
+        let options = &server.client_options["windsurf"];
+        assert_eq!(options.disabled, Some(true));
+        assert_eq!(options.disabled_tools, Some(vec!["write_file".to_string()]));
+
+        let plain = parse_single_server("fs", &entry, ClientId::ClaudeCode).unwrap();
+        assert!(plain.client_options.is_empty());
+    }
+
+    #[test]
+    fn test_standard_format_keeps_options_set_in_client() {
+        let server = create_test_server("fs");
+        let existing = json!({
+            "mcpServers": {
+                "fs": {"command": "old", "disabled": true, "autoApprove": ["read_file"]}
+            }
+        });
+
+        let result = transform_to_standard_format(
+            &[&server],
+            Some(&existing),
+            &owned(&["fs"]),
+            &options_for(ClientId::Cline),
+        )
+        .unwrap();
+        let entry = &result["mcpServers"]["fs"];
+        assert_eq!(entry["command"], "npx");
+        assert_eq!(entry["disabled"], true);
+        assert_eq!(entry["autoApprove"], json!(["read_file"]));
+
+        // Options set in Nexus take precedence; the rest keep the client's values
+        let mut server = server;
+        server.set_client_options(
+            "cline",
+            Some(ClientOptions {
+                disabled: Some(false),
+                ..Default::default()
+            }),
+        );
+        let result = transform_to_standard_format(
+            &[&server],
+            Some(&existing),
+            &owned(&["fs"]),
+            &options_for(ClientId::Cline),
+        )
+        .unwrap();
+        assert_eq!(result["mcpServers"]["fs"]["disabled"], false);
+        assert_eq!(result["mcpServers"]["fs"]["autoApprove"], json!(["read_file"]));
+    }
+
+    #[test]
+    fn test_standard_format_drops_options_cleared_in_nexus() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cline_mcp_settings.json");
+        let mut config = McpHubConfig::default();
+        let mut server = create_test_server("fs");
+        server.enable_for_client("cline");
+        server.set_client_options(
+            "cline",
+            Some(ClientOptions {
+                auto_approve: Some(vec!["read_file".to_string()]),
+                ..Default::default()
+            }),
+        );
+        config.add_server(server);
+
+        let options = options_for(ClientId::Cline);
+        let servers: Vec<&McpServer> = config.servers.iter().collect();
+        let mut written =
+            transform_to_standard_format(&servers, None, &owned(&[]), &options).unwrap();
+        // The user then sets a timeout in Cline
+        written["mcpServers"]["fs"]["timeout"] = json!(300);
+        fs::write(&path, written.to_string()).unwrap();
+        let synced = vec!["fs".to_string()];
+        update_client_sync_status(&mut config, ClientId::Cline, &path.to_string_lossy(), &synced);
+
+        let keys = written_option_keys(&config, ClientId::Cline).unwrap();
+        assert_eq!(keys["fs"], vec!["autoApprove".to_string()]);
+
+        // Clearing the list in Nexus removes it; the client's own timeout stays
+        config.servers[0].set_client_options("cline", None);
+        let options = TransformOptions {
+            written_option_keys: Some(keys),
+            ..options_for(ClientId::Cline)
+        };
+        let servers: Vec<&McpServer> = config.servers.iter().collect();
+        let result =
+            transform_to_standard_format(&servers, Some(&written), &owned(&["fs"]), &options)
+                .unwrap();
+        let entry = &result["mcpServers"]["fs"];
+        assert!(entry.get("autoApprove").is_none());
+        assert_eq!(entry["timeout"], 300);
+    }
+
+    #[test]
+    fn test_clients_with_option_keys_use_standard_format() {
+        // Only the standard format carries over options set in the client
+        for id in ClientId::all() {
+            if client_option_keys(Some(id)).keys().next().is_some() {
+                assert_eq!(DetectedClient::new(id).config_format, ConfigFormat::Standard);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vscode_and_continue_formats_preserve_unmanaged_servers() {
+        let server = create_test_server("managed");
+
+        let vscode_existing = json!({"mcp": {"servers": {"hand-added": {"command": "node"}}}});
+        let vscode = transform_to_vscode_format(
+            &[&server],
+            Some(&vscode_existing),
+            &owned(&["managed"]),
+            &default_options(),
+        )
+        .unwrap();
+        let vscode_servers = vscode["mcp"]["servers"].as_object().unwrap();
+        assert!(vscode_servers.contains_key("hand-added"));
+        assert!(vscode_servers.contains_key("managed"));
+
+        let continue_existing = json!({"mcpServers": {"hand-added": {"command": "node"}}});
+        let continue_config = transform_to_continue_format(
+            &[],
+            Some(&continue_existing),
+            &owned(&["managed"]),
+            &default_options(),
+        )
+        .unwrap();
+        let continue_servers = continue_config["mcpServers"].as_object().unwrap();
+        assert_eq!(continue_servers.len(), 1);
+        assert!(continue_servers.contains_key("hand-added"));
+    }
+
+    #[test]
+    fn test_owned_server_names_ledger_and_fallback() {
+        let mut config = McpHubConfig::default();
+        config.add_server(create_test_server("in-hub"));
+
+        // Without a ledger, only names known to the central config are claimed
+        let fallback = owned_server_names(&config, ClientId::Cursor);
+        assert_eq!(fallback, owned(&["in-hub"]));
+
+        update_client_sync_status(&mut config, ClientId::Cursor, "/nonexistent/mcp.json", &[]);
+        let settings = config.clients.get("cursor").unwrap();
+        assert_eq!(settings.managed_servers, Some(vec![]));
+
+        config.servers[0].enable_for_client("cursor");
+        let synced = ["in-hub".to_string()];
+        update_client_sync_status(&mut config, ClientId::Cursor, "/nonexistent/mcp.json", &synced);
+        assert_eq!(owned_server_names(&config, ClientId::Cursor), owned(&["in-hub"]));
+    }
+
+    fn staged_write(client_id: ClientId, path: PathBuf, original: Option<&str>) -> StagedClientWrite {
+        StagedClientWrite {
+            client_id,
+            config_path: path,
+            content: format!("{{\"synced\": \"{}\"}}", client_id),
+            servers_synced: 1,
+            synced_servers: vec![],
+            original: original.map(String::from),
+            warnings: vec![],
+        }
+    }
+
+    fn write_without_backup(staged: &StagedClientWrite) -> ClientSyncResult {
+        match write_config_file(&staged.config_path, &staged.content) {
+            Ok(()) => ClientSyncResult {
+                client_id: staged.client_id,
+                success: true,
+                servers_synced: staged.servers_synced,
+                backup_path: None,
+                error: None,
+                manual_config: None,
+                warnings: staged.warnings.clone(),
+                synced_servers: staged.synced_servers.clone(),
+            },
+            Err(e) => failed_sync_result(staged.client_id, None, e.to_string()),
+        }
+    }
 
-    settings.config_path = config_path.to_string();
-    settings.last_sync = Some(now);
-    settings.last_sync_checksum = checksum;
-}
+    #[test]
+    fn test_commit_staged_writes_all_succeed() {
+        let temp_dir = TempDir::new().unwrap();
+        let staged = vec![
+            staged_write(ClientId::ClaudeCode, temp_dir.path().join("a.json"), None),
+            staged_write(ClientId::Cursor, temp_dir.path().join("b.json"), None),
+        ];
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+        let outcome = commit_staged_writes(&staged, write_without_backup);
 
-    fn create_test_server(name: &str) -> McpServer {
-        McpServer::new(
-            name.to_string(),
-            ServerSource::Npm {
-                package: format!("@test/{}", name),
-                version: None,
-            },
-            Transport::Stdio {
-                command: "npx".to_string(),
-                args: vec!["-y".to_string(), format!("@test/{}", name)],
-                env: HashMap::new(),
-            },
-        )
+        assert!(!outcome.rolled_back);
+        assert!(outcome.results.iter().all(|r| r.success));
+        assert!(temp_dir.path().join("a.json").exists());
+        assert!(temp_dir.path().join("b.json").exists());
     }
 
-    fn create_test_sse_server(name: &str) -> McpServer {
+    #[test]
+    fn test_commit_staged_writes_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("claude.json");
+        let created = temp_dir.path().join("cursor.json");
+        let untouched = temp_dir.path().join("windsurf.json");
+        fs::write(&existing, "before").unwrap();
+        fs::write(&untouched, "untouched").unwrap();
+
+        let staged = vec![
+            staged_write(ClientId::ClaudeCode, existing.clone(), Some("before")),
+            staged_write(ClientId::Cursor, created.clone(), None),
+            staged_write(ClientId::Vscode, temp_dir.path().join("vscode.json"), None),
+            staged_write(ClientId::Windsurf, untouched.clone(), Some("untouched")),
+        ];
+
+        let outcome = commit_staged_writes(&staged, |s| {
+            if s.client_id == ClientId::Vscode {
+                failed_sync_result(s.client_id, None, "disk full".to_string())
+            } else {
+                write_without_backup(s)
+            }
+        });
+
+        assert!(outcome.rolled_back);
+        assert!(outcome.rollback_errors.is_empty());
+        assert_eq!(outcome.results.len(), 4);
+        assert!(outcome.results.iter().all(|r| !r.success));
+
+        // Already-written clients are restored, created files removed
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "before");
+        assert!(!created.exists());
+        assert_eq!(fs::read_to_string(&untouched).unwrap(), "untouched");
+
+        let ids: Vec<ClientId> = outcome.results.iter().map(|r| r.client_id).collect();
+        assert_eq!(
+            ids,
+            vec![ClientId::ClaudeCode, ClientId::Cursor, ClientId::Vscode, ClientId::Windsurf]
+        );
+        assert!(outcome.results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Rolled back after vscode: disk full"));
+    }
+
+    #[test]
+    fn test_summarize_sync_results_counts() {
+        let results = vec![
+            failed_sync_result(ClientId::Cursor, None, "boom".to_string()),
+            manual_sync_result(ClientId::Warp, &McpHubConfig::default()),
+        ];
+
+        let summary = summarize_sync_results(results);
+
+        assert_eq!(summary.total_clients, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.successful, 1);
+        assert_eq!(summary.manual_required, 1);
+        assert!(!summary.rolled_back);
+    }
+
+    fn create_test_http_server(name: &str) -> McpServer {
         McpServer::new(
             name.to_string(),
             ServerSource::Remote {
-                url: format!("https://api.example.com/{}", name),
+                url: format!("https://api.example.com/{}/mcp", name),
             },
-            Transport::Sse {
-                url: format!("https://api.example.com/{}/sse", name),
+            Transport::Http {
+                url: format!("https://api.example.com/{}/mcp", name),
                 headers: HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]),
             },
         )
     }
 
-    fn default_options() -> TransformOptions {
-        TransformOptions { resolve_credentials: false }
+    fn options_for(client: ClientId) -> TransformOptions {
+        TransformOptions {
+            resolve_credentials: false,
+            client: Some(client),
+            ..Default::default()
+        }
     }
 
     #[test]
-    fn test_transform_server_to_standard_stdio() {
-        let server = create_test_server("filesystem");
-        let result = transform_server_to_standard(&server, &default_options()).unwrap();
+    fn test_transform_http_server_per_client() {
+        let server = create_test_http_server("remote");
+        let url = json!("https://api.example.com/remote/mcp");
+
+        for client in [ClientId::ClaudeCode, ClientId::Vscode, ClientId::ClaudeDesktop] {
+            let entry = transform_server_to_standard(&server, &options_for(client)).unwrap();
+            assert_eq!(entry["type"], "http", "{}", client);
+            assert_eq!(entry["url"], url);
+            assert_eq!(entry["headers"]["Authorization"], "Bearer token");
+        }
 
-        assert!(result.is_object());
-        let obj = result.as_object().unwrap();
-        assert_eq!(obj.get("command").unwrap(), "npx");
-        assert!(obj.get("args").unwrap().is_array());
-    }
+        let windsurf = transform_server_to_standard(&server, &options_for(ClientId::Windsurf)).unwrap();
+        assert_eq!(windsurf["serverUrl"], url);
+        assert!(windsurf.get("url").is_none());
+        assert!(windsurf.get("type").is_none());
 
-    #[test]
-    fn test_transform_server_to_standard_sse() {
-        let server = create_test_sse_server("remote");
-        let result = transform_server_to_standard(&server, &default_options()).unwrap();
+        let cursor = transform_server_to_standard(&server, &options_for(ClientId::Cursor)).unwrap();
+        assert_eq!(cursor["url"], url);
+        assert!(cursor.get("type").is_none());
 
-        assert!(result.is_object());
-        let obj = result.as_object().unwrap();
-        assert_eq!(obj.get("transport").unwrap(), "sse");
-        assert!(obj.get("url").is_some());
-        assert!(obj.get("headers").is_some());
+        let cline = transform_server_to_standard(&server, &options_for(ClientId::Cline)).unwrap();
+        assert_eq!(cline["type"], "streamableHttp");
+
+        let generic = transform_server_to_standard(&server, &default_options()).unwrap();
+        assert_eq!(generic["type"], "http");
+        assert!(generic.get("transport").is_none());
     }
 
     #[test]
-    fn test_transform_to_standard_format() {
-        let server1 = create_test_server("server1");
-        let server2 = create_test_server("server2");
-        let servers = vec![&server1, &server2];
+    fn test_parse_single_server_http() {
+        let typed = json!({"type": "http", "url": "https://api.example.com/mcp"});
+        let server = parse_single_server("typed", &typed, ClientId::ClaudeCode).unwrap();
+        assert!(matches!(server.transport, Transport::Http { .. }));
+        assert!(matches!(server.source, ServerSource::Remote { .. }));
+
+        let windsurf = json!({"serverUrl": "https://api.example.com/mcp"});
+        let server = parse_single_server("windsurf", &windsurf, ClientId::Windsurf).unwrap();
+        match &server.transport {
+            Transport::Http { url, .. } => assert_eq!(url, "https://api.example.com/mcp"),
+            _ => panic!("Expected Http transport"),
+        }
 
-        let result = transform_to_standard_format(&servers, &default_options()).unwrap();
+        let untyped_sse = json!({"url": "https://api.example.com/sse"});
+        let server = parse_single_server("legacy", &untyped_sse, ClientId::Cursor).unwrap();
+        assert!(matches!(server.transport, Transport::Sse { .. }));
+    }
 
-        assert!(result.get("mcpServers").is_some());
-        let mcp_servers = result.get("mcpServers").unwrap().as_object().unwrap();
-        assert!(mcp_servers.contains_key("server1"));
-        assert!(mcp_servers.contains_key("server2"));
+    #[test]
+    fn test_gemini_cli_remote_keys() {
+        let http = create_test_http_server("remote");
+        let entry = transform_server_to_standard(&http, &options_for(ClientId::GeminiCli)).unwrap();
+        assert_eq!(entry["httpUrl"], "https://api.example.com/remote/mcp");
+        assert!(entry.get("url").is_none());
+        assert!(entry.get("type").is_none());
+
+        let sse = json!({"url": "https://api.example.com/events"});
+        let server = parse_single_server("events", &sse, ClientId::GeminiCli).unwrap();
+        assert!(matches!(server.transport, Transport::Sse { .. }));
     }
 
     #[test]
-    fn test_transform_to_vscode_format() {
-        let server = create_test_server("test");
-        let servers = vec![&server];
+    fn test_adapt_servers_bridges_remotes_for_stdio_only_client() {
+        let local = create_test_server("fs");
+        let sse = create_test_sse_server("events");
+        let mut http = create_test_http_server("remote");
+        http.set_client_override(
+            "claude-desktop",
+            Some(ClientOverride {
+                headers: HashMap::from([("X-Team".to_string(), "core".to_string())]),
+                ..Default::default()
+            }),
+        );
+
+        let (adapted, warnings) =
+            adapt_servers_for_client(ClientId::ClaudeDesktop, &[&local, &sse, &http]);
+
+        assert_eq!(adapted.len(), 3);
+        assert_eq!(adapted[0].transport, local.transport);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("'events': Claude Desktop does not support SSE"));
+        assert!(warnings[0].ends_with("bridged through mcp-remote"));
+
+        match &adapted[1].transport {
+            Transport::Stdio { command, args, env } => {
+                assert_eq!(command, "npx");
+                assert_eq!(
+                    args,
+                    &[
+                        "-y",
+                        "mcp-remote",
+                        "https://api.example.com/events/sse",
+                        "--transport",
+                        "sse-only",
+                        "--header",
+                        "Authorization:${MCP_REMOTE_HEADER_AUTHORIZATION}",
+                    ]
+                );
+                assert_eq!(env["MCP_REMOTE_HEADER_AUTHORIZATION"], "Bearer token");
+            }
+            _ => panic!("Expected an mcp-remote bridge"),
+        }
 
-        let result = transform_to_vscode_format(&servers, None, &default_options()).unwrap();
+        // The client's header override is folded into the bridge
+        match &adapted[2].transport {
+            Transport::Stdio { args, env, .. } => {
+                assert!(args.contains(&"http-only".to_string()));
+                assert!(args.contains(&"X-Team:${MCP_REMOTE_HEADER_X_TEAM}".to_string()));
+                assert_eq!(env["MCP_REMOTE_HEADER_X_TEAM"], "core");
+            }
+            _ => panic!("Expected an mcp-remote bridge"),
+        }
+        assert!(adapted[2].client_overrides.is_empty());
 
-        assert!(result.get("mcp").is_some());
-        let mcp = result.get("mcp").unwrap();
-        assert!(mcp.get("servers").is_some());
+        let entry =
+            transform_server_to_standard(&adapted[1], &options_for(ClientId::ClaudeDesktop))
+                .unwrap();
+        assert_eq!(entry["command"], "npx");
+        assert!(entry.get("url").is_none());
     }
 
     #[test]
-    fn test_transform_to_vscode_format_preserves_existing() {
-        let server = create_test_server("test");
-        let servers = vec![&server];
+    fn test_adapt_servers_keeps_supported_and_skips_unbridgeable() {
+        let sse = create_test_sse_server("events");
+        let http = create_test_http_server("remote");
+
+        let (adapted, warnings) = adapt_servers_for_client(ClientId::Cursor, &[&sse, &http]);
+        assert_eq!(adapted.len(), 2);
+        assert!(warnings.is_empty());
+
+        // Codex speaks Streamable HTTP but not SSE
+        let (adapted, warnings) = adapt_servers_for_client(ClientId::Codex, &[&sse, &http]);
+        assert!(matches!(adapted[0].transport, Transport::Stdio { .. }));
+        assert!(matches!(adapted[1].transport, Transport::Http { .. }));
+        assert_eq!(warnings.len(), 1);
+
+        let no_env = ClientCapabilities {
+            sse: false,
+            env: false,
+            ..Default::default()
+        };
+        assert!(mcp_remote_bridge(&sse.transport, &no_env).is_none());
+        assert_eq!(transport_label(&sse.transport, &no_env), "SSE servers");
+    }
 
-        let existing = json!({
-            "editor.fontSize": 14,
-            "mcp": {
-                "enabled": true
+    fn acme_descriptor() -> ClientDescriptor {
+        serde_json::from_value(json!({
+            "id": "acme-agent",
+            "name": "Acme Agent",
+            "configPath": {"default": "~/.acme/agent.json"},
+            "serversPointer": "/agent/tools",
+            "fields": {
+                "command": "exec",
+                "env": "environment",
+                "url": "endpoint",
+                "type": "kind",
+                "stdioType": "local",
+                "httpType": "remote"
             }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_transform_to_descriptor_format() {
+        let descriptor = acme_descriptor();
+        let stdio = create_test_server("fs");
+        let remote = create_test_http_server("remote");
+        let existing = json!({
+            "agent": {"model": "fast", "tools": {"mine": {"exec": "node"}}},
+            "theme": "dark"
         });
 
-        let result = transform_to_vscode_format(&servers, Some(&existing), &default_options()).unwrap();
+        let result = transform_to_descriptor_format(
+            &descriptor,
+            &[&stdio, &remote],
+            Some(&existing),
+            &HashSet::new(),
+            &default_options(),
+        )
+        .unwrap();
+
+        assert_eq!(result["theme"], "dark");
+        assert_eq!(result["agent"]["model"], "fast");
+        let tools = &result["agent"]["tools"];
+        assert_eq!(tools["mine"]["exec"], "node");
+        assert_eq!(tools["fs"]["exec"], "npx");
+        assert_eq!(tools["fs"]["kind"], "local");
+        assert!(tools["fs"].get("command").is_none());
+        assert_eq!(tools["remote"]["endpoint"], "https://api.example.com/remote/mcp");
+        assert_eq!(tools["remote"]["kind"], "remote");
+        assert!(tools["remote"].get("type").is_none());
+
+        // Reading the entries back yields the original transports
+        for server in [&stdio, &remote] {
+            let entry = from_descriptor_entry(&descriptor.fields, &tools[server.name.as_str()]);
+            let parsed = parse_single_server(&server.name, &entry, ClientId::ClaudeCode).unwrap();
+            assert_eq!(parsed.transport, server.transport);
+        }
+    }
 
-        // Should preserve editor.fontSize
-        assert_eq!(result.get("editor.fontSize").unwrap(), 14);
-        // Should have mcp.servers
-        assert!(result.get("mcp").unwrap().get("servers").is_some());
+    #[test]
+    fn test_transform_to_descriptor_format_creates_pointer_path() {
+        let result = transform_to_descriptor_format(
+            &acme_descriptor(),
+            &[&create_test_server("fs")],
+            None,
+            &HashSet::new(),
+            &default_options(),
+        )
+        .unwrap();
+
+        assert_eq!(result["agent"]["tools"]["fs"]["exec"], "npx");
     }
 
     #[test]
-    fn test_transform_to_continue_format() {
-        let server = create_test_server("test");
-        let servers = vec![&server];
+    fn test_http_server_round_trips_through_every_client_format() {
+        let server = create_test_http_server("remote");
+
+        for client in ClientId::all() {
+            let entry = transform_server_to_standard(&server, &options_for(client)).unwrap();
+            let parsed = parse_single_server("remote", &entry, client).unwrap();
+            assert_eq!(parsed.transport, server.transport, "{}", client);
+        }
+    }
 
+    #[test]
+    fn test_diff_server_entry_protocol_change() {
+        let current = json!({"url": "https://api.example.com/mcp", "transport": "sse"});
+        let desired = json!({"url": "https://api.example.com/mcp", "type": "http"});
+
+        let change = diff_server_entry("remote", &current, &desired).unwrap();
+        assert!(change.url.is_none());
+        assert_eq!(
+            change.transport,
+            Some(FieldChange {
+                before: Some(json!("sse")),
+                after: Some(json!("http")),
+            })
+        );
+    }
+
+    #[test]
+    fn test_transform_applies_client_override() {
+        let mut server = create_test_server("fs");
+        server.set_client_override(
+            "cursor",
+            Some(ClientOverride {
+                args: Some(ArgsOverride::Append(vec!["--root".to_string(), "/work".to_string()])),
+                env: HashMap::from([("API_KEY".to_string(), "cursor-key".to_string())]),
+                headers: HashMap::new(),
+            }),
+        );
+
+        let cursor = transform_server_to_standard(&server, &options_for(ClientId::Cursor)).unwrap();
+        assert_eq!(cursor["args"], json!(["-y", "@test/fs", "--root", "/work"]));
+        assert_eq!(cursor["env"]["API_KEY"], "cursor-key");
+
+        let desktop = transform_server_to_standard(&server, &options_for(ClientId::ClaudeDesktop)).unwrap();
+        assert_eq!(desktop["args"], json!(["-y", "@test/fs"]));
+        assert!(desktop.get("env").is_none());
+    }
+
+    fn test_project(path: &str, targets: Vec<ProjectTarget>) -> ProjectSettings {
+        ProjectSettings::new(path.to_string(), "app".to_string(), targets)
+    }
+
+    #[test]
+    fn test_build_project_output_claude_code_local_scope() {
         let existing = json!({
-            "models": [{"title": "GPT-4"}],
-            "tabAutocompleteModel": {"title": "Codestral"}
+            "numStartups": 3,
+            "mcpServers": {"global": {"command": "global-cmd"}},
+            "projects": {
+                "/work/app": {
+                    "allowedTools": [],
+                    "mcpServers": {
+                        "user-server": {"command": "mine"},
+                        "stale": {"command": "old"}
+                    }
+                },
+                "/work/other": {"mcpServers": {"x": {"command": "x"}}}
+            }
         });
+        let server = create_test_server("fs");
+
+        let output = build_project_output(
+            "/work/app",
+            ProjectTarget::ClaudeCodeLocal,
+            &[&server],
+            Some(&existing),
+            &owned(&["stale"]),
+            &options_for(ClientId::ClaudeCode),
+        )
+        .unwrap();
+
+        let project_servers = &output["projects"]["/work/app"]["mcpServers"];
+        assert!(project_servers.get("fs").is_some());
+        assert!(project_servers.get("user-server").is_some());
+        assert!(project_servers.get("stale").is_none());
+
+        // Everything outside this project's server map is untouched
+        assert_eq!(output["numStartups"], 3);
+        assert_eq!(output["mcpServers"], existing["mcpServers"]);
+        assert_eq!(output["projects"]["/work/app"]["allowedTools"], json!([]));
+        assert_eq!(output["projects"]["/work/other"], existing["projects"]["/work/other"]);
+    }
 
-        let result = transform_to_continue_format(&servers, Some(&existing), &default_options()).unwrap();
+    #[test]
+    fn test_build_project_output_creates_missing_parents() {
+        let server = create_test_server("fs");
+
+        let output = build_project_output(
+            "/work/app",
+            ProjectTarget::ClaudeCodeLocal,
+            &[&server],
+            Some(&json!({"projects": {}})),
+            &HashSet::new(),
+            &options_for(ClientId::ClaudeCode),
+        )
+        .unwrap();
 
-        // Should preserve existing settings
-        assert!(result.get("models").is_some());
-        assert!(result.get("tabAutocompleteModel").is_some());
-        // Should have mcpServers
-        assert!(result.get("mcpServers").is_some());
+        assert_eq!(output["projects"]["/work/app"]["mcpServers"]["fs"]["command"], "npx");
     }
 
     #[test]
-    fn test_generate_warp_config() {
-        let server = create_test_server("test");
-        let servers = vec![&server];
+    fn test_sync_project_target_writes_workspace_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_string_lossy().to_string();
+        let project = test_project(&project_path, vec![ProjectTarget::McpJson, ProjectTarget::Vscode]);
+        let server = create_test_server("fs");
 
-        let result = generate_warp_config(&servers);
+        let mcp_json = sync_project_target(&project, ProjectTarget::McpJson, &[&server], 10);
+        assert!(mcp_json.success, "{:?}", mcp_json.error);
+        assert_eq!(mcp_json.servers_synced, 1);
+        assert!(mcp_json.backup_path.is_none());
 
-        assert!(result.contains("mcpServers"));
-        assert!(result.contains("test"));
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".mcp.json")).unwrap())
+                .unwrap();
+        assert_eq!(written["mcpServers"]["fs"]["command"], "npx");
+
+        let vscode = sync_project_target(&project, ProjectTarget::Vscode, &[&server], 10);
+        assert!(vscode.success, "{:?}", vscode.error);
+
+        let written: Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join(".vscode").join("mcp.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written["servers"]["fs"]["command"], "npx");
+        assert!(written.get("mcp").is_none());
     }
 
     #[test]
-    fn test_write_and_read_config_file() {
+    fn test_sync_project_target_refuses_secrets_in_shared_files() {
         let temp_dir = TempDir::new().unwrap();
-        let config_path = temp_dir.path().join("test.json");
+        let project_path = temp_dir.path().to_string_lossy().to_string();
+        let project = test_project(&project_path, vec![ProjectTarget::McpJson]);
 
-        let content = r#"{"test": "value"}"#;
-        write_config_file(&config_path, content).unwrap();
+        let mut server = create_test_server("fs");
+        if let Transport::Stdio { env, .. } = &mut server.transport {
+            env.insert("API_KEY".to_string(), "keychain:fs-key".to_string());
+        }
 
-        // Verify file exists and has correct content
-        let read_content = fs::read_to_string(&config_path).unwrap();
-        assert_eq!(read_content, content);
+        let result = sync_project_target(&project, ProjectTarget::McpJson, &[&server], 10);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("keychain"));
+        assert!(!temp_dir.path().join(".mcp.json").exists());
     }
 
-    #[cfg(unix)]
     #[test]
-    fn test_write_config_file_permissions() {
-        use std::os::unix::fs::PermissionsExt;
+    fn test_import_from_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_string_lossy().to_string();
+        fs::create_dir(temp_dir.path().join(".cursor")).unwrap();
+        fs::write(
+            temp_dir.path().join(".cursor").join("mcp.json"),
+            r#"{"mcpServers": {
+                "existing": {"command": "npx", "args": ["-y", "@test/existing"]},
+                "new-one": {"command": "uvx", "args": ["new-one"]}
+            }}"#,
+        )
+        .unwrap();
+
+        let mut config = McpHubConfig::new();
+        let existing = create_test_server("existing");
+        let existing_id = existing.id.to_string();
+        config.add_server(existing);
+        config
+            .projects
+            .push(test_project(&project_path, vec![ProjectTarget::Cursor]));
+
+        let result =
+            import_from_project(&mut config, &project_path, ProjectTarget::Cursor, false).unwrap();
+
+        assert_eq!(result.servers_imported, 1);
+        assert_eq!(result.skipped_existing, 1);
+        assert_eq!(result.server_names, vec!["new-one"]);
+
+        // Both servers now apply to the project, but the new one is not global
+        let project = config.get_project(&project_path).unwrap();
+        assert_eq!(project.servers.len(), 2);
+        assert!(project.servers.contains(&existing_id));
+        let imported = config.servers.iter().find(|s| s.name == "new-one").unwrap();
+        assert!(imported.enabled_clients.is_empty());
+
+        assert!(matches!(
+            import_from_project(&mut config, "/not/registered", ProjectTarget::Cursor, false),
+            Err(SyncError::ProjectNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_client_servers_overwrites_in_place() {
+        let mut config = McpHubConfig::new();
+        let mut existing = create_test_server("existing");
+        existing.enable_for_client("claude-code");
+        existing.set_client_override(
+            "claude-code",
+            Some(ClientOverride {
+                args: Some(ArgsOverride::Append(vec!["-v".to_string()])),
+                ..Default::default()
+            }),
+        );
+        let existing_id = existing.id;
+        config.add_server(existing);
+        let mut project = test_project("/work/app", vec![ProjectTarget::McpJson]);
+        project.servers.push(existing_id.to_string());
+        config.projects.push(project);
+
+        let entry = json!({"command": "uvx", "args": ["existing"]});
+        let parsed = || vec![parse_single_server("existing", &entry, ClientId::Cursor).unwrap()];
+
+        let result = merge_client_servers(&mut config, ClientId::Cursor, parsed(), false);
+        assert_eq!((result.servers_imported, result.skipped_existing), (0, 1));
+
+        let result = merge_client_servers(&mut config, ClientId::Cursor, parsed(), true);
+        assert_eq!(result.servers_imported, 1);
+        assert_eq!(config.servers.len(), 1);
+
+        let server = &config.servers[0];
+        assert_eq!(server.id, existing_id);
+        assert!(server.is_enabled_for_client("claude-code"));
+        assert!(server.is_enabled_for_client("cursor"));
+        assert!(server.client_overrides.contains_key("claude-code"));
+        match &server.transport {
+            Transport::Stdio { command, .. } => assert_eq!(command, "uvx"),
+            other => panic!("expected stdio transport, got {:?}", other),
+        }
+        let servers = &config.get_project("/work/app").unwrap().servers;
+        assert_eq!(servers, &vec![existing_id.to_string()]);
+    }
 
+    #[test]
+    fn test_import_from_project_overwrites_in_place() {
         let temp_dir = TempDir::new().unwrap();
-        let config_path = temp_dir.path().join("test.json");
+        let project_path = temp_dir.path().to_string_lossy().to_string();
+        fs::create_dir(temp_dir.path().join(".cursor")).unwrap();
+        fs::write(
+            temp_dir.path().join(".cursor").join("mcp.json"),
+            r#"{"mcpServers": {"existing": {"command": "uvx", "args": ["existing"]}}}"#,
+        )
+        .unwrap();
+
+        let mut config = McpHubConfig::new();
+        let mut existing = create_test_server("existing");
+        existing.enable_for_client("claude-code");
+        existing.set_client_options(
+            "cline",
+            Some(ClientOptions {
+                disabled: Some(true),
+                ..Default::default()
+            }),
+        );
+        let existing_id = existing.id;
+        config.add_server(existing);
+        config
+            .projects
+            .push(test_project(&project_path, vec![ProjectTarget::Cursor]));
+        let mut other = test_project("/work/other", vec![ProjectTarget::McpJson]);
+        other.servers.push(existing_id.to_string());
+        config.projects.push(other);
+
+        let result =
+            import_from_project(&mut config, &project_path, ProjectTarget::Cursor, true).unwrap();
+        assert_eq!(result.servers_imported, 1);
+        assert_eq!(result.server_names, vec!["existing"]);
+
+        // The same server now runs the project's command and keeps everything else
+        assert_eq!(config.servers.len(), 1);
+        let server = &config.servers[0];
+        assert_eq!(server.id, existing_id);
+        assert!(server.is_enabled_for_client("claude-code"));
+        assert!(server.client_options.contains_key("cline"));
+        match &server.transport {
+            Transport::Stdio { command, .. } => assert_eq!(command, "uvx"),
+            other => panic!("expected stdio transport, got {:?}", other),
+        }
 
-        write_config_file(&config_path, "{}").unwrap();
+        let id = existing_id.to_string();
+        assert_eq!(config.get_project(&project_path).unwrap().servers, vec![id.clone()]);
+        assert_eq!(config.get_project("/work/other").unwrap().servers, vec![id]);
+    }
 
-        let metadata = fs::metadata(&config_path).unwrap();
-        let mode = metadata.permissions().mode();
+    #[test]
+    fn test_record_project_sync_results() {
+        let mut config = McpHubConfig::new();
+        let server = create_test_server("fs");
+        let mut project = test_project("/work/app", vec![ProjectTarget::McpJson]);
+        project.servers.push(server.id.to_string());
+        config.add_server(server);
+        config.projects.push(project);
+
+        let results = vec![ProjectSyncResult {
+            project_path: "/work/app".to_string(),
+            target: ProjectTarget::McpJson,
+            success: true,
+            servers_synced: 1,
+            config_path: Some("/work/app/.mcp.json".to_string()),
+            backup_path: None,
+            error: None,
+        }];
+        record_project_sync_results(&mut config, &results);
 
-        // Check that file is 0600 (user read/write only)
-        assert_eq!(mode & 0o777, 0o600);
+        let project = config.get_project("/work/app").unwrap();
+        assert_eq!(project.managed_servers.get("mcp-json"), Some(&vec!["fs".to_string()]));
+        assert!(project.last_sync.is_some());
     }
 
     #[test]
-    fn test_create_backup() {
-        let temp_dir = TempDir::new().unwrap();
-        let config_path = temp_dir.path().join("test.json");
+    fn test_render_config_preserves_jsonc_outside_servers() {
+        let original = r#"{
+    // Keep my editor settings
+    "editor.fontSize": 14,
+    "mcp": {
+        "servers": {
+            "user-server": {"command": "mine"}, // added by hand
+        },
+    },
+}
+"#;
+        let existing = jsonc::parse(original).unwrap();
+        let server = create_test_server("fs");
+
+        let output = build_client_output(
+            ConfigFormat::Vscode,
+            &[&server],
+            Some(&existing),
+            &HashSet::new(),
+            &default_options(),
+        )
+        .unwrap();
+        let content =
+            render_config(Some(original), &output, &format_servers_pointer(ConfigFormat::Vscode))
+                .unwrap();
+
+        assert!(content.starts_with("{\n    // Keep my editor settings\n    \"editor.fontSize\": 14,"));
+        let written = jsonc::parse(&content).unwrap();
+        assert_eq!(written, output);
+    }
 
-        // Create original file
-        fs::write(&config_path, "original content").unwrap();
+    #[test]
+    fn test_render_config_without_existing_file() {
+        let output = json!({"mcpServers": {}});
+        let content = render_config(None, &output, &format_servers_pointer(ConfigFormat::Standard))
+            .unwrap();
+        assert_eq!(content, serde_json::to_string_pretty(&output).unwrap());
+    }
+
+    #[test]
+    fn test_transform_to_codex_format() {
+        let stdio = create_test_server("fs");
+        let remote = create_test_http_server("remote");
+
+        let result = build_client_output(
+            ConfigFormat::CodexToml,
+            &[&stdio, &remote],
+            None,
+            &HashSet::new(),
+            &options_for(ClientId::Codex),
+        )
+        .unwrap();
 
-        // Create backup
-        let backup_path = create_backup(&config_path).unwrap();
-        assert!(backup_path.is_some());
+        assert_eq!(result["mcp_servers"]["fs"]["command"], "npx");
+        let entry = &result["mcp_servers"]["remote"];
+        assert_eq!(entry["url"], "https://api.example.com/remote/mcp");
+        assert_eq!(entry["http_headers"]["Authorization"], "Bearer token");
+        assert!(entry.get("headers").is_none());
+        assert!(entry.get("type").is_none());
 
-        let backup = backup_path.unwrap();
-        assert!(backup.exists());
-        assert_eq!(fs::read_to_string(&backup).unwrap(), "original content");
+        let parsed = parse_single_server("remote", entry, ClientId::Codex).unwrap();
+        assert_eq!(parsed.transport, remote.transport);
     }
 
     #[test]
-    fn test_create_backup_no_file() {
+    fn test_codex_sync_preserves_rest_of_config_toml() {
+        let original = r#"# My Codex settings
+model = "o3"
+approval_policy = "on-request"
+
+[mcp_servers.hand-added]
+command = "node"
+args = ["mine.js"]
+
+[profiles.fast]
+model = "mini"
+"#;
         let temp_dir = TempDir::new().unwrap();
-        let config_path = temp_dir.path().join("nonexistent.json");
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(&config_path, original).unwrap();
+
+        let existing = read_existing_config(&config_path).unwrap().unwrap();
+        assert_eq!(existing["model"], "o3");
+
+        let server = create_test_server("fs");
+        let output = build_client_output(
+            ConfigFormat::CodexToml,
+            &[&server],
+            Some(&existing),
+            &HashSet::new(),
+            &options_for(ClientId::Codex),
+        )
+        .unwrap();
+        let content = render_toml_config(
+            Some(original),
+            &output,
+            &format_servers_pointer(ConfigFormat::CodexToml),
+        )
+        .unwrap();
+
+        assert!(content.starts_with("# My Codex settings\nmodel = \"o3\"\n"));
+        assert!(content.contains("[mcp_servers.hand-added]\ncommand = \"node\""));
+        assert!(content.contains("[mcp_servers.fs]"));
+        assert!(content.contains("[profiles.fast]\nmodel = \"mini\""));
 
-        let backup_path = create_backup(&config_path).unwrap();
-        assert!(backup_path.is_none());
+        let written = toml_config::parse(&content).unwrap();
+        assert_eq!(written, output);
     }
 
     #[test]
-    fn test_parse_single_server_stdio() {
-        let config = json!({
-            "command": "npx",
-            "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
-            "env": {"DEBUG": "true"}
-        });
-
-        let server = parse_single_server("filesystem", &config, ClientId::ClaudeCode).unwrap();
+    fn test_zed_sync_touches_only_context_servers() {
+        let original = r#"{
+  // Zed settings
+  "theme": "One Dark",
+  "context_servers": {
+    "hand-added": {
+      "command": {"path": "node", "args": ["mine.js"]},
+      "settings": {}
+    }
+  },
+  "vim_mode": true
+}
+"#;
+        let existing = jsonc::parse(original).unwrap();
+        let mut server = create_test_server("fs");
+        if let Transport::Stdio { env, .. } = &mut server.transport {
+            env.insert("LOG".to_string(), "debug".to_string());
+        }
 
-        assert_eq!(server.name, "filesystem");
-        assert!(server.is_enabled_for_client("claude-code"));
+        let output = build_client_output(
+            ConfigFormat::Zed,
+            &[&server],
+            Some(&existing),
+            &HashSet::new(),
+            &options_for(ClientId::Zed),
+        )
+        .unwrap();
+
+        let entry = &output["context_servers"]["fs"];
+        assert_eq!(entry["command"]["path"], "npx");
+        assert_eq!(entry["command"]["args"], json!(["-y", "@test/fs"]));
+        assert_eq!(entry["command"]["env"]["LOG"], "debug");
+        assert!(entry.get("args").is_none());
+
+        let content =
+            render_config(Some(original), &output, &format_servers_pointer(ConfigFormat::Zed))
+                .unwrap();
+        assert!(content.starts_with("{\n  // Zed settings\n  \"theme\": \"One Dark\","));
+        assert!(content.contains("\"vim_mode\": true"));
+        assert_eq!(jsonc::parse(&content).unwrap(), output);
+
+        let parsed = parse_single_server("fs", entry, ClientId::Zed).unwrap();
+        assert_eq!(parsed.transport, server.transport);
+        let standard = transform_server_to_standard(&server, &default_options()).unwrap();
+        assert!(diff_server_entry("fs", entry, &standard).is_none());
+    }
 
+    #[test]
+    fn test_parse_single_server_zed_flat_command() {
+        // Older Zed settings put the command fields at the top level
+        let entry = json!({"source": "custom", "command": "uvx", "args": ["mcp-server-git"]});
+        let server = parse_single_server("git", &entry, ClientId::Zed).unwrap();
         match &server.transport {
-            Transport::Stdio { command, args, env } => {
-                assert_eq!(command, "npx");
-                assert_eq!(args.len(), 3);
-                assert_eq!(env.get("DEBUG").unwrap(), "true");
+            Transport::Stdio { command, args, .. } => {
+                assert_eq!(command, "uvx");
+                assert_eq!(args, &vec!["mcp-server-git".to_string()]);
             }
             _ => panic!("Expected Stdio transport"),
         }
     }
 
     #[test]
-    fn test_parse_single_server_sse() {
-        let config = json!({
-            "url": "https://api.example.com/mcp",
-            "transport": "sse",
-            "headers": {"Authorization": "Bearer token"}
+    fn test_transform_to_goose_format() {
+        let stdio = create_test_server("fs");
+        let remote = create_test_http_server("remote");
+        let existing = json!({
+            "GOOSE_PROVIDER": "openai",
+            "extensions": {
+                "developer": {"bundled": true, "enabled": true, "name": "developer", "type": "builtin"},
+                "remote": {"enabled": false, "timeout": 60, "type": "streamable_http"}
+            }
         });
 
-        let server = parse_single_server("remote", &config, ClientId::Cursor).unwrap();
-
-        assert_eq!(server.name, "remote");
-        assert!(server.is_enabled_for_client("cursor"));
-
-        match &server.transport {
-            Transport::Sse { url, headers } => {
-                assert_eq!(url, "https://api.example.com/mcp");
-                assert_eq!(headers.get("Authorization").unwrap(), "Bearer token");
-            }
-            _ => panic!("Expected Sse transport"),
+        let result = build_client_output(
+            ConfigFormat::GooseYaml,
+            &[&stdio, &remote],
+            Some(&existing),
+            &HashSet::new(),
+            &options_for(ClientId::Goose),
+        )
+        .unwrap();
+
+        assert_eq!(result["GOOSE_PROVIDER"], "openai");
+        assert_eq!(result["extensions"]["developer"]["type"], "builtin");
+
+        let fs = &result["extensions"]["fs"];
+        assert_eq!(fs["cmd"], "npx");
+        assert_eq!(fs["type"], "stdio");
+        assert_eq!(fs["name"], "fs");
+        assert_eq!(fs["enabled"], true);
+        assert_eq!(fs["timeout"], GOOSE_EXTENSION_TIMEOUT);
+        assert!(fs.get("command").is_none());
+
+        // What the user set in Goose survives the sync
+        let remote_entry = &result["extensions"]["remote"];
+        assert_eq!(remote_entry["uri"], "https://api.example.com/remote/mcp");
+        assert_eq!(remote_entry["type"], "streamable_http");
+        assert_eq!(remote_entry["enabled"], false);
+        assert_eq!(remote_entry["timeout"], 60);
+
+        for server in [&stdio, &remote] {
+            let entry = &result["extensions"][server.name.as_str()];
+            let parsed = parse_single_server(&server.name, entry, ClientId::Goose).unwrap();
+            assert_eq!(parsed.transport, server.transport);
         }
+
+        // Builtin extensions are not importable servers
+        let builtin = &result["extensions"]["developer"];
+        assert!(parse_single_server("developer", builtin, ClientId::Goose).is_none());
     }
 
     #[test]
-    fn test_compute_checksum() {
-        let content1 = b"hello world";
-        let content2 = b"hello world";
-        let content3 = b"different";
-
-        let checksum1 = compute_checksum(content1);
-        let checksum2 = compute_checksum(content2);
-        let checksum3 = compute_checksum(content3);
+    fn test_goose_sync_preserves_rest_of_config_yaml() {
+        let original = "# Goose settings\nGOOSE_PROVIDER: openai\nextensions:\n  developer:\n    bundled: true\n    enabled: true\n    name: developer\n    type: builtin\nGOOSE_MODEL: gpt-4o # lead\n";
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, original).unwrap();
+
+        let existing = read_existing_config(&config_path).unwrap().unwrap();
+        let server = create_test_server("fs");
+        let output = build_client_output(
+            ConfigFormat::GooseYaml,
+            &[&server],
+            Some(&existing),
+            &HashSet::new(),
+            &options_for(ClientId::Goose),
+        )
+        .unwrap();
+        let content = render_yaml_config(
+            Some(original),
+            &output,
+            &format_servers_pointer(ConfigFormat::GooseYaml),
+        )
+        .unwrap();
 
-        assert_eq!(checksum1, checksum2);
-        assert_ne!(checksum1, checksum3);
+        assert!(content.starts_with("# Goose settings\nGOOSE_PROVIDER: openai\nextensions:\n"));
+        assert!(content.ends_with("GOOSE_MODEL: gpt-4o # lead\n"));
+        assert_eq!(yaml_config::parse(&content).unwrap(), output);
     }
 
     #[test]
-    fn test_sync_to_client_manual_only() {
-        let config = McpHubConfig::default();
-
-        let result = sync_to_client(ClientId::Warp, &config);
+    fn test_transform_to_continue_yaml_format() {
+        let server = create_test_server("fs");
+
+        let created = build_client_output(
+            ConfigFormat::ContinueYaml,
+            &[&server],
+            None,
+            &HashSet::new(),
+            &options_for(ClientId::Continue),
+        )
+        .unwrap();
+        assert_eq!(created["schema"], "v1");
+        assert_eq!(created["mcpServers"][0]["name"], "fs");
+        assert_eq!(created["mcpServers"][0]["command"], "npx");
+
+        let existing = json!({"name": "Mine", "models": [{"name": "gpt"}]});
+        let merged = build_client_output(
+            ConfigFormat::ContinueYaml,
+            &[&server],
+            Some(&existing),
+            &HashSet::new(),
+            &options_for(ClientId::Continue),
+        )
+        .unwrap();
+        assert_eq!(merged["name"], "Mine");
+        assert_eq!(merged["models"][0]["name"], "gpt");
+        assert!(merged["mcpServers"].is_array());
+        assert_eq!(
+            extract_servers_at(&merged, &format_servers_pointer(ConfigFormat::ContinueYaml))
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["fs"]
+        );
+    }
 
-        assert!(result.success);
-        assert!(result.manual_config.is_some());
-        assert!(result.error.is_none());
+    #[test]
+    fn test_render_toml_config_without_existing_file() {
+        let output = json!({"mcp_servers": {"fs": {"command": "npx"}}});
+        let content =
+            render_toml_config(None, &output, &format_servers_pointer(ConfigFormat::CodexToml))
+                .unwrap();
+        assert_eq!(content, "[mcp_servers.fs]\ncommand = \"npx\"\n");
     }
 }
@@ -0,0 +1,212 @@
+// src-tauri/src/services/toml_config.rs
+//! TOML support for client config files (e.g. Codex CLI's `config.toml`)
+//!
+//! The sync engine works on `serde_json::Value` documents. [`parse`] turns a
+//! TOML file into one, and [`replace_at`] writes a subtree back into the
+//! original text with `toml_edit`, so comments, ordering and every other
+//! table in the user's file are kept. Entries whose content did not change
+//! keep their original formatting too.
+
+use serde_json::Value;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table};
+
+/// Parse a TOML document into a JSON value
+pub fn parse(content: &str) -> Result<Value, toml_edit::de::Error> {
+    toml_edit::de::from_str(content)
+}
+
+/// Convert a JSON value to a TOML value (`None` for null, which TOML cannot express)
+fn to_toml_value(value: &Value) -> Option<toml_edit::Value> {
+    match value {
+        Value::Null => None,
+        Value::Bool(b) => Some((*b).into()),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(i.into()),
+            None => n.as_f64().map(Into::into),
+        },
+        Value::String(s) => Some(s.as_str().into()),
+        Value::Array(items) => {
+            let mut array = Array::new();
+            for item in items.iter().filter_map(to_toml_value) {
+                array.push(item);
+            }
+            Some(array.into())
+        }
+        Value::Object(map) => {
+            let mut table = InlineTable::new();
+            for (key, item) in map {
+                if let Some(item) = to_toml_value(item) {
+                    table.insert(key, item);
+                }
+            }
+            Some(table.into())
+        }
+    }
+}
+
+/// Convert a JSON object to a standard table, with nested objects kept inline
+fn to_table(map: &serde_json::Map<String, Value>) -> Table {
+    let mut table = Table::new();
+    for (key, value) in map {
+        if let Some(value) = to_toml_value(value) {
+            table.insert(key, Item::Value(value));
+        }
+    }
+    table
+}
+
+/// JSON form of a TOML item, used to tell whether an entry changed
+fn item_to_json(item: &Item) -> Option<Value> {
+    let mut doc = DocumentMut::new();
+    doc.insert("item", item.clone());
+    parse(&doc.to_string()).ok()?.get("item").cloned()
+}
+
+/// Build the table for a server collection, reusing unchanged entries from `existing`
+fn build_collection(map: &serde_json::Map<String, Value>, existing: Option<&Table>) -> Table {
+    let mut table = Table::new();
+    // Only the `[parent.child]` headers are written, never a bare `[parent]`
+    table.set_implicit(true);
+
+    for (name, entry) in map {
+        let reused = existing
+            .and_then(|t| t.get(name))
+            .filter(|item| item_to_json(item).as_ref() == Some(entry));
+
+        let item = match (reused, entry) {
+            (Some(item), _) => item.clone(),
+            (None, Value::Object(fields)) => Item::Table(to_table(fields)),
+            (None, other) => match to_toml_value(other) {
+                Some(value) => Item::Value(value),
+                None => continue,
+            },
+        };
+        table.insert(name, item);
+    }
+
+    table
+}
+
+/// Set the object at `pointer` (a list of table keys), touching nothing else.
+///
+/// Returns `None` if the document cannot be parsed, the pointer is empty or
+/// `value` is not an object.
+pub fn replace_at(content: &str, pointer: &[String], value: &Value) -> Option<String> {
+    let map = value.as_object()?;
+    let (last, parents) = pointer.split_last()?;
+    let mut doc: DocumentMut = content.parse().ok()?;
+
+    let mut table = doc.as_table_mut();
+    for key in parents {
+        if !table.get(key).is_some_and(|item| item.is_table()) {
+            let mut parent = Table::new();
+            parent.set_implicit(true);
+            table.insert(key, Item::Table(parent));
+        }
+        table = table.get_mut(key)?.as_table_mut()?;
+    }
+
+    let collection = build_collection(map, table.get(last).and_then(|item| item.as_table()));
+    table.insert(last, Item::Table(collection));
+
+    Some(doc.to_string())
+}
+
+/// Serialize a whole JSON document as TOML (used when there is no existing file)
+pub fn to_string(value: &Value) -> Option<String> {
+    let map = value.as_object()?;
+    let mut doc = DocumentMut::new();
+
+    for (key, item) in map {
+        match item {
+            Value::Object(children) => {
+                doc.insert(key, Item::Table(build_collection(children, None)));
+            }
+            other => {
+                if let Some(value) = to_toml_value(other) {
+                    doc.insert(key, Item::Value(value));
+                }
+            }
+        }
+    }
+
+    Some(doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CODEX_CONFIG: &str = r#"# Codex settings
+model = "o3"
+
+[mcp_servers.keep]
+command = "keep"  # hand-written
+
+[mcp_servers.old]
+command = "old"
+
+[profiles.fast]
+model = "mini"
+"#;
+
+    fn pointer(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let value = parse(CODEX_CONFIG).unwrap();
+        assert_eq!(value["model"], "o3");
+        assert_eq!(value["mcp_servers"]["keep"]["command"], "keep");
+        assert_eq!(value["profiles"]["fast"]["model"], "mini");
+    }
+
+    #[test]
+    fn test_replace_at_preserves_rest_of_file() {
+        let servers = json!({
+            "keep": {"command": "keep"},
+            "fs": {"command": "npx", "args": ["-y", "@test/fs"], "env": {"LOG": "debug"}}
+        });
+
+        let patched = replace_at(CODEX_CONFIG, &pointer(&["mcp_servers"]), &servers).unwrap();
+
+        assert!(patched.starts_with("# Codex settings\nmodel = \"o3\"\n"));
+        // Unchanged entry keeps its comment
+        assert!(patched.contains("command = \"keep\"  # hand-written"));
+        assert!(patched.contains("[mcp_servers.fs]\n"));
+        assert!(patched.contains("env = { LOG = \"debug\" }"));
+        assert!(!patched.contains("mcp_servers.old"));
+        assert!(patched.contains("[profiles.fast]\nmodel = \"mini\""));
+
+        let value = parse(&patched).unwrap();
+        assert_eq!(value["mcp_servers"], servers);
+    }
+
+    #[test]
+    fn test_replace_at_creates_missing_table() {
+        let patched = replace_at(
+            "model = \"o3\"\n",
+            &pointer(&["mcp_servers"]),
+            &json!({"fs": {"command": "npx"}}),
+        )
+        .unwrap();
+
+        assert_eq!(patched, "model = \"o3\"\n\n[mcp_servers.fs]\ncommand = \"npx\"\n");
+    }
+
+    #[test]
+    fn test_to_string() {
+        let doc = json!({"mcp_servers": {"fs": {"command": "npx", "args": ["-y"]}}});
+        let content = to_string(&doc).unwrap();
+
+        assert_eq!(content, "[mcp_servers.fs]\nargs = [\"-y\"]\ncommand = \"npx\"\n");
+        assert_eq!(parse(&content).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_replace_at_rejects_invalid_toml() {
+        assert!(replace_at("not = = toml", &pointer(&["mcp_servers"]), &json!({})).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+// src-tauri/src/services/yaml_config.rs
+//! YAML support for client config files (Goose, Continue's `config.yaml`)
+//!
+//! YAML libraries drop comments when re-serializing, so [`replace_at`]
+//! rewrites only the text of the top-level block holding the servers and
+//! leaves every other line of the user's file untouched.
+
+use serde_json::Value;
+
+/// Parse a YAML document into a JSON value (an empty document is `null`)
+pub fn parse(content: &str) -> Result<Value, serde_yaml::Error> {
+    serde_yaml::from_str(content)
+}
+
+/// Serialize a JSON value as a YAML document
+pub fn to_string(value: &Value) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(value)
+}
+
+/// Whether a line starts a new top-level node (a key or a document marker)
+fn is_top_level(line: &str) -> bool {
+    match line.chars().next() {
+        None | Some(' ' | '\t' | '\r' | '\n' | '#') => false,
+        // Block sequences may sit at the same indentation as their key
+        Some('-') => line.starts_with("---"),
+        Some(_) => true,
+    }
+}
+
+/// Whether a line is part of a block's content rather than spacing or a comment
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+/// Line range `[start, end)` of the top-level block for `key`
+fn find_block(lines: &[&str], key: &str) -> Option<(usize, usize)> {
+    let prefix = format!("{}:", key);
+    let start = lines.iter().position(|line| {
+        line.strip_prefix(&prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '\r', '\n']))
+    })?;
+
+    // Trailing blank lines and comments belong to whatever follows the block
+    let mut end = start + 1;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        if is_top_level(line) {
+            break;
+        }
+        if is_content(line) {
+            end = i + 1;
+        }
+    }
+
+    Some((start, end))
+}
+
+/// Set the top-level key `pointer[0]` to `value`, touching nothing else.
+///
+/// Returns `None` if the document cannot be parsed or the pointer is not a
+/// single key, in which case callers fall back to a full rewrite.
+pub fn replace_at(content: &str, pointer: &[String], value: &Value) -> Option<String> {
+    let [key] = pointer else {
+        return None;
+    };
+    if !parse(content).ok()?.is_object() {
+        return None;
+    }
+
+    let mut block = serde_json::Map::new();
+    block.insert(key.clone(), value.clone());
+    let rendered = to_string(&Value::Object(block)).ok()?;
+
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let patched = match find_block(&lines, key) {
+        Some((start, end)) => {
+            let mut patched = lines[..start].concat();
+            patched.push_str(&rendered);
+            patched.push_str(&lines[end..].concat());
+            patched
+        }
+        None => {
+            let mut patched = content.to_string();
+            if !patched.is_empty() && !patched.ends_with('\n') {
+                patched.push('\n');
+            }
+            patched.push_str(&rendered);
+            patched
+        }
+    };
+
+    // Never write a file that no longer parses
+    parse(&patched).ok()?;
+    Some(patched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GOOSE_CONFIG: &str = r#"# Goose settings
+GOOSE_PROVIDER: openai
+extensions:
+  developer:
+    bundled: true
+    enabled: true
+    name: developer
+    type: builtin
+
+  old:
+    cmd: old
+    enabled: true
+    name: old
+    type: stdio
+# Model for the lead worker
+GOOSE_MODEL: gpt-4o
+"#;
+
+    fn pointer(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let value = parse(GOOSE_CONFIG).unwrap();
+        assert_eq!(value["GOOSE_PROVIDER"], "openai");
+        assert_eq!(value["extensions"]["old"]["cmd"], "old");
+        assert_eq!(parse("").unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_replace_at_preserves_rest_of_file() {
+        let extensions = json!({"fs": {"cmd": "npx", "args": ["-y", "@test/fs"], "type": "stdio"}});
+
+        let patched = replace_at(GOOSE_CONFIG, &pointer(&["extensions"]), &extensions).unwrap();
+
+        assert!(patched.starts_with("# Goose settings\nGOOSE_PROVIDER: openai\nextensions:\n  fs:\n"));
+        assert!(patched.ends_with("# Model for the lead worker\nGOOSE_MODEL: gpt-4o\n"));
+        assert!(!patched.contains("old"));
+
+        let value = parse(&patched).unwrap();
+        assert_eq!(value["extensions"], extensions);
+        assert_eq!(value["GOOSE_MODEL"], "gpt-4o");
+    }
+
+    #[test]
+    fn test_replace_at_block_sequence_at_key_indent() {
+        let content = "name: Local\nmcpServers:\n- name: old\n  command: old\nmodels: []\n";
+        let servers = json!([{"name": "fs", "command": "npx"}]);
+
+        let patched = replace_at(content, &pointer(&["mcpServers"]), &servers).unwrap();
+
+        assert!(patched.starts_with("name: Local\nmcpServers:\n"));
+        assert!(patched.ends_with("models: []\n"));
+        assert_eq!(parse(&patched).unwrap()["mcpServers"], servers);
+    }
+
+    #[test]
+    fn test_replace_at_appends_missing_key() {
+        let patched =
+            replace_at("name: Local", &pointer(&["mcpServers"]), &json!([])).unwrap();
+        assert_eq!(patched, "name: Local\nmcpServers: []\n");
+    }
+
+    #[test]
+    fn test_replace_at_rejects_nested_pointer_and_invalid_yaml() {
+        let nested = pointer(&["a", "b"]);
+        assert!(replace_at("a: {}\n", &nested, &json!({})).is_none());
+        assert!(replace_at("a: [unclosed\n", &pointer(&["a"]), &json!({})).is_none());
+    }
+}
@@ -4,6 +4,8 @@ import { Sidebar } from "./components/layout/Sidebar";
 import { Toaster } from "@/components/ui/sonner";
 import { ErrorBoundary } from "./components/common/ErrorBoundary";
 import { useGlobalKeyboardShortcuts } from "./hooks/useKeyboard";
+import { useConfigChangeEvents } from "./hooks/useClients";
+import { useAutoSyncEvents } from "./hooks/useAutoSync";
 import { ThemeProvider } from "./components/ThemeProvider";
 import { Dashboard, Marketplace, Servers, Clients, Settings } from "./pages";
 import "./App.css";
@@ -14,6 +16,12 @@ function AppContent() {
   // Enable global keyboard shortcuts
   useGlobalKeyboardShortcuts();
 
+  // Pick up config files edited outside the app
+  useConfigChangeEvents();
+
+  // Refresh client data after background auto-syncs
+  useAutoSyncEvents();
+
   return (
     <div className="flex h-screen bg-gray-100 dark:bg-gray-900">
       <Sidebar />
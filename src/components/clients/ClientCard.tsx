@@ -1,5 +1,8 @@
 import { useState } from "react";
 import {
+  Bird,
+  Bot,
+  Cloud,
   Loader2,
   RefreshCw,
   Copy,
@@ -8,6 +11,8 @@ import {
   Code,
   Play,
   Wind,
+  Zap,
+  Sparkles,
   Server as ServerIcon,
 } from "lucide-react";
 import type { DetectedClient, ClientId } from "../../types";
@@ -17,6 +22,8 @@ interface ClientCardProps {
     syncEnabled: boolean;
     lastSync?: string;
     externallyModified: boolean;
+    modifiedServers: string[];
+    removedServers: string[];
     syncError?: string;
   };
   onSync: (clientId: ClientId) => void;
@@ -43,7 +50,18 @@ function ClientIcon({ clientId }: { clientId: ClientId }) {
       return <Play className={iconClass} />;
     case "windsurf":
       return <Wind className={iconClass} />;
+    case "zed":
+      return <Zap className={iconClass} />;
+    case "goose":
+      return <Bird className={iconClass} />;
+    case "gemini-cli":
+      return <Sparkles className={iconClass} />;
+    case "roo-code":
+      return <Bot className={iconClass} />;
+    case "amazon-q":
+      return <Cloud className={iconClass} />;
     case "warp":
+    case "codex":
       return <Terminal className={iconClass} />;
     default:
       return <ServerIcon className={iconClass} />;
@@ -107,8 +125,15 @@ export function ClientCard({
     }
 
     if (client.externallyModified) {
+      const drift = [
+        ...client.modifiedServers.map((name) => `${name} (edited)`),
+        ...client.removedServers.map((name) => `${name} (removed)`),
+      ];
       return (
-        <span className="px-2 py-1 text-xs rounded-full bg-amber-100 dark:bg-amber-900/30 text-amber-700 dark:text-amber-300">
+        <span
+          className="px-2 py-1 text-xs rounded-full bg-amber-100 dark:bg-amber-900/30 text-amber-700 dark:text-amber-300"
+          title={drift.length > 0 ? drift.join(", ") : undefined}
+        >
           Modified externally
         </span>
       );
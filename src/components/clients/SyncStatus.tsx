@@ -92,27 +92,36 @@ export function SyncStatus({ result, isLoading, onDismiss }: SyncStatusProps) {
             {/* Detailed results */}
             <div className="mt-2 space-y-1">
               {result.results.map((r) => (
-                <div
-                  key={r.clientId}
-                  className={`text-xs flex items-center gap-1 ${
-                    r.success
-                      ? "text-green-600 dark:text-green-400"
-                      : "text-red-600 dark:text-red-400"
-                  }`}
-                >
-                  {r.success ? (
-                    <Check className="h-3 w-3" />
-                  ) : (
-                    <X className="h-3 w-3" />
-                  )}
-                  <span>
-                    {r.clientId}
-                    {r.manualConfig && " (manual)"}
-                    {r.error && `: ${r.error}`}
-                    {r.success &&
-                      !r.manualConfig &&
-                      ` (${r.serversSynced} servers)`}
-                  </span>
+                <div key={r.clientId}>
+                  <div
+                    className={`text-xs flex items-center gap-1 ${
+                      r.success
+                        ? "text-green-600 dark:text-green-400"
+                        : "text-red-600 dark:text-red-400"
+                    }`}
+                  >
+                    {r.success ? (
+                      <Check className="h-3 w-3" />
+                    ) : (
+                      <X className="h-3 w-3" />
+                    )}
+                    <span>
+                      {r.clientId}
+                      {r.manualConfig && " (manual)"}
+                      {r.error && `: ${r.error}`}
+                      {r.success &&
+                        !r.manualConfig &&
+                        ` (${r.serversSynced} servers)`}
+                    </span>
+                  </div>
+                  {r.warnings?.map((warning) => (
+                    <p
+                      key={warning}
+                      className="ml-4 text-xs text-amber-600 dark:text-amber-400"
+                    >
+                      {warning}
+                    </p>
+                  ))}
                 </div>
               ))}
             </div>
@@ -35,6 +35,12 @@ function getClientName(clientId: string): string {
     continue: "Continue",
     windsurf: "Windsurf",
     warp: "Warp",
+    codex: "Codex CLI",
+    zed: "Zed",
+    goose: "Goose",
+    "gemini-cli": "Gemini CLI",
+    "roo-code": "Roo Code",
+    "amazon-q": "Amazon Q",
   };
   return names[clientId] || clientId;
 }
@@ -106,6 +112,12 @@ export function ServerCard({
     "continue",
     "windsurf",
     "warp",
+    "codex",
+    "zed",
+    "goose",
+    "gemini-cli",
+    "roo-code",
+    "amazon-q",
   ];
 
   return (
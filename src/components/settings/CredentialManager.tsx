@@ -29,12 +29,22 @@ function getCredentialsUsedByServer(server: McpServer): string[] {
       if (name) refs.push(name);
     }
   }
-  if (server.transport.type === "sse" && server.transport.headers) {
+  if (server.transport.type !== "stdio" && server.transport.headers) {
     for (const value of Object.values(server.transport.headers)) {
       const name = extractKeychainName(value);
       if (name) refs.push(name);
     }
   }
+  for (const override of Object.values(server.clientOverrides ?? {})) {
+    const values = [
+      ...Object.values(override.env ?? {}),
+      ...Object.values(override.headers ?? {}),
+    ];
+    for (const value of values) {
+      const name = extractKeychainName(value);
+      if (name && !refs.includes(name)) refs.push(name);
+    }
+  }
   return refs;
 }
 
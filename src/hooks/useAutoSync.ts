@@ -1,52 +1,36 @@
 // src/hooks/useAutoSync.ts
-import { useCallback, useRef } from "react";
+import { useEffect } from "react";
 import { useQueryClient } from "@tanstack/react-query";
-import { syncAllClients } from "../lib/tauri";
-import { useConfig } from "./useConfig";
+import { listen } from "@tauri-apps/api/event";
 import { notifySyncAllError } from "../lib/notifications";
+import type { AutoSyncProgress } from "../types";
 
-const AUTO_SYNC_DEBOUNCE_MS = 1000;
-
-export function useAutoSync() {
-  const { data: config } = useConfig();
+/**
+ * Follow the backend auto-sync, which runs after every config save when
+ * `autoSyncOnChanges` is on, and refresh client data once it finishes.
+ */
+export function useAutoSyncEvents() {
   const queryClient = useQueryClient();
-  const timeoutRef = useRef<number | null>(null);
-  const isSyncingRef = useRef(false);
-
-  const triggerAutoSync = useCallback(() => {
-    if (!config?.preferences.autoSyncOnChanges) {
-      return;
-    }
-
-    if (timeoutRef.current !== null) {
-      window.clearTimeout(timeoutRef.current);
-    }
 
-    timeoutRef.current = window.setTimeout(async () => {
-      timeoutRef.current = null;
+  useEffect(() => {
+    const unlisten = listen<AutoSyncProgress>("auto-sync", (event) => {
+      const progress = event.payload;
+      if (progress.phase !== "finished") return;
 
-      if (isSyncingRef.current) return;
-      isSyncingRef.current = true;
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
+      queryClient.invalidateQueries({ queryKey: ["config"] });
 
-      try {
-        const result = await syncAllClients();
-        queryClient.invalidateQueries({ queryKey: ["clients", "statuses"] });
-
-        if (result.failed > 0) {
-          notifySyncAllError(
-            new Error(
-              `Auto-sync completed with ${result.failed} client failure(s). See logs at ~/.mcp-nexus/logs/auto-sync.log.`
-            )
-          );
-        }
-      } catch (err) {
-        notifySyncAllError(err);
-      } finally {
-        isSyncingRef.current = false;
+      if (progress.result.failed > 0) {
+        notifySyncAllError(
+          new Error(
+            `Auto-sync completed with ${progress.result.failed} client failure(s). See logs at ~/.mcp-nexus/logs/auto-sync.log.`
+          )
+        );
       }
-    }, AUTO_SYNC_DEBOUNCE_MS);
-  }, [config?.preferences.autoSyncOnChanges, queryClient]);
+    });
 
-  return { triggerAutoSync };
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [queryClient]);
 }
-
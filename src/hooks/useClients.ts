@@ -1,4 +1,6 @@
+import { useEffect } from "react";
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { listen } from "@tauri-apps/api/event";
 import {
   detectClients,
   getAllClientStatuses,
@@ -15,8 +17,8 @@ import type {
   ClientSyncResult,
   SyncResult,
   ImportResult,
+  ConfigChangeEvent,
 } from "../types";
-import { useAutoSync } from "./useAutoSync";
 
 /** Hook for fetching detected clients */
 export function useDetectedClients() {
@@ -88,7 +90,6 @@ export function useSetClientSyncEnabled() {
 /** Hook for importing servers from a client */
 export function useImportClientServers() {
   const queryClient = useQueryClient();
-  const { triggerAutoSync } = useAutoSync();
 
   return useMutation<
     ImportResult,
@@ -101,7 +102,6 @@ export function useImportClientServers() {
       // Invalidate servers list after import
       queryClient.invalidateQueries({ queryKey: ["servers"] });
       queryClient.invalidateQueries({ queryKey: ["clients"] });
-      triggerAutoSync();
     },
   });
 }
@@ -121,6 +121,8 @@ export function useClients() {
       syncEnabled: status?.enabled ?? true,
       lastSync: status?.lastSync,
       externallyModified: status?.externallyModified ?? false,
+      modifiedServers: status?.modifiedServers ?? [],
+      removedServers: status?.removedServers ?? [],
       syncError: status?.syncError,
     };
   });
@@ -135,3 +137,23 @@ export function useClients() {
     },
   };
 }
+
+/** Refresh client and server data when a config file is edited outside the app */
+export function useConfigChangeEvents() {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    const unlisten = listen<ConfigChangeEvent>("config-changed", (event) => {
+      queryClient.invalidateQueries({ queryKey: ["clients"] });
+      if (!event.payload.clientId) {
+        // The central config changed: the backend already dropped its cache
+        queryClient.invalidateQueries({ queryKey: ["config"] });
+        queryClient.invalidateQueries({ queryKey: ["servers"] });
+      }
+    });
+
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [queryClient]);
+}
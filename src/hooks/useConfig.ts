@@ -1,13 +1,14 @@
 // src/hooks/useConfig.ts
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { getConfig, saveConfig } from "../lib/tauri";
-import type { McpHubConfig, UserPreferences } from "../types";
+import type { ConfigSnapshot, McpHubConfig, UserPreferences } from "../types";
 
 /** Fetch the full MCP hub configuration */
 export function useConfig() {
-  return useQuery<McpHubConfig, Error>({
+  return useQuery<ConfigSnapshot, Error, McpHubConfig>({
     queryKey: ["config"],
     queryFn: getConfig,
+    select: (snapshot) => snapshot.config,
     staleTime: 60_000,
   });
 }
@@ -18,8 +19,8 @@ export function useUpdatePreferences() {
 
   return useMutation<McpHubConfig, Error, Partial<UserPreferences>>({
     mutationFn: async (updates) => {
-      const current =
-        queryClient.getQueryData<McpHubConfig>(["config"]) ??
+      const { config: current, revision } =
+        queryClient.getQueryData<ConfigSnapshot>(["config"]) ??
         (await getConfig());
 
       const updated: McpHubConfig = {
@@ -30,11 +31,13 @@ export function useUpdatePreferences() {
         },
       };
 
-      await saveConfig(updated);
+      const next = await saveConfig(updated, revision);
+      queryClient.setQueryData<ConfigSnapshot>(["config"], { config: updated, revision: next });
       return updated;
     },
-    onSuccess: (updated) => {
-      queryClient.setQueryData(["config"], updated);
+    onError: () => {
+      // The file changed underneath us; refetch so the next attempt starts from it
+      queryClient.invalidateQueries({ queryKey: ["config"] });
     },
   });
 }
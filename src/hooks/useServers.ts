@@ -17,7 +17,6 @@ import type {
   UninstallServerResponse,
   SyncResult,
 } from "../types";
-import { useAutoSync } from "./useAutoSync";
 
 /** Hook for fetching all servers */
 export function useServerList() {
@@ -41,7 +40,6 @@ export function useServer(serverId: string | null) {
 /** Hook for updating a server */
 export function useUpdateServer() {
   const queryClient = useQueryClient();
-  const { triggerAutoSync } = useAutoSync();
 
   return useMutation<McpServer, Error, McpServer>({
     mutationFn: updateServer,
@@ -51,7 +49,6 @@ export function useUpdateServer() {
         old?.map((s) => (s.id === updatedServer.id ? updatedServer : s))
       );
       queryClient.invalidateQueries({ queryKey: ["servers", updatedServer.id] });
-      triggerAutoSync();
     },
   });
 }
@@ -75,7 +72,6 @@ export function useRemoveServer() {
 /** Hook for toggling a server's client status */
 export function useToggleServerClient() {
   const queryClient = useQueryClient();
-  const { triggerAutoSync } = useAutoSync();
 
   return useMutation<
     void,
@@ -87,7 +83,6 @@ export function useToggleServerClient() {
     onSuccess: () => {
       // Invalidate servers to refresh the list
       queryClient.invalidateQueries({ queryKey: ["servers"] });
-      triggerAutoSync();
     },
   });
 }
@@ -1,15 +1,31 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   McpServer,
+  ClientOverride,
+  ClientOptions,
   McpHubConfig,
+  ConfigSnapshot,
+  JournalSummary,
+  ServerDiagnostic,
   DetectedClient,
   ClientSyncStatus,
   ClientConfigInfo,
+  DescriptorLoadResult,
   DoctorReport,
   SyncResult,
   ClientSyncResult,
+  ClientSyncPreview,
+  SyncPreview,
+  BackupEntry,
+  RestoreResult,
   ImportResult,
   ClientId,
+  ProjectSettings,
+  ProjectTarget,
+  ProjectSyncResult,
+  Profile,
+  ProfileServerDiff,
+  ProfileActivation,
   MarketplaceServer,
   SearchServersParams,
   SearchResult,
@@ -39,14 +55,17 @@ export async function initializeConfig(): Promise<InitResult> {
   return invoke("initialize_config");
 }
 
-/** Get the full configuration */
-export async function getConfig(): Promise<McpHubConfig> {
+/** Get the full configuration and the revision it was read at */
+export async function getConfig(): Promise<ConfigSnapshot> {
   return invoke("get_config");
 }
 
-/** Save the full configuration */
-export async function saveConfig(config: McpHubConfig): Promise<void> {
-  return invoke("save_config", { config });
+/**
+ * Save the full configuration read at `revision`, returning the new revision.
+ * Fails if the config file changed since.
+ */
+export async function saveConfig(config: McpHubConfig, revision: string): Promise<string> {
+  return invoke("save_config", { config, revision });
 }
 
 /** Get all servers */
@@ -69,6 +88,11 @@ export async function updateServer(server: McpServer): Promise<McpServer> {
   return invoke("update_server", { server });
 }
 
+/** Check a server definition without saving it */
+export async function validateServer(server: McpServer): Promise<ServerDiagnostic[]> {
+  return invoke("validate_server", { server });
+}
+
 /** Remove a server by ID */
 export async function removeServer(serverId: string): Promise<McpServer> {
   return invoke("remove_server", { serverId });
@@ -83,6 +107,51 @@ export async function toggleServerClient(
   return invoke("toggle_server_client", { serverId, clientId, enabled });
 }
 
+/** Set (or clear, with null) a server's override for one client */
+export async function setServerClientOverride(
+  serverId: string,
+  clientId: string,
+  clientOverride: ClientOverride | null
+): Promise<McpServer> {
+  return invoke("set_server_client_override", {
+    serverId,
+    clientId,
+    clientOverride,
+  });
+}
+
+export async function setServerClientOptions(
+  serverId: string,
+  clientId: string,
+  options: ClientOptions | null
+): Promise<McpServer> {
+  return invoke("set_server_client_options", { serverId, clientId, options });
+}
+
+// Config history commands
+
+/** List journaled config changes, newest first */
+export async function getConfigHistory(): Promise<JournalSummary[]> {
+  return invoke("get_config_history");
+}
+
+/** Revert the most recent config change (null if there is nothing to undo) */
+export async function undoLastChange(): Promise<JournalSummary | null> {
+  return invoke("undo_last_change");
+}
+
+/** Re-apply the most recently undone change (null if there is nothing to redo) */
+export async function redoChange(): Promise<JournalSummary | null> {
+  return invoke("redo");
+}
+
+/** Restore the config as it was at an ISO timestamp */
+export async function restoreConfigTo(
+  timestamp: string
+): Promise<JournalSummary | null> {
+  return invoke("restore_config_to", { timestamp });
+}
+
 // Client detection commands (Phase 2.1)
 
 /** Detect installed AI clients */
@@ -109,6 +178,11 @@ export async function getClientConfig(
   return invoke("get_client_config", { clientId });
 }
 
+/** Reload user-defined client descriptors from ~/.mcp-nexus/clients.d/ */
+export async function reloadClientDescriptors(): Promise<DescriptorLoadResult> {
+  return invoke("reload_client_descriptors");
+}
+
 // Sync commands
 
 /** Sync configuration to a single client */
@@ -116,9 +190,26 @@ export async function syncClient(clientId: ClientId): Promise<ClientSyncResult>
   return invoke("sync_client", { clientId });
 }
 
-/** Sync configuration to all enabled clients */
-export async function syncAllClients(): Promise<SyncResult> {
-  return invoke("sync_all_clients");
+/**
+ * Sync configuration to all enabled clients.
+ * With `transactional`, a failed write rolls back every client already written.
+ */
+export async function syncAllClients(
+  transactional: boolean = false
+): Promise<SyncResult> {
+  return invoke("sync_all_clients", { transactional });
+}
+
+/** Preview what syncing a single client would change, without writing */
+export async function previewSyncClient(
+  clientId: ClientId
+): Promise<ClientSyncPreview> {
+  return invoke("preview_sync_client", { clientId });
+}
+
+/** Preview what syncing all enabled clients would change, without writing */
+export async function previewSyncAllClients(): Promise<SyncPreview> {
+  return invoke("preview_sync_all_clients");
 }
 
 /** Import servers from a client's configuration */
@@ -142,6 +233,92 @@ export async function setClientSyncEnabled(
   return invoke("set_client_sync_enabled", { clientId, enabled });
 }
 
+/** List the backup history of a client's config file, newest first */
+export async function listClientBackups(clientId: ClientId): Promise<BackupEntry[]> {
+  return invoke("list_client_backups", { clientId });
+}
+
+/** Restore a client's config from a backup (the current file is backed up first) */
+export async function restoreClientBackup(
+  clientId: ClientId,
+  backupId: string
+): Promise<RestoreResult> {
+  return invoke("restore_client_backup", { clientId, backupId });
+}
+
+// Project commands
+
+/** Register a project directory (targets are detected when omitted) */
+export async function addProject(
+  path: string,
+  name?: string,
+  targets?: ProjectTarget[]
+): Promise<ProjectSettings> {
+  return invoke("add_project", { path, name, targets });
+}
+
+/** Unregister a project, leaving its config files in place */
+export async function removeProject(path: string): Promise<ProjectSettings> {
+  return invoke("remove_project", { path });
+}
+
+/** Choose which servers apply to a project */
+export async function setProjectServers(
+  path: string,
+  serverIds: string[]
+): Promise<ProjectSettings> {
+  return invoke("set_project_servers", { path, serverIds });
+}
+
+/** Choose which project-level config files are written for a project */
+export async function setProjectTargets(
+  path: string,
+  targets: ProjectTarget[]
+): Promise<ProjectSettings> {
+  return invoke("set_project_targets", { path, targets });
+}
+
+/** Sync a project's servers to its project-level config files */
+export async function syncProject(path: string): Promise<ProjectSyncResult[]> {
+  return invoke("sync_project", { path });
+}
+
+/** Import servers from one of a project's config files */
+export async function importProjectServers(
+  path: string,
+  target: ProjectTarget,
+  overwriteExisting: boolean = false
+): Promise<ImportResult> {
+  return invoke("import_project_servers", { path, target, overwriteExisting });
+}
+
+// Profile commands
+
+/** List saved profiles */
+export async function listProfiles(): Promise<Profile[]> {
+  return invoke("list_profiles");
+}
+
+/** Save the current server enablement as a new profile */
+export async function createProfile(name: string): Promise<Profile> {
+  return invoke("create_profile", { name });
+}
+
+/** Delete a saved profile, leaving server enablement as it is */
+export async function deleteProfile(name: string): Promise<Profile> {
+  return invoke("delete_profile", { name });
+}
+
+/** Preview the enablement changes activating a profile would make */
+export async function diffProfile(name: string): Promise<ProfileServerDiff[]> {
+  return invoke("diff_profile", { name });
+}
+
+/** Apply a profile's server enablement, then sync every client */
+export async function activateProfile(name: string): Promise<ProfileActivation> {
+  return invoke("activate_profile", { name });
+}
+
 /** Run environment doctor check (Phase 1.4) */
 export async function runDoctor(): Promise<DoctorReport> {
   return invoke("run_doctor");
@@ -59,6 +59,9 @@ export function Clients() {
           failed: result.success ? 0 : 1,
           manualRequired: result.manualConfig ? 1 : 0,
           results: [result],
+          rolledBack: false,
+          rollbackErrors: [],
+          projectResults: [],
         });
         if (result.success) {
           notifyClientSyncSuccess(clientName);
@@ -1,6 +1,6 @@
 // Types matching Rust structs from src-tauri/src/models/
 
-/** Client IDs supported by the application */
+/** Client IDs supported by the application; any other string is a custom client */
 export type ClientId =
   | "claude-code"
   | "claude-desktop"
@@ -9,7 +9,14 @@ export type ClientId =
   | "vscode"
   | "continue"
   | "windsurf"
-  | "warp";
+  | "warp"
+  | "codex"
+  | "zed"
+  | "goose"
+  | "gemini-cli"
+  | "roo-code"
+  | "amazon-q"
+  | (string & {});
 
 /** Represents an MCP server's source/installation type */
 export type ServerSource =
@@ -32,6 +39,11 @@ export type Transport =
       type: "sse";
       url: string;
       headers?: Record<string, string>;
+    }
+  | {
+      type: "http";
+      url: string;
+      headers?: Record<string, string>;
     };
 
 /** Represents an MCP server configuration */
@@ -48,6 +60,37 @@ export interface McpServer {
   installedVersion?: string;
   sourceUrl?: string;
   tags: string[];
+  /** Per-client overrides, keyed by client ID */
+  clientOverrides?: Record<string, ClientOverride>;
+  /** Per-client entry options, keyed by client ID */
+  clientOptions?: Record<string, ClientOptions>;
+}
+
+/** How a client override changes the server's base args */
+export type ArgsOverride =
+  | { mode: "append"; values: string[] }
+  | { mode: "replace"; values: string[] };
+
+/** Per-client adjustments applied on top of a server's transport */
+export interface ClientOverride {
+  /** Args change (stdio only) */
+  args?: ArgsOverride;
+  /** Env vars merged over the base env (stdio only) */
+  env?: Record<string, string>;
+  /** Headers merged over the base headers (remote only) */
+  headers?: Record<string, string>;
+}
+
+/** Client-specific entry settings, written only to clients with a matching key */
+export interface ClientOptions {
+  /** Keep the entry in the client but turn it off (Cline, Roo Code, Windsurf) */
+  disabled?: boolean;
+  /** Tools the client may call without asking (Cline, Roo Code) */
+  autoApprove?: string[];
+  /** Tools hidden from the model (Windsurf) */
+  disabledTools?: string[];
+  /** Request timeout in seconds (Cline, Roo Code) */
+  timeout?: number;
 }
 
 /** Client-specific settings */
@@ -56,6 +99,12 @@ export interface ClientSettings {
   configPath: string;
   lastSync?: string;
   lastSyncChecksum?: string;
+  /** Server entries Nexus wrote to this client at last sync */
+  managedServers?: string[];
+  /** Checksum of each managed entry at last sync, keyed by server name */
+  serverHashes?: Record<string, string>;
+  /** Entry option keys Nexus wrote for each managed server at last sync */
+  optionKeys?: Record<string, string[]>;
 }
 
 /** User preferences for the application */
@@ -64,6 +113,8 @@ export interface UserPreferences {
   showNotifications: boolean;
   autoSyncOnChanges: boolean;
   registryRefreshInterval: number;
+  /** Number of timestamped backups to keep per client */
+  backupRetention: number;
 }
 
 /** The central MCP Hub configuration */
@@ -72,13 +123,104 @@ export interface McpHubConfig {
   servers: McpServer[];
   clients: Record<string, ClientSettings>;
   preferences: UserPreferences;
+  /** Registered project directories with project-level configs */
+  projects?: ProjectSettings[];
+  /** Saved server enablement snapshots */
+  profiles?: Profile[];
+  /** Name of the profile activated last */
+  activeProfile?: string;
+}
+
+/** A problem found in one field of a server definition */
+export interface ServerDiagnostic {
+  /** Field path, e.g. "transport.env.API_KEY"; errors block saving */
+  field: string;
+  severity: "error" | "warning" | "info";
+  message: string;
+}
+
+/** The central config and the revision it was read at */
+export interface ConfigSnapshot {
+  config: McpHubConfig;
+  /** Pass back to saveConfig so edits made since are not overwritten */
+  revision: string;
+}
+
+/** A journaled change to the central config */
+export type JournalSummary = {
+  id: number;
+  timestamp: string;
+  operation: string;
+} & (
+  | { action: "change" }
+  /** Reverted the entry with ID `entry` */
+  | { action: "undo"; entry: number }
+  /** Re-applied the entry with ID `entry` */
+  | { action: "redo"; entry: number }
+);
+
+/** A project-level MCP config file that can be synced */
+export type ProjectTarget = "mcp-json" | "claude-code-local" | "cursor" | "vscode";
+
+/** A registered project directory with its own server selection */
+export interface ProjectSettings {
+  /** Absolute path of the project root */
+  path: string;
+  name: string;
+  targets: ProjectTarget[];
+  /** IDs of the servers that apply to this project */
+  servers: string[];
+  /** Entry names written to each target at last sync, keyed by target */
+  managedServers?: Record<string, string[]>;
+  lastSync?: string;
+}
+
+/** Result of syncing one project-level config file */
+export interface ProjectSyncResult {
+  projectPath: string;
+  target: ProjectTarget;
+  success: boolean;
+  serversSynced: number;
+  configPath?: string;
+  backupPath?: string;
+  error?: string;
+}
+
+/** A named snapshot of which servers are enabled for which clients */
+export interface Profile {
+  name: string;
+  /** Client IDs each server is enabled for, keyed by server ID */
+  enabledClients: Record<string, string[]>;
+  createdAt: string;
+}
+
+/** How activating a profile would change one server's enablement */
+export interface ProfileServerDiff {
+  serverId: string;
+  serverName: string;
+  enabled: string[];
+  disabled: string[];
+}
+
+/** Result of activating a profile */
+export interface ProfileActivation {
+  changes: ProfileServerDiff[];
+  sync: SyncResult;
 }
 
 /** Sync mode for a client */
 export type SyncMode = "automatic" | "manualOnly";
 
 /** Config format used by a client */
-export type ConfigFormat = "standard" | "vscode" | "continue";
+export type ConfigFormat =
+  | "standard"
+  | "vscode"
+  | "continue"
+  | "codexToml"
+  | "zed"
+  | "continueYaml"
+  | "gooseYaml"
+  | "custom";
 
 /** Detected client information */
 export interface DetectedClient {
@@ -86,12 +228,36 @@ export interface DetectedClient {
   name: string;
   detected: boolean;
   configPath?: string;
+  /** Every location checked for the config on this platform, most preferred first */
+  configCandidates: string[];
   configExists: boolean;
   serverCount: number;
   syncMode: SyncMode;
   configFormat: ConfigFormat;
   error?: string;
   docsUrl?: string;
+  /** Transports and fields the client supports */
+  capabilities: ClientCapabilities;
+}
+
+/** Transports and fields a client's config supports */
+export interface ClientCapabilities {
+  stdio: boolean;
+  sse: boolean;
+  http: boolean;
+  headers: boolean;
+  env: boolean;
+}
+
+/** A watched config file changed on disk (the "config-changed" event) */
+export interface ConfigChangeEvent {
+  /** Client owning the file; absent for the central Nexus config */
+  clientId?: ClientId;
+  path: string;
+  added: string[];
+  removed: string[];
+  /** Set if the file no longer parses */
+  error?: string;
 }
 
 /** Client sync status */
@@ -101,6 +267,10 @@ export interface ClientSyncStatus {
   lastSync?: string;
   lastSyncChecksum?: string;
   externallyModified: boolean;
+  /** Managed servers edited outside Nexus since last sync */
+  modifiedServers: string[];
+  /** Managed servers removed outside Nexus since last sync */
+  removedServers: string[];
   syncError?: string;
 }
 
@@ -111,6 +281,43 @@ export interface ClientConfigInfo {
   rawConfig?: Record<string, unknown>;
 }
 
+/** Key names a custom client uses in a server entry */
+export interface FieldMapping {
+  command: string;
+  args: string;
+  env: string;
+  url: string;
+  headers: string;
+  type?: string | null;
+  stdioType?: string | null;
+  sseType?: string | null;
+  httpType?: string | null;
+}
+
+/** A user-defined client loaded from ~/.mcp-nexus/clients.d/ */
+export interface ClientDescriptor {
+  id: string;
+  name: string;
+  configPath: {
+    macos?: string;
+    linux?: string;
+    windows?: string;
+    default?: string;
+  };
+  /** JSON pointer to the server map, e.g. "/mcpServers" */
+  serversPointer: string;
+  fields: FieldMapping;
+  capabilities: ClientCapabilities;
+  docsUrl?: string;
+}
+
+/** Outcome of loading client descriptors */
+export interface DescriptorLoadResult {
+  directory: string;
+  clients: ClientDescriptor[];
+  errors: { path: string; message: string }[];
+}
+
 /** Client status with server count (legacy compatibility) */
 export interface ClientStatus {
   id: ClientId;
@@ -148,8 +355,18 @@ export interface ClientSyncResult {
   backupPath?: string;
   error?: string;
   manualConfig?: string;
+  /** Servers bridged through mcp-remote or skipped for the client's capabilities */
+  warnings: string[];
+  /** Names of the server entries written to the client's config */
+  syncedServers: string[];
 }
 
+/** Progress of a background auto-sync (the "auto-sync" event) */
+export type AutoSyncProgress =
+  | { phase: "started"; clients: ClientId[] }
+  | { phase: "clientSynced"; result: ClientSyncResult }
+  | { phase: "finished"; result: SyncResult };
+
 /** Result of syncing to all clients */
 export interface SyncResult {
   totalClients: number;
@@ -157,6 +374,75 @@ export interface SyncResult {
   failed: number;
   manualRequired: number;
   results: ClientSyncResult[];
+  /** Whether a transactional sync failed and restored already-written clients */
+  rolledBack: boolean;
+  /** Clients that could not be restored during a rollback */
+  rollbackErrors: string[];
+  /** Project-level configs synced alongside the clients */
+  projectResults: ProjectSyncResult[];
+}
+
+/** Before/after values of a single changed field */
+export interface FieldChange {
+  before?: unknown;
+  after?: unknown;
+}
+
+/** Key-level changes to env or headers (values omitted) */
+export interface KeyChanges {
+  added: string[];
+  removed: string[];
+  modified: string[];
+}
+
+/** Field-level diff of a server present on disk and in the central config */
+export interface ServerChange {
+  name: string;
+  command?: FieldChange;
+  args?: FieldChange;
+  url?: FieldChange;
+  /** Remote protocol change between "sse" and "http" */
+  transport?: FieldChange;
+  env?: KeyChanges;
+  headers?: KeyChanges;
+}
+
+/** Preview of what a sync would change in a single client */
+export interface ClientSyncPreview {
+  clientId: ClientId;
+  configPath?: string;
+  added: string[];
+  removed: string[];
+  changed: ServerChange[];
+  unchanged: string[];
+  error?: string;
+  manualConfig?: string;
+  /** Servers that would be bridged through mcp-remote or skipped */
+  warnings: string[];
+}
+
+/** Preview of a sync across all enabled clients */
+export interface SyncPreview {
+  totalClients: number;
+  clientsWithChanges: number;
+  results: ClientSyncPreview[];
+}
+
+/** A timestamped snapshot of a client's config file */
+export interface BackupEntry {
+  id: string;
+  clientId: ClientId;
+  path: string;
+  createdAt: string;
+  sizeBytes: number;
+}
+
+/** Result of restoring a client config from a backup */
+export interface RestoreResult {
+  clientId: ClientId;
+  restoredFrom: string;
+  configPath: string;
+  safetyBackup?: string;
 }
 
 /** Result of importing servers from a client */
@@ -259,7 +545,13 @@ export type InstallSource =
   | { type: "local"; path: string; command?: string; args?: string[] }
   | { type: "github"; repo: string; branch?: string; runCommand?: string }
   | { type: "docker"; image: string; dockerArgs?: string[] }
-  | { type: "remote"; url: string; headers?: Record<string, string> };
+  | {
+      type: "remote";
+      url: string;
+      headers?: Record<string, string>;
+      /** Wire protocol of the remote server (defaults to "sse") */
+      protocol?: "sse" | "http";
+    };
 
 /** Request to install a server */
 export interface InstallServerRequest {